@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::clock::{Clock, SystemClock};
+
+/// A short-lived cache for `GET` responses, keyed by the full request url.
+///
+/// Useful for dashboards that poll the same resources. `Cache-Control: no-store` and
+/// `no-cache` responses are never cached and a shorter `max-age` wins over the
+/// configured ttl. Any mutating request to a path evicts the cached responses under it.
+#[derive(Debug)]
+pub struct ResponseCache {
+    ttl: Duration,
+    max_entries: usize,
+    clock: Arc<dyn Clock>,
+    entries: Mutex<HashMap<String, (DateTime<Utc>, String)>>,
+}
+
+impl ResponseCache {
+    pub fn new(ttl: std::time::Duration) -> Self {
+        Self {
+            ttl: Duration::from_std(ttl).unwrap_or(Duration::MAX),
+            max_entries: 1024,
+            clock: Arc::new(SystemClock),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The most responses kept at once. Defaults to 1024.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub(crate) fn get(&self, url: &str) -> Option<String> {
+        let now = self.clock.now();
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(url)
+            .filter(|(expires_at, _)| *expires_at > now)
+            .map(|(_, body)| body.clone())
+    }
+
+    pub(crate) fn put(&self, url: &str, body: &str, cache_control: Option<&str>) {
+        let mut ttl = self.ttl;
+        if let Some(cache_control) = cache_control {
+            for directive in cache_control.split(',').map(str::trim) {
+                if directive == "no-store" || directive == "no-cache" {
+                    return;
+                }
+                if let Some(max_age) = directive
+                    .strip_prefix("max-age=")
+                    .and_then(|v| v.parse::<i64>().ok())
+                {
+                    ttl = ttl.min(Duration::seconds(max_age));
+                }
+            }
+        }
+        if ttl <= Duration::zero() {
+            return;
+        }
+
+        let now = self.clock.now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (expires_at, _)| *expires_at > now);
+        if entries.len() >= self.max_entries {
+            return;
+        }
+        entries.insert(url.to_string(), (now + ttl, body.to_string()));
+    }
+
+    /// Evicts every cached url whose path starts with `path`, ignoring a `.json` suffix.
+    pub(crate) fn invalidate(&self, path: &str) {
+        let prefix = path.trim_end_matches(".json");
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|url, _| {
+            url::Url::parse(url)
+                .map(|u| !u.path().starts_with(prefix))
+                .unwrap_or(false)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::Duration;
+
+    use crate::cache::ResponseCache;
+    use crate::clock::MockClock;
+
+    const URL: &str = "https://api.twilio.com/2010-04-01/Accounts/AC1/Messages/SM1.json";
+
+    #[test]
+    fn entries_expire_after_the_ttl() {
+        let clock = MockClock::default();
+        let cache =
+            ResponseCache::new(std::time::Duration::from_secs(10)).clock(Arc::new(clock.clone()));
+
+        cache.put(URL, "{}", None);
+        assert!(cache.get(URL).is_some());
+
+        clock.advance(Duration::seconds(11));
+        assert!(cache.get(URL).is_none());
+    }
+
+    #[test]
+    fn no_store_responses_are_not_cached() {
+        let cache = ResponseCache::new(std::time::Duration::from_secs(10));
+
+        cache.put(URL, "{}", Some("private, no-store"));
+
+        assert!(cache.get(URL).is_none());
+    }
+
+    #[test]
+    fn shorter_max_age_wins() {
+        let clock = MockClock::default();
+        let cache =
+            ResponseCache::new(std::time::Duration::from_secs(60)).clock(Arc::new(clock.clone()));
+
+        cache.put(URL, "{}", Some("max-age=5"));
+        clock.advance(Duration::seconds(6));
+
+        assert!(cache.get(URL).is_none());
+    }
+
+    #[test]
+    fn mutations_evict_the_resource() {
+        let cache = ResponseCache::new(std::time::Duration::from_secs(60));
+
+        cache.put(URL, "{}", None);
+        cache.invalidate("/2010-04-01/Accounts/AC1/Messages/SM1.json");
+
+        assert!(cache.get(URL).is_none());
+    }
+}
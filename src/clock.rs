@@ -0,0 +1,96 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+use futures_util::future::BoxFuture;
+
+/// A source of the current time.
+///
+/// Time-dependent logic (schedule validation, backoff, rate limiting, session expiry)
+/// reads the time and waits through this trait so tests can drive it with a
+/// [`MockClock`].
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Waits for `duration`, e.g. a retry's backoff.
+    fn sleep(&self, duration: std::time::Duration) -> BoxFuture<'static, ()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// The wall clock.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to, or when something sleeps on it, which
+/// returns at once. Clones share the same time.
+#[derive(Clone, Debug)]
+pub struct MockClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl MockClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new(DateTime::UNIX_EPOCH)
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: std::time::Duration) -> BoxFuture<'static, ()> {
+        self.advance(Duration::from_std(duration).unwrap_or(Duration::MAX));
+        Box::pin(std::future::ready(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::clock::{Clock, MockClock};
+
+    #[test]
+    fn mock_clock_clones_share_time() {
+        let clock = MockClock::default();
+        let other = clock.clone();
+
+        clock.advance(Duration::seconds(30));
+
+        assert_eq!(other.now(), clock.now());
+        assert_eq!(other.now().timestamp(), 30);
+    }
+
+    #[tokio::test]
+    async fn sleeping_on_a_mock_clock_advances_it() {
+        let clock = MockClock::default();
+
+        clock.sleep(std::time::Duration::from_secs(3600)).await;
+
+        assert_eq!(clock.now().timestamp(), 3600);
+    }
+}
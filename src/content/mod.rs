@@ -0,0 +1,466 @@
+mod variables;
+pub use variables::*;
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::cache::ResponseCache;
+use crate::clock::{Clock, SystemClock};
+use crate::error::ClientError;
+use crate::limiter::{ConcurrencyLimiter, Priority};
+use crate::make_request::{Transport, DEFAULT_MAX_RESPONSE_SIZE, DEFAULT_TIMEOUT, STUB_BASE_URL};
+use crate::retry::RetryPolicy;
+use crate::sampling::BodySampler;
+use crate::stub::StubResponses;
+use crate::upload::Upload;
+use crate::{Page, Params, TwilioDateTime};
+use reqwest::{Method, Url};
+use secrecy::SecretString;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// A Content API template.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ContentTemplate {
+    pub sid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_sid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub friendly_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// The content type definitions, e.g. `twilio/text` or `twilio/quick-reply`, keyed
+    /// by type.
+    #[serde(default)]
+    pub types: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_created: Option<TwilioDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_updated: Option<TwilioDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// The body for creating a Content API template.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NewContentTemplate {
+    pub friendly_name: String,
+    pub language: String,
+    /// Default values for the template's `{{1}}`-style placeholders.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, String>,
+    pub types: serde_json::Value,
+}
+
+/// The WhatsApp template category requested for approval.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TemplateCategory {
+    Utility,
+    Marketing,
+    Authentication,
+}
+
+/// The body for submitting a template for WhatsApp approval.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ApprovalRequest {
+    /// The template name WhatsApp will know it by; lowercase alphanumerics and underscores.
+    pub name: String,
+    pub category: TemplateCategory,
+}
+
+/// Where a template is in WhatsApp's review process.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalState {
+    Unsubmitted,
+    Received,
+    Pending,
+    Approved,
+    Rejected,
+    Paused,
+    Disabled,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WhatsAppApproval {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<TemplateCategory>,
+    pub status: ApprovalState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection_reason: Option<String>,
+}
+
+/// The approval status of a template across channels.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ApprovalStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whatsapp: Option<WhatsAppApproval>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClientBuilder {
+    base_url: Option<Url>,
+    account_sid: Option<SecretString>,
+    auth_token: Option<SecretString>,
+    timeout: Option<Duration>,
+    max_response_size: Option<usize>,
+    stub: Option<StubResponses>,
+    local_address: Option<IpAddr>,
+    cache: Option<Arc<ResponseCache>>,
+    sampler: Option<Arc<BodySampler>>,
+    hedge_after: Option<Duration>,
+    limiter: Option<ConcurrencyLimiter>,
+    retry: Option<RetryPolicy>,
+    priority: Priority,
+    read_only: bool,
+    clock: Option<Arc<dyn Clock>>,
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The Content API base url, normally `https://content.twilio.com`.
+    pub fn base_url(mut self, url: Url) -> Self {
+        self.base_url = Some(url);
+        self
+    }
+
+    pub fn account_sid(mut self, account_sid: SecretString) -> Self {
+        self.account_sid = Some(account_sid);
+        self
+    }
+
+    pub fn auth_token(mut self, token: SecretString) -> Self {
+        self.auth_token = Some(token);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The largest response body, in bytes, the client will buffer before giving up.
+    pub fn max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
+    /// Bind outgoing connections to `address`, e.g. to leave through the egress IP that
+    /// is allowed by the account's IP access control list.
+    pub fn local_address(mut self, address: IpAddr) -> Self {
+        self.local_address = Some(address);
+        self
+    }
+
+    /// Cache `GET` responses (message, service, template fetches) in `cache`.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Record a sample of redacted request/response pairs with `sampler`.
+    pub fn sampler(mut self, sampler: BodySampler) -> Self {
+        self.sampler = Some(Arc::new(sampler));
+        self
+    }
+
+    /// Send a second copy of a read that has no response after `delay`, and use
+    /// whichever copy succeeds first.
+    pub fn hedge_after(mut self, delay: Duration) -> Self {
+        self.hedge_after = Some(delay);
+        self
+    }
+
+    /// Share `limiter` with other clients to cap in-flight requests across all of them.
+    pub fn concurrency_limiter(mut self, limiter: ConcurrencyLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// The priority of this client's requests when a shared `concurrency_limiter` is
+    /// saturated. Defaults to [`Priority::Normal`].
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// The clock retries and hedged reads wait on. Defaults to the system clock.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Retry transient failures according to `policy`. Off by default.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Allow only reads, e.g. fetches and lists, and fail every other call with
+    /// [`ClientError::ReadOnlyMode`] before it reaches Twilio. Lets disaster recovery
+    /// drills and game days run against production credentials without changing templates.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Build a client that never touches the network and answers every call from
+    /// `responses`. Credentials and the base url become optional.
+    pub fn stub(mut self, responses: StubResponses) -> Self {
+        self.stub = Some(responses);
+        self
+    }
+
+    pub fn build(mut self) -> Result<Client, ClientError> {
+        if self.stub.is_some() {
+            // Stub clients never send anything, so placeholders are enough
+            self.base_url = self.base_url.or_else(|| Url::parse(STUB_BASE_URL).ok());
+            self.account_sid = self
+                .account_sid
+                .or_else(|| Some(SecretString::from("stub")));
+            self.auth_token = self.auth_token.or_else(|| Some(SecretString::from("stub")));
+        }
+        let base_url = self.base_url.ok_or_else(|| {
+            ClientError::Configuration("Twilio content base_url is required".to_string())
+        })?;
+        let account_sid = self.account_sid.ok_or_else(|| {
+            ClientError::Configuration("Twilio content account_sid is required".to_string())
+        })?;
+        let auth_token = self.auth_token.ok_or_else(|| {
+            ClientError::Configuration("Twilio content auth_token is required".to_string())
+        })?;
+
+        let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        let max_response_size = self.max_response_size.unwrap_or(DEFAULT_MAX_RESPONSE_SIZE);
+
+        let http_client = reqwest::Client::builder()
+            .timeout(timeout)
+            .local_address(self.local_address)
+            .build()
+            .map_err(ClientError::Reqwest)?;
+
+        Ok(Client {
+            transport: Transport {
+                http_client,
+                base_url,
+                account_sid,
+                auth_token,
+                timeout,
+                max_response_size,
+                stub: self.stub.map(Arc::new),
+                local_address: self.local_address,
+                cache: self.cache,
+                limiter: self.limiter,
+                dry_run: false,
+                span: None,
+                retry: self.retry,
+                priority: self.priority,
+                sampler: self.sampler,
+                hedge_after: self.hedge_after,
+                read_only: self.read_only,
+                service_name: "Twilio Content",
+                clock: self.clock.unwrap_or_else(|| Arc::new(SystemClock)),
+            },
+            schemas: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+}
+
+/// A client for managing Content API templates and their WhatsApp approval.
+#[derive(Clone, Debug)]
+pub struct Client {
+    transport: Transport,
+    // Variable schemas by content SID, shared between clones
+    schemas: Arc<Mutex<HashMap<String, Arc<VariableSchema>>>>,
+}
+
+impl Client {
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// A copy of the client whose calls are traced as children of `span`, e.g. one
+    /// carrying `order_id` or `tenant_id` fields, instead of the current span.
+    pub fn with_span(&self, span: tracing::Span) -> Self {
+        let mut client = self.clone();
+        client.transport.span = Some(span);
+        client
+    }
+
+    /// The local address outgoing connections are bound to, if any.
+    pub fn local_address(&self) -> Option<IpAddr> {
+        self.transport.local_address
+    }
+
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Content: Create template",
+        skip(self, template)
+    )]
+    pub async fn create_template(
+        &self,
+        template: &NewContentTemplate,
+    ) -> Result<ContentTemplate, ClientError> {
+        self.transport
+            .request_json(Method::POST, "/v1/Content", template)
+            .await
+    }
+
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Content: Fetch template",
+        skip(self)
+    )]
+    pub async fn fetch_template(&self, content_sid: &str) -> Result<ContentTemplate, ClientError> {
+        let url = format!("/v1/Content/{content_sid}");
+        self.transport
+            .request(Method::GET, &url, &Params::new())
+            .await
+    }
+
+    /// The variables template `content_sid` expects. Fetched once, then cached for the
+    /// life of the client; templates can't be edited after creation.
+    pub async fn variable_schema(
+        &self,
+        content_sid: &str,
+    ) -> Result<Arc<VariableSchema>, ClientError> {
+        if let Some(schema) = self.schemas.lock().unwrap().get(content_sid) {
+            return Ok(schema.clone());
+        }
+        let template = self.fetch_template(content_sid).await?;
+        let schema = Arc::new(VariableSchema::from_template(&template));
+        self.schemas
+            .lock()
+            .unwrap()
+            .insert(content_sid.to_string(), schema.clone());
+        Ok(schema)
+    }
+
+    /// Checks `variables` against template `content_sid` without sending anything, so
+    /// a mismatch fails with a descriptive error instead of a 400 from Twilio.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Content: Validate variables",
+        skip(self, variables)
+    )]
+    pub async fn validate_variables(
+        &self,
+        content_sid: &str,
+        variables: &serde_json::Value,
+    ) -> Result<(), ClientError> {
+        let schema = self.variable_schema(content_sid).await?;
+        schema.validate(variables)?;
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Content: Delete template",
+        skip(self)
+    )]
+    pub async fn delete_template(&self, content_sid: &str) -> Result<(), ClientError> {
+        let url = format!("/v1/Content/{content_sid}");
+        self.transport
+            .request(Method::DELETE, &url, &Params::new())
+            .await
+    }
+
+    /// Lists the first page of templates on the account.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Content: List templates",
+        skip(self)
+    )]
+    pub async fn list_templates(&self) -> Result<Page<ContentTemplate>, ClientError> {
+        self.transport
+            .request(Method::GET, "/v1/Content", &Params::new())
+            .await
+    }
+
+    /// Submits a template to WhatsApp for review.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Content: Request approval",
+        skip(self, request)
+    )]
+    pub async fn submit_for_approval(
+        &self,
+        content_sid: &str,
+        request: &ApprovalRequest,
+    ) -> Result<WhatsAppApproval, ClientError> {
+        let url = format!("/v1/Content/{content_sid}/ApprovalRequests/whatsapp");
+        self.transport
+            .request_json(Method::POST, &url, request)
+            .await
+    }
+
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Content: Fetch approval status",
+        skip(self)
+    )]
+    pub async fn approval_status(&self, content_sid: &str) -> Result<ApprovalStatus, ClientError> {
+        let url = format!("/v1/Content/{content_sid}/ApprovalRequests");
+        self.transport
+            .request(Method::GET, &url, &Params::new())
+            .await
+    }
+
+    /// Uploads a file to an arbitrary Twilio endpoint as `multipart/form-data`, e.g.
+    /// a regulatory supporting document. `path` may be an absolute url on another
+    /// Twilio host.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Content: Raw upload",
+        skip(self, upload)
+    )]
+    pub async fn raw_upload(
+        &self,
+        method: Method,
+        path: &str,
+        upload: Upload,
+    ) -> Result<serde_json::Value, ClientError> {
+        self.transport.upload(method, path, upload).await
+    }
+
+    /// Fetches the page after `page`, or `None` on the last page.
+    pub async fn next_page<T: DeserializeOwned>(
+        &self,
+        page: &Page<T>,
+    ) -> Result<Option<Page<T>>, ClientError> {
+        match &page.next_page_uri {
+            Some(uri) => self.transport.fetch_page(uri).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetches the page before `page`, or `None` on the first page.
+    pub async fn previous_page<T: DeserializeOwned>(
+        &self,
+        page: &Page<T>,
+    ) -> Result<Option<Page<T>>, ClientError> {
+        match &page.previous_page_uri {
+            Some(uri) => self.transport.fetch_page(uri).await.map(Some),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+
+use crate::content::ContentTemplate;
+use crate::error::ContentVariablesError;
+
+/// The variables a template expects: every `{{name}}` placeholder in its types, and
+/// which of them have a default value.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VariableSchema {
+    placeholders: BTreeSet<String>,
+    defaults: BTreeSet<String>,
+}
+
+impl VariableSchema {
+    pub fn from_template(template: &ContentTemplate) -> Self {
+        let mut placeholders = BTreeSet::new();
+        collect_placeholders(&template.types, &mut placeholders);
+        Self {
+            placeholders,
+            defaults: template.variables.keys().cloned().collect(),
+        }
+    }
+
+    pub fn placeholders(&self) -> impl Iterator<Item = &str> {
+        self.placeholders.iter().map(String::as_str)
+    }
+
+    /// Checks `variables`, the `ContentVariables` of a send, against the template.
+    /// Twilio takes string values only, and every placeholder without a default needs
+    /// one.
+    pub fn validate(&self, variables: &Value) -> Result<(), ContentVariablesError> {
+        let Value::Object(variables) = variables else {
+            return Err(ContentVariablesError::NotAnObject);
+        };
+
+        let missing: Vec<String> = self
+            .placeholders
+            .iter()
+            .filter(|name| !variables.contains_key(*name) && !self.defaults.contains(*name))
+            .cloned()
+            .collect();
+        let unknown: Vec<String> = variables
+            .keys()
+            .filter(|name| !self.placeholders.contains(*name))
+            .cloned()
+            .collect();
+        let not_strings: Vec<String> = variables
+            .iter()
+            .filter(|(_, value)| !value.is_string())
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        if missing.is_empty() && unknown.is_empty() && not_strings.is_empty() {
+            Ok(())
+        } else {
+            Err(ContentVariablesError::Mismatch {
+                missing,
+                unknown,
+                not_strings,
+            })
+        }
+    }
+}
+
+fn collect_placeholders(value: &Value, placeholders: &mut BTreeSet<String>) {
+    match value {
+        Value::String(text) => {
+            let mut rest = text.as_str();
+            while let Some(start) = rest.find("{{") {
+                let after = &rest[start + 2..];
+                let Some(end) = after.find("}}") else {
+                    break;
+                };
+                let name = after[..end].trim();
+                if !name.is_empty() {
+                    placeholders.insert(name.to_string());
+                }
+                rest = &after[end + 2..];
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_placeholders(item, placeholders)),
+        Value::Object(fields) => fields
+            .values()
+            .for_each(|field| collect_placeholders(field, placeholders)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use crate::content::{ContentTemplate, VariableSchema};
+    use crate::error::ContentVariablesError;
+
+    fn schema() -> VariableSchema {
+        VariableSchema::from_template(&ContentTemplate {
+            variables: HashMap::from([("2".to_string(), "friend".to_string())]),
+            types: json!({
+                "twilio/quick-reply": {
+                    "body": "Hi {{2}}, your order {{1}} has shipped",
+                    "actions": [{"title": "Track {{ 1 }}", "id": "track"}]
+                }
+            }),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn placeholders_are_found_in_nested_types() {
+        assert_eq!(schema().placeholders().collect::<Vec<_>>(), ["1", "2"]);
+    }
+
+    #[test]
+    fn defaults_cover_missing_variables() {
+        assert_eq!(schema().validate(&json!({"1": "A-100"})), Ok(()));
+    }
+
+    #[test]
+    fn mismatches_are_reported_together() {
+        let err = schema()
+            .validate(&json!({"2": 7, "3": "extra"}))
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            ContentVariablesError::Mismatch {
+                missing: vec!["1".to_string()],
+                unknown: vec!["3".to_string()],
+                not_strings: vec!["2".to_string()],
+            }
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::error::ClientError;
+use crate::task::spawn_named;
+
+/// The latest result of a [`HealthCheck`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Health {
+    /// No check has finished yet.
+    Unknown,
+    Healthy {
+        checked_at: DateTime<Utc>,
+    },
+    /// The credentials were rejected or Twilio couldn't be reached.
+    Unhealthy {
+        checked_at: DateTime<Utc>,
+        error: String,
+    },
+}
+
+impl Health {
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Health::Healthy { .. })
+    }
+}
+
+/// A background task that periodically checks the credentials and connectivity of a
+/// client, e.g. to feed a readiness probe. The task stops when this is dropped.
+///
+/// The task is named after the client, e.g. `Twilio SMS: Health check`, to find it in
+/// tokio-console.
+#[derive(Debug)]
+pub struct HealthCheck {
+    name: &'static str,
+    receiver: watch::Receiver<Health>,
+    task: JoinHandle<()>,
+}
+
+impl HealthCheck {
+    pub(crate) fn spawn<F, Fut>(name: &'static str, interval: Duration, probe: F) -> Self
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), ClientError>> + Send,
+    {
+        let (sender, receiver) = watch::channel(Health::Unknown);
+        let task = spawn_named(name, async move {
+            let mut ticks = tokio::time::interval(interval);
+            loop {
+                ticks.tick().await;
+                let health = match probe().await {
+                    Ok(()) => Health::Healthy {
+                        checked_at: Utc::now(),
+                    },
+                    Err(err) => {
+                        tracing::warn!("Twilio health check failed: {}", err);
+                        Health::Unhealthy {
+                            checked_at: Utc::now(),
+                            error: err.to_string(),
+                        }
+                    }
+                };
+                if sender.send(health).is_err() {
+                    // Every receiver is gone, including ours, so nobody is listening
+                    break;
+                }
+            }
+        });
+        Self {
+            name,
+            receiver,
+            task,
+        }
+    }
+
+    /// The name of the task.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// A receiver that is notified after every check.
+    pub fn subscribe(&self) -> watch::Receiver<Health> {
+        self.receiver.clone()
+    }
+
+    pub fn latest(&self) -> Health {
+        self.receiver.borrow().clone()
+    }
+
+    /// Whether the task has stopped, i.e. it was aborted or panicked.
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Stops the task without waiting for it. A check in flight is cancelled.
+    pub fn abort(&self) {
+        self.task.abort();
+    }
+
+    /// Stops the task and waits until it has, e.g. during a graceful shutdown.
+    pub async fn stop(mut self) {
+        self.task.abort();
+        // The task only ends cancelled or panicked; either way it is gone
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for HealthCheck {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// How serious a [`Finding`] is.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Severity {
+    /// Sends may work, but something looks off.
+    Warning,
+    /// Sends will fail until this is fixed.
+    Error,
+}
+
+/// One problem found by a client's `validate_configuration`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// The result of a client's `validate_configuration`, e.g. to fail a deploy before
+/// the first user is affected.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigReport {
+    findings: Vec<Finding>,
+}
+
+impl ConfigReport {
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+
+    /// Whether nothing was found that stops sends from working.
+    pub fn is_ok(&self) -> bool {
+        self.findings
+            .iter()
+            .all(|finding| finding.severity < Severity::Error)
+    }
+
+    pub(crate) fn error(&mut self, message: impl Into<String>) {
+        self.findings.push(Finding {
+            severity: Severity::Error,
+            message: message.into(),
+        });
+    }
+
+    pub(crate) fn warning(&mut self, message: impl Into<String>) {
+        self.findings.push(Finding {
+            severity: Severity::Warning,
+            message: message.into(),
+        });
+    }
+}
+
+impl fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.findings.is_empty() {
+            return write!(f, "configuration ok");
+        }
+        for (i, finding) in self.findings.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:?}: {}", finding.severity, finding.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Describes a failed credentials probe.
+pub(crate) fn credentials_finding(err: &ClientError) -> String {
+    match err {
+        ClientError::Authentication(err) => {
+            format!("credentials were rejected ({err}); check the account SID and auth token")
+        }
+        err => format!("could not reach Twilio to check the credentials: {err}"),
+    }
+}
@@ -1,6 +1,35 @@
+#[cfg(any(feature = "sms", feature = "verify", feature = "content"))]
+pub mod cache;
+pub mod clock;
+#[cfg(feature = "content")]
+pub mod content;
+#[cfg(any(feature = "sms", feature = "verify"))]
+pub mod health;
+#[cfg(any(feature = "sms", feature = "verify", feature = "content"))]
+pub mod limiter;
+#[cfg(any(feature = "sms", feature = "verify", feature = "content"))]
 mod make_request;
 mod models;
+#[cfg(feature = "openapi-models")]
+pub mod openapi;
+pub mod prelude;
+#[cfg(any(feature = "sms", feature = "verify", feature = "content"))]
+pub mod retry;
+#[cfg(any(feature = "sms", feature = "verify", feature = "content"))]
+pub mod sampling;
+#[cfg(feature = "sms")]
 pub mod sms;
+#[cfg(any(feature = "sms", feature = "verify", feature = "content"))]
+pub mod stub;
+#[cfg(any(feature = "sms", feature = "verify"))]
+mod task;
+#[cfg(feature = "webhooks")]
+pub mod twiml;
+#[cfg(any(feature = "sms", feature = "verify", feature = "content"))]
+pub mod upload;
+#[cfg(feature = "verify")]
 pub mod verify;
+#[cfg(feature = "webhooks")]
+pub mod webhooks;
 
 pub use models::*;
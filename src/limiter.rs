@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::error::ClientError;
+
+/// How urgent a client's requests are when the limiter is saturated. Set it with the
+/// client builder's `priority`, or for a single SMS with `SendOptions::priority`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Priority {
+    /// e.g. OTP codes, where latency matters more than throughput.
+    High,
+    #[default]
+    Normal,
+    /// e.g. notification bursts and campaigns, which can be shed first.
+    Low,
+}
+
+/// What a request does when every slot is taken.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OverloadPolicy {
+    /// Queue until a slot frees up.
+    #[default]
+    Wait,
+    /// Queue for at most the given time, then fail with [`ClientError::Overloaded`].
+    WaitFor(Duration),
+    /// Fail with [`ClientError::Overloaded`] straight away.
+    Reject,
+}
+
+/// Caps the requests in flight to Twilio across every client that shares it.
+///
+/// Clone the handle into each client builder; the clones share one budget, so the
+/// whole process stays under the account's concurrency limit.
+#[derive(Clone, Debug)]
+pub struct ConcurrencyLimiter {
+    max_in_flight: usize,
+    semaphore: Arc<Semaphore>,
+    // Slots only `Priority::High` requests may take
+    reserved: Arc<Semaphore>,
+    overload: HashMap<Priority, OverloadPolicy>,
+}
+
+impl ConcurrencyLimiter {
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            max_in_flight,
+            semaphore: Arc::new(Semaphore::new(max_in_flight)),
+            reserved: Arc::new(Semaphore::new(0)),
+            overload: HashMap::new(),
+        }
+    }
+
+    /// Keeps `slots` of the budget for [`Priority::High`] requests, so they don't
+    /// queue behind a burst of lower priority ones. High requests still use the
+    /// shared slots when those are free. Capped at `max_in_flight`.
+    ///
+    /// ```
+    /// # use twilio_client::limiter::ConcurrencyLimiter;
+    /// // Campaigns never take the last two slots, which are left for OTP codes
+    /// let limiter = ConcurrencyLimiter::new(10).reserve_for_high(2);
+    /// ```
+    pub fn reserve_for_high(mut self, slots: usize) -> Self {
+        let slots = slots.min(self.max_in_flight);
+        self.semaphore = Arc::new(Semaphore::new(self.max_in_flight - slots));
+        self.reserved = Arc::new(Semaphore::new(slots));
+        self
+    }
+
+    /// What requests of `priority` do when the limiter is saturated. Every priority
+    /// waits by default.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use twilio_client::limiter::{ConcurrencyLimiter, OverloadPolicy, Priority};
+    /// let limiter = ConcurrencyLimiter::new(10)
+    ///     .on_overload(Priority::High, OverloadPolicy::WaitFor(Duration::from_secs(2)))
+    ///     .on_overload(Priority::Low, OverloadPolicy::Reject);
+    /// ```
+    pub fn on_overload(mut self, priority: Priority, policy: OverloadPolicy) -> Self {
+        self.overload.insert(priority, policy);
+        self
+    }
+
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight
+    }
+
+    /// The number of requests currently holding a slot.
+    pub fn in_flight(&self) -> usize {
+        self.max_in_flight - self.semaphore.available_permits() - self.reserved.available_permits()
+    }
+
+    pub(crate) async fn acquire(
+        &self,
+        priority: Priority,
+    ) -> Result<OwnedSemaphorePermit, ClientError> {
+        let high = priority == Priority::High;
+        if let Ok(permit) = self.semaphore.clone().try_acquire_owned() {
+            return Ok(permit);
+        }
+        if high {
+            if let Ok(permit) = self.reserved.clone().try_acquire_owned() {
+                return Ok(permit);
+            }
+        }
+        // High requests take whichever slot frees up first
+        let acquire = async {
+            if high {
+                tokio::select! {
+                    permit = self.semaphore.clone().acquire_owned() => permit,
+                    permit = self.reserved.clone().acquire_owned() => permit,
+                }
+            } else {
+                self.semaphore.clone().acquire_owned().await
+            }
+        };
+        let permit = match self.overload.get(&priority).copied().unwrap_or_default() {
+            OverloadPolicy::Wait => acquire.await,
+            OverloadPolicy::WaitFor(deadline) => tokio::time::timeout(deadline, acquire)
+                .await
+                .map_err(|_| ClientError::Overloaded)?,
+            OverloadPolicy::Reject => return Err(ClientError::Overloaded),
+        };
+        Ok(permit.expect("concurrency limiter semaphore is never closed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use claim::{assert_err, assert_ok};
+
+    use crate::limiter::{ConcurrencyLimiter, OverloadPolicy, Priority};
+
+    #[tokio::test]
+    async fn saturated_limiter_sheds_by_priority() {
+        let limiter = ConcurrencyLimiter::new(1)
+            .on_overload(Priority::Low, OverloadPolicy::Reject)
+            .on_overload(
+                Priority::High,
+                OverloadPolicy::WaitFor(Duration::from_millis(10)),
+            );
+        let _held = limiter.acquire(Priority::Normal).await.unwrap();
+
+        assert_err!(limiter.acquire(Priority::Low).await);
+        assert_err!(limiter.acquire(Priority::High).await);
+    }
+
+    #[tokio::test]
+    async fn free_slots_are_granted_to_every_priority() {
+        let limiter = ConcurrencyLimiter::new(1).on_overload(Priority::Low, OverloadPolicy::Reject);
+
+        let permit = assert_ok!(limiter.acquire(Priority::Low).await);
+        assert_eq!(limiter.in_flight(), 1);
+        drop(permit);
+    }
+
+    #[tokio::test]
+    async fn reserved_slots_are_kept_for_high_priority() {
+        let limiter = ConcurrencyLimiter::new(2)
+            .reserve_for_high(1)
+            .on_overload(Priority::Low, OverloadPolicy::Reject)
+            .on_overload(Priority::Normal, OverloadPolicy::Reject)
+            .on_overload(Priority::High, OverloadPolicy::Reject);
+
+        let _low = assert_ok!(limiter.acquire(Priority::Low).await);
+        assert_err!(limiter.acquire(Priority::Normal).await);
+        let _high = assert_ok!(limiter.acquire(Priority::High).await);
+        assert_eq!(limiter.in_flight(), 2);
+        assert_err!(limiter.acquire(Priority::High).await);
+    }
+
+    #[tokio::test]
+    async fn queued_high_priority_takes_the_first_free_slot() {
+        let limiter = ConcurrencyLimiter::new(2).reserve_for_high(1);
+        let low = limiter.acquire(Priority::Low).await.unwrap();
+        let _high = limiter.acquire(Priority::High).await.unwrap();
+
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire(Priority::High).await.map(drop) }
+        });
+        tokio::task::yield_now().await;
+        drop(low);
+
+        assert_ok!(tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .unwrap()
+            .unwrap());
+    }
+}
@@ -1,59 +1,517 @@
-use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::error::ClientError;
-use reqwest::Url;
+use crate::cache::ResponseCache;
+use crate::clock::Clock;
+use crate::error::{AuthenticationError, ClientError, VerifyError};
+use crate::limiter::{ConcurrencyLimiter, Priority};
+use crate::retry::{Idempotency, RetryPolicy};
+use crate::sampling::BodySampler;
+use crate::stub::StubResponses;
+use crate::upload::Upload;
+use crate::{Page, Params};
+use reqwest::{Method, Request, RequestBuilder, StatusCode, Url};
 use secrecy::{ExposeSecret, SecretString};
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 
-pub(crate) async fn make_request<T: DeserializeOwned>(
-    http_client: &reqwest::Client,
-    urls: (&Url, &str),
-    account_sid: &SecretString,
-    auth_token: &SecretString,
-    timeout: Duration,
-    body: &HashMap<&str, String>,
-    service_name: &str,
-) -> Result<T, ClientError> {
-    let account_sid = account_sid.expose_secret();
-
-    let url = urls
-        .0
-        .join(urls.1)
-        .map_err(|e| ClientError::Configuration(format!("{service_name}: invalid URL: {}", e)))?;
-
-    let req = http_client
-        .post(url.as_str())
-        .basic_auth(account_sid, Some(auth_token.expose_secret()))
-        .form(body)
-        .build()?;
-
-    let resp = http_client.execute(req).await.map_err(|err| {
-        tracing::error!("{service_name}: failed to send sms: {}", err);
-        if err.is_timeout() {
-            ClientError::Timeout(timeout.as_secs())
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 1024 * 1024;
+pub(crate) const STUB_BASE_URL: &str = "http://stub.invalid";
+
+/// The authenticated HTTP transport shared by all the API clients.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    not(all(feature = "sms", feature = "verify", feature = "content")),
+    allow(dead_code)
+)]
+pub(crate) struct Transport {
+    pub(crate) http_client: reqwest::Client,
+    pub(crate) base_url: Url,
+    pub(crate) account_sid: SecretString,
+    pub(crate) auth_token: SecretString,
+    pub(crate) timeout: Duration,
+    pub(crate) max_response_size: usize,
+    pub(crate) service_name: &'static str,
+    pub(crate) stub: Option<Arc<StubResponses>>,
+    pub(crate) local_address: Option<IpAddr>,
+    pub(crate) cache: Option<Arc<ResponseCache>>,
+    pub(crate) limiter: Option<ConcurrencyLimiter>,
+    pub(crate) dry_run: bool,
+    pub(crate) span: Option<tracing::Span>,
+    pub(crate) retry: Option<RetryPolicy>,
+    pub(crate) priority: Priority,
+    pub(crate) sampler: Option<Arc<BodySampler>>,
+    pub(crate) hedge_after: Option<Duration>,
+    pub(crate) read_only: bool,
+    pub(crate) clock: Arc<dyn Clock>,
+}
+
+// Not every client uses every kind of request, so parts go unused when only some of
+// the client features are enabled.
+#[cfg_attr(
+    not(all(feature = "sms", feature = "verify", feature = "content")),
+    allow(dead_code)
+)]
+impl Transport {
+    /// Sends `params` to `path` (relative to the base url) and parses the JSON response.
+    ///
+    /// Params are sent as the query string for `GET` and `DELETE` requests and as a
+    /// form-encoded body otherwise.
+    pub(crate) async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        params: &Params,
+    ) -> Result<T, ClientError> {
+        let idempotency = if method == Method::GET || method == Method::DELETE {
+            Idempotency::Safe
         } else {
-            ClientError::Reqwest(err)
+            Idempotency::Unsafe
+        };
+        self.request_idempotent(method, path, params, idempotency)
+            .await
+    }
+
+    /// Like [`Transport::request`], retrying according to `idempotency` instead of the
+    /// method's default.
+    pub(crate) async fn request_idempotent<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        params: &Params,
+        idempotency: Idempotency,
+    ) -> Result<T, ClientError> {
+        let req = self.builder(method.clone(), path)?;
+        let req = if method == Method::GET || method == Method::DELETE {
+            req.query(params)
+        } else {
+            req.form(params)
+        };
+        self.execute_idempotent(req, idempotency).await
+    }
+
+    /// Sends `body` as JSON to `path` and parses the JSON response. Used by the newer
+    /// APIs, such as Content, that don't take form bodies.
+    pub(crate) async fn request_json<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        method: Method,
+        path: &str,
+        body: &B,
+    ) -> Result<T, ClientError> {
+        let req = self.builder(method, path)?.json(body);
+        self.execute(req).await
+    }
+
+    /// Sends `upload` as a multipart form to `path` and parses the JSON response.
+    pub(crate) async fn upload<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        upload: Upload,
+    ) -> Result<T, ClientError> {
+        let req = self.builder(method, path)?.multipart(upload.into_form()?);
+        self.execute(req).await
+    }
+
+    /// Fetches a page link from a list response. Links are either relative to the base
+    /// url (2010 API) or absolute (newer APIs) and already carry the query string.
+    pub(crate) async fn fetch_page<T: DeserializeOwned>(
+        &self,
+        uri: &str,
+    ) -> Result<Page<T>, ClientError> {
+        let req = self.builder(Method::GET, uri)?;
+        self.execute_idempotent(req, Idempotency::Safe).await
+    }
+
+    /// The parent for a call's span: the span attached with `with_span`, unless the call
+    /// is nested inside another call on the same client (e.g. `check` calling `verify`),
+    /// which keeps its contextual parent.
+    pub(crate) fn parent_span(&self) -> Option<tracing::Id> {
+        let current = tracing::Span::current();
+        let nested = current
+            .metadata()
+            .is_some_and(|m| m.name().starts_with(self.service_name));
+        match &self.span {
+            Some(span) if !nested => span.id(),
+            _ => current.id(),
         }
-    })?;
+    }
 
-    let status_code = resp.status();
-    let message = resp.text().await.map_err(|err| {
-        tracing::error!("{service_name}: failed to read response body: {}", err);
-        ClientError::Reqwest(err)
-    })?;
+    /// Sends an authenticated `GET` to `path`, skipping the cache, to check that the
+    /// credentials are accepted. Stub clients always pass.
+    pub(crate) async fn probe(&self, path: &str) -> Result<(), ClientError> {
+        if self.stub.is_some() {
+            return Ok(());
+        }
+        let req = self.builder(Method::GET, path)?.build()?;
+        self.send::<serde_json::Value>(req).await.map(|_| ())
+    }
 
-    if status_code.is_success() {
-        serde_json::from_str(&message).map_err(|err| {
-            tracing::error!("{service_name}: failed to parse response: {}", err);
+    /// Stands in for a request in dry-run mode: waits for a limiter permit like a real
+    /// request would, logs what would have been sent, and parses `response` instead.
+    pub(crate) async fn simulate<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        response: serde_json::Value,
+    ) -> Result<T, ClientError> {
+        let service_name = self.service_name;
+        self.check_writable(&method)?;
+        let _permit = match &self.limiter {
+            Some(limiter) => Some(limiter.acquire(self.priority).await?),
+            None => None,
+        };
+        tracing::info!("{service_name}: dry run, skipping {method} {path}");
+        serde_json::from_value(response).map_err(|err| {
+            tracing::error!("{service_name}: failed to parse dry-run response: {}", err);
             ClientError::Serde(err)
         })
-    } else if status_code.as_str() == "401" {
-        Err(ClientError::Authentication(message))
-    } else {
-        Err(ClientError::ServerResponse {
-            status_code,
-            message,
-        })
+    }
+
+    /// Fails requests that could change anything, which is any but a `GET`, when the
+    /// client is read-only.
+    fn check_writable(&self, method: &Method) -> Result<(), ClientError> {
+        if self.read_only && method != Method::GET {
+            tracing::warn!(
+                "{}: read-only mode, rejecting {method} request",
+                self.service_name
+            );
+            return Err(ClientError::ReadOnlyMode);
+        }
+        Ok(())
+    }
+
+    fn builder(&self, method: Method, path: &str) -> Result<RequestBuilder, ClientError> {
+        let service_name = self.service_name;
+        self.check_writable(&method)?;
+        let account_sid = self.account_sid.expose_secret();
+
+        let url = self.base_url.join(path).map_err(|e| {
+            ClientError::Configuration(format!("{service_name}: invalid URL: {}", e))
+        })?;
+
+        Ok(self
+            .http_client
+            .request(method, url.as_str())
+            .basic_auth(account_sid, Some(self.auth_token.expose_secret())))
+    }
+
+    async fn execute<T: DeserializeOwned>(&self, req: RequestBuilder) -> Result<T, ClientError> {
+        self.execute_idempotent(req, Idempotency::Unsafe).await
+    }
+
+    async fn execute_idempotent<T: DeserializeOwned>(
+        &self,
+        req: RequestBuilder,
+        idempotency: Idempotency,
+    ) -> Result<T, ClientError> {
+        let service_name = self.service_name;
+        let req = req.build()?;
+
+        if let Some(stub) = &self.stub {
+            if let Some(latency) = stub.next_latency() {
+                tokio::time::sleep(latency.min(self.timeout)).await;
+                if latency > self.timeout {
+                    return Err(ClientError::Timeout(self.timeout.as_secs()));
+                }
+            }
+            let body = stub.response_for(req.method(), req.url().path());
+            return serde_json::from_value(body.clone()).map_err(|err| {
+                tracing::error!("{service_name}: failed to parse stub response: {}", err);
+                ClientError::Serde(err)
+            });
+        }
+
+        let is_get = req.method() == Method::GET;
+        let url = req.url().clone();
+        if let (Some(cache), true) = (&self.cache, is_get) {
+            if let Some(body) = cache.get(url.as_str()) {
+                return serde_json::from_str(&body).map_err(ClientError::Serde);
+            }
+        }
+
+        let Some(policy) = &self.retry else {
+            return self.send_hedged(req, idempotency).await;
+        };
+        let mut attempt = 1;
+        loop {
+            // Bodies are always buffered, so this only fails for streamed uploads
+            let Some(retry) = req.try_clone() else {
+                return self.send_hedged(req, idempotency).await;
+            };
+            match self.send_hedged(retry, idempotency).await {
+                Err(err) if policy.should_retry(attempt, &err, idempotency) => {
+                    tracing::warn!(
+                        "{service_name}: attempt {attempt} failed, retrying: {}",
+                        err
+                    );
+                    self.clock.sleep(policy.delay(attempt)).await;
+                    attempt += 1;
+                }
+                resp => return resp,
+            }
+        }
+    }
+
+    /// Sends `req` like [`Transport::send`], plus a second copy if it is a read and has
+    /// no response after `hedge_after`. The first success wins; the other copy is
+    /// dropped.
+    async fn send_hedged<T: DeserializeOwned>(
+        &self,
+        req: Request,
+        idempotency: Idempotency,
+    ) -> Result<T, ClientError> {
+        // Checks are safe to repeat, but each copy counts against the verification's
+        // attempts; and racing writes gains nothing
+        let (Some(delay), Idempotency::Safe, true) =
+            (self.hedge_after, idempotency, req.method() == Method::GET)
+        else {
+            return self.send(req).await;
+        };
+        let Some(hedge) = req.try_clone() else {
+            return self.send(req).await;
+        };
+
+        let first = self.send::<T>(req);
+        tokio::pin!(first);
+        tokio::select! {
+            resp = &mut first => return resp,
+            _ = self.clock.sleep(delay) => {}
+        }
+        tracing::debug!(
+            "{}: no response after {delay:?}, sending a hedged request",
+            self.service_name
+        );
+        let second = self.send::<T>(hedge);
+        tokio::pin!(second);
+        tokio::select! {
+            resp = &mut first => match resp {
+                Ok(value) => Ok(value),
+                Err(_) => second.await,
+            },
+            resp = &mut second => match resp {
+                Ok(value) => Ok(value),
+                Err(_) => first.await,
+            },
+        }
+    }
+
+    /// Sends `req` once and parses the response.
+    async fn send<T: DeserializeOwned>(&self, req: Request) -> Result<T, ClientError> {
+        let service_name = self.service_name;
+        let is_get = req.method() == Method::GET;
+        let url = req.url().clone();
+        // What's needed to record the exchange, if the sampler picks it
+        let sample = self
+            .sampler
+            .as_ref()
+            .filter(|sampler| sampler.should_sample(url.path()))
+            .map(|sampler| {
+                let body = req
+                    .body()
+                    .and_then(|body| body.as_bytes())
+                    .map(<[u8]>::to_vec);
+                (sampler, req.method().to_string(), body)
+            });
+        let _permit = match &self.limiter {
+            Some(limiter) => Some(limiter.acquire(self.priority).await?),
+            None => None,
+        };
+        let resp = self.http_client.execute(req).await.map_err(|err| {
+            tracing::error!("{service_name}: failed to send request: {}", err);
+            if err.is_timeout() {
+                ClientError::Timeout(self.timeout.as_secs())
+            } else {
+                ClientError::Reqwest(err)
+            }
+        })?;
+
+        let status_code = resp.status();
+        let retry_after = resp
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_secs);
+        let cache_control = resp
+            .headers()
+            .get(reqwest::header::CACHE_CONTROL)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let message = self.read_body(resp).await?;
+        if let Some((sampler, method, body)) = sample {
+            sampler.record(
+                service_name,
+                &method,
+                &url,
+                body.as_deref(),
+                status_code.as_u16(),
+                &message,
+            );
+        }
+
+        if status_code.is_success() {
+            if let Some(cache) = &self.cache {
+                if is_get {
+                    cache.put(url.as_str(), &message, cache_control.as_deref());
+                } else {
+                    cache.invalidate(url.path());
+                }
+            }
+            // 204 No Content (e.g. deletes) parses as `null`, so `()` responses work
+            let body = if message.trim().is_empty() {
+                "null"
+            } else {
+                &message
+            };
+            serde_json::from_str(body).map_err(|err| {
+                tracing::error!("{service_name}: failed to parse response: {}", err);
+                ClientError::Serde(err)
+            })
+        } else if status_code == StatusCode::UNAUTHORIZED {
+            Err(ClientError::Authentication(AuthenticationError::from_body(
+                &message,
+            )))
+        } else if let Some(err) = VerifyError::from_body(&message, retry_after) {
+            Err(ClientError::Verify(err))
+        } else if status_code.is_server_error()
+            && serde_json::from_str::<serde_json::Value>(&message).is_err()
+        {
+            Err(ClientError::UpstreamUnavailable {
+                status_code,
+                message: summarize_body(&message),
+            })
+        } else {
+            Err(ClientError::ServerResponse {
+                status_code,
+                message,
+            })
+        }
+    }
+
+    /// Opens a pooled connection to the base url so the first real request skips DNS
+    /// resolution and the TLS handshake. Any HTTP status counts as success.
+    pub(crate) async fn warm_up(&self) -> Result<(), ClientError> {
+        let service_name = self.service_name;
+        if self.stub.is_some() {
+            return Ok(());
+        }
+        self.http_client
+            .head(self.base_url.as_str())
+            .send()
+            .await
+            .map_err(|err| {
+                tracing::error!("{service_name}: failed to warm up connection: {}", err);
+                if err.is_timeout() {
+                    ClientError::Timeout(self.timeout.as_secs())
+                } else {
+                    ClientError::Reqwest(err)
+                }
+            })?;
+        Ok(())
+    }
+
+    /// Buffers the response body, refusing anything larger than `max_response_size`.
+    async fn read_body(&self, mut resp: reqwest::Response) -> Result<String, ClientError> {
+        let service_name = self.service_name;
+        let limit = self.max_response_size;
+
+        if resp.content_length().is_some_and(|len| len > limit as u64) {
+            tracing::error!("{service_name}: response body exceeds {limit} bytes");
+            return Err(ClientError::ResponseTooLarge(limit));
+        }
+
+        // Content-Length can be absent or wrong, so enforce the limit while reading too.
+        let mut body = Vec::new();
+        while let Some(chunk) = resp.chunk().await.map_err(|err| {
+            tracing::error!("{service_name}: failed to read response body: {}", err);
+            ClientError::Reqwest(err)
+        })? {
+            if body.len() + chunk.len() > limit {
+                tracing::error!("{service_name}: response body exceeds {limit} bytes");
+                return Err(ClientError::ResponseTooLarge(limit));
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+}
+
+const MAX_SUMMARY_LEN: usize = 200;
+
+/// Reduces a non-JSON body such as an HTML error page to its text, on one line and at
+/// most `MAX_SUMMARY_LEN` characters, so it stays readable in logs and alerts.
+fn summarize_body(body: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.char_indices().nth(MAX_SUMMARY_LEN) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::make_request::summarize_body;
+
+    #[test]
+    fn html_bodies_are_reduced_to_their_text() {
+        let body = "<html>\n<head><title>503 Service Unavailable</title></head>\n\
+                    <body><h1>Down for maintenance</h1></body></html>";
+
+        assert_eq!(
+            summarize_body(body),
+            "503 Service Unavailable Down for maintenance"
+        );
+    }
+
+    #[test]
+    fn long_bodies_are_truncated() {
+        let summary = summarize_body(&"a".repeat(1000));
+
+        assert_eq!(summary.len(), 203);
+    }
+}
+
+#[cfg(all(test, feature = "sms", feature = "verify"))]
+mod fuzz_tests {
+    use crate::sms::SendSmsResponse;
+    use crate::verify::{TwilioRequestResponse, TwilioVerifyResponse};
+
+    #[quickcheck_macros::quickcheck]
+    fn arbitrary_bodies_never_panic_when_parsed(body: String) -> bool {
+        let _ = serde_json::from_str::<SendSmsResponse>(&body);
+        let _ = serde_json::from_str::<TwilioRequestResponse>(&body);
+        let _ = serde_json::from_str::<TwilioVerifyResponse>(&body);
+        true
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn arbitrary_field_values_never_panic_when_parsed(values: Vec<(String, String)>) -> bool {
+        let object: serde_json::Map<String, serde_json::Value> = values
+            .into_iter()
+            .map(|(k, v)| (k, serde_json::Value::String(v)))
+            .collect();
+        let body = serde_json::Value::Object(object).to_string();
+
+        let _ = serde_json::from_str::<SendSmsResponse>(&body);
+        let _ = serde_json::from_str::<TwilioRequestResponse>(&body);
+        let _ = serde_json::from_str::<TwilioVerifyResponse>(&body);
+        true
     }
 }
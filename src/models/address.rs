@@ -0,0 +1,175 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::ParseError;
+use crate::models::Phone;
+
+/// A `From`/`To` address as it appears in Twilio webhooks and message resources.
+///
+/// Twilio prefixes non-SMS channels (`whatsapp:+254...`, `messenger:1234`); bare
+/// values are phone numbers, short codes or alphanumeric sender IDs.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Address {
+    Sms(Phone),
+    WhatsApp(Phone),
+    /// A Facebook Messenger page-scoped ID.
+    Messenger(String),
+    /// A 3 to 8 digit short code.
+    ShortCode(String),
+    /// An alphanumeric sender ID, e.g. `MyBrand`.
+    Alphanumeric(String),
+    /// Any other `channel:id` address.
+    Other {
+        channel: String,
+        id: String,
+    },
+}
+
+impl Address {
+    pub fn parse(address: &str) -> Result<Address, ParseError> {
+        let address = address.trim();
+        if address.is_empty() {
+            return Err(ParseError("address is empty".to_string()));
+        }
+
+        match address.split_once(':') {
+            Some(("whatsapp", number)) => {
+                Phone::parse_with_no_country(number).map(Address::WhatsApp)
+            }
+            Some(("messenger", id)) => Ok(Address::Messenger(id.to_string())),
+            Some((channel, id)) => Ok(Address::Other {
+                channel: channel.to_string(),
+                id: id.to_string(),
+            }),
+            None if address.starts_with('+') => {
+                Phone::parse_with_no_country(address).map(Address::Sms)
+            }
+            None if address.len() <= 8 && address.bytes().all(|b| b.is_ascii_digit()) => {
+                Ok(Address::ShortCode(address.to_string()))
+            }
+            None if address
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b' ') =>
+            {
+                Ok(Address::Alphanumeric(address.to_string()))
+            }
+            None => Err(ParseError(format!(
+                "{address} is not a known address format"
+            ))),
+        }
+    }
+
+    /// The phone number behind an SMS or WhatsApp address.
+    pub fn phone(&self) -> Option<&Phone> {
+        match self {
+            Address::Sms(phone) | Address::WhatsApp(phone) => Some(phone),
+            _ => None,
+        }
+    }
+
+    /// The channel the address belongs to, e.g. `sms` or `whatsapp`.
+    pub fn channel(&self) -> &str {
+        match self {
+            Address::Sms(_) | Address::ShortCode(_) | Address::Alphanumeric(_) => "sms",
+            Address::WhatsApp(_) => "whatsapp",
+            Address::Messenger(_) => "messenger",
+            Address::Other { channel, .. } => channel,
+        }
+    }
+}
+
+impl FromStr for Address {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Address::parse(s)
+    }
+}
+
+impl fmt::Display for Address {
+    /// Formats the address the way Twilio expects it in `From`/`To`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Address::Sms(phone) => write!(f, "{}", phone.e164_number()),
+            Address::WhatsApp(phone) => write!(f, "whatsapp:{}", phone.e164_number()),
+            Address::Messenger(id) => write!(f, "messenger:{id}"),
+            Address::ShortCode(code) => write!(f, "{code}"),
+            Address::Alphanumeric(id) => write!(f, "{id}"),
+            Address::Other { channel, id } => write!(f, "{channel}:{id}"),
+        }
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Address::parse(&s).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Address {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Address".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "description": "A phone number, short code or sender ID, or a channel address such as `whatsapp:+254700123456`"
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claim::assert_err;
+
+    use crate::models::Address;
+
+    #[test]
+    fn whatsapp_addresses_carry_the_phone() {
+        let address = Address::parse("whatsapp:+254700123456").unwrap();
+
+        assert_eq!(address.channel(), "whatsapp");
+        assert_eq!(address.phone().unwrap().e164_number(), "+254700123456");
+        assert_eq!(address.to_string(), "whatsapp:+254700123456");
+    }
+
+    #[test]
+    fn bare_values_are_told_apart() {
+        assert!(matches!(
+            Address::parse("+254700123456"),
+            Ok(Address::Sms(_))
+        ));
+        assert!(matches!(Address::parse("40404"), Ok(Address::ShortCode(_))));
+        assert!(matches!(
+            Address::parse("MyBrand"),
+            Ok(Address::Alphanumeric(_))
+        ));
+        assert!(matches!(
+            Address::parse("messenger:1234"),
+            Ok(Address::Messenger(_))
+        ));
+        assert_err!(Address::parse(""));
+        assert_err!(Address::parse("whatsapp:not-a-number"));
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+use std::ops::Deref;
+
+use chrono::{DateTime, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A timestamp from a Twilio response.
+///
+/// The 2010 API uses RFC 2822 (`Wed, 18 Aug 2010 20:01:40 +0000`) while the newer APIs
+/// use ISO 8601 (`2015-07-30T20:00:00Z`). Both deserialize into this type, which always
+/// serializes as RFC 3339.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TwilioDateTime(DateTime<Utc>);
+
+impl TwilioDateTime {
+    pub fn parse(s: &str) -> Option<Self> {
+        DateTime::parse_from_rfc3339(s)
+            .or_else(|_| DateTime::parse_from_rfc2822(s))
+            .ok()
+            .map(|dt| TwilioDateTime(dt.with_timezone(&Utc)))
+    }
+
+    pub fn into_inner(self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+impl Deref for TwilioDateTime {
+    type Target = DateTime<Utc>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<DateTime<Utc>> for TwilioDateTime {
+    fn from(dt: DateTime<Utc>) -> Self {
+        TwilioDateTime(dt)
+    }
+}
+
+impl From<TwilioDateTime> for DateTime<Utc> {
+    fn from(dt: TwilioDateTime) -> Self {
+        dt.0
+    }
+}
+
+impl fmt::Display for TwilioDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.to_rfc3339())
+    }
+}
+
+impl Serialize for TwilioDateTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_rfc3339())
+    }
+}
+
+impl<'de> Deserialize<'de> for TwilioDateTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        TwilioDateTime::parse(&s)
+            .ok_or_else(|| de::Error::custom(format!("{s} is not an RFC 2822 or ISO 8601 date")))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for TwilioDateTime {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "TwilioDateTime".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": "string", "format": "date-time" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::models::TwilioDateTime;
+
+    #[test]
+    fn rfc_2822_and_iso_8601_parse_to_the_same_instant() {
+        let rfc2822: TwilioDateTime =
+            serde_json::from_str("\"Wed, 18 Aug 2010 20:01:40 +0000\"").unwrap();
+        let iso8601: TwilioDateTime = serde_json::from_str("\"2010-08-18T20:01:40Z\"").unwrap();
+
+        assert_eq!(rfc2822, iso8601);
+        assert_eq!(
+            rfc2822.into_inner(),
+            Utc.with_ymd_and_hms(2010, 8, 18, 20, 1, 40).unwrap()
+        );
+    }
+
+    #[test]
+    fn serializes_as_rfc_3339() {
+        let dt = TwilioDateTime::parse("Wed, 18 Aug 2010 20:01:40 +0000").unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&dt).unwrap(),
+            "\"2010-08-18T20:01:40+00:00\""
+        );
+    }
+
+    #[test]
+    fn garbage_is_rejected() {
+        assert!(serde_json::from_str::<TwilioDateTime>("\"yesterday\"").is_err());
+    }
+}
@@ -1,6 +1,12 @@
+use std::fmt;
+use std::time::Duration;
+
 use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
 use serde_json;
 
+use crate::ErrorCode;
+
 #[derive(Debug, thiserror::Error)]
 pub enum ClientError {
     #[error("HTTP request failed: {0}")]
@@ -19,12 +25,280 @@ pub enum ClientError {
     },
 
     #[error("Authentication failed: {0}")]
-    Authentication(String),
+    Authentication(AuthenticationError),
 
     #[error("Operation timed out after {0} seconds")]
     Timeout(u64),
+
+    #[error("Response body exceeded the {0} byte limit")]
+    ResponseTooLarge(usize),
+
+    #[error("Every configured sender has reached its daily cap")]
+    NoSenderAvailable,
+
+    /// A 5xx whose body isn't a Twilio error, e.g. an HTML maintenance page. `message`
+    /// is the body stripped of markup and truncated.
+    #[error("Twilio is unavailable: {status_code} - {message}")]
+    UpstreamUnavailable {
+        status_code: StatusCode,
+        message: String,
+    },
+
+    #[error("Too many requests in flight; shed by the concurrency limiter")]
+    Overloaded,
+
+    /// A request that could change something, e.g. a send, from a client built with
+    /// `read_only`. Nothing was sent to Twilio.
+    #[error("The client is read-only; mutating requests are rejected")]
+    ReadOnlyMode,
+
+    #[error("Verification failed: {0}")]
+    Verify(#[from] VerifyError),
+
+    #[error("Invalid content variables: {0}")]
+    ContentVariables(#[from] ContentVariablesError),
+}
+
+impl ClientError {
+    /// The Twilio error body behind a rejected request, if Twilio sent one.
+    pub fn twilio_error(&self) -> Option<TwilioError> {
+        match self {
+            ClientError::ServerResponse { message, .. } => Some(TwilioError::from_body(message)),
+            ClientError::Authentication(
+                AuthenticationError::InvalidCredentials(error)
+                | AuthenticationError::AccountSuspended(error)
+                | AuthenticationError::IpNotAllowed(error)
+                | AuthenticationError::Other(error),
+            ) => Some(error.clone()),
+            _ => None,
+        }
+    }
+
+    /// The Twilio error code behind a rejected request, e.g. [`ErrorCode::Unsubscribed`]
+    /// for a send to a number that replied STOP. Test credentials' magic numbers
+    /// decode to the same codes as real traffic.
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        self.twilio_error()?.code.map(ErrorCode::from)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 #[error("{0}")]
 pub struct ParseError(pub String);
+
+/// The JSON error body Twilio returns for failed requests.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TwilioError {
+    pub code: Option<i32>,
+    pub message: Option<String>,
+    pub more_info: Option<String>,
+    pub status: Option<u16>,
+}
+
+impl TwilioError {
+    /// Parses a Twilio error body, keeping a non-JSON body as the message.
+    pub fn from_body(body: &str) -> Self {
+        serde_json::from_str(body).unwrap_or_else(|_| TwilioError {
+            message: Some(body.to_string()),
+            ..Default::default()
+        })
+    }
+}
+
+impl fmt::Display for TwilioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = self.message.as_deref().unwrap_or("no message");
+        match self.code {
+            Some(code) => write!(f, "{code} - {message}"),
+            None => write!(f, "{message}"),
+        }
+    }
+}
+
+/// Why Twilio rejected the request with a 401.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum AuthenticationError {
+    #[error("invalid credentials: {0}")]
+    InvalidCredentials(TwilioError),
+
+    #[error("account suspended: {0}")]
+    AccountSuspended(TwilioError),
+
+    #[error("request rejected by IP access control list: {0}")]
+    IpNotAllowed(TwilioError),
+
+    #[error("{0}")]
+    Other(TwilioError),
+}
+
+impl AuthenticationError {
+    #[cfg_attr(
+        not(any(feature = "sms", feature = "verify", feature = "content")),
+        allow(dead_code)
+    )]
+    pub(crate) fn from_body(body: &str) -> Self {
+        let error = TwilioError::from_body(body);
+        // Twilio reports IP access control rejections under the generic 20003 code,
+        // so the message is the only thing that tells them apart.
+        let ip_rejected = error
+            .message
+            .as_deref()
+            .map(|m| {
+                let m = m.to_lowercase();
+                m.contains("ip address") || m.contains("ip access")
+            })
+            .unwrap_or(false);
+
+        match error.code {
+            Some(20003) if ip_rejected => AuthenticationError::IpNotAllowed(error),
+            Some(20003) => AuthenticationError::InvalidCredentials(error),
+            Some(20005) => AuthenticationError::AccountSuspended(error),
+            _ if ip_rejected => AuthenticationError::IpNotAllowed(error),
+            _ => AuthenticationError::Other(error),
+        }
+    }
+}
+
+/// Verify-specific failures that callers usually need to handle individually.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum VerifyError {
+    /// Too many codes were checked against the verification (Twilio error 60202).
+    ///
+    /// `retry_after` is taken from the `Retry-After` header when Twilio sends one.
+    #[error("max check attempts reached: {error}")]
+    MaxCheckAttempts {
+        error: TwilioError,
+        retry_after: Option<Duration>,
+    },
+
+    /// Too many codes were sent to the phone (Twilio error 60203).
+    #[error("max send attempts reached: {error}")]
+    MaxSendAttempts {
+        error: TwilioError,
+        retry_after: Option<Duration>,
+    },
+
+    /// The client's [`ResendPolicy`](crate::verify::ResendPolicy) holds back the next
+    /// code for `retry_in`.
+    #[error("resend requested too soon, retry in {}s", retry_in.as_secs())]
+    ResendTooSoon { retry_in: Duration },
+
+    /// Every resend the client's `ResendPolicy` allows has been used.
+    #[error("resend limit reached")]
+    ResendLimitReached,
+
+    /// The client's [`VerificationBudget`](crate::verify::VerificationBudget) allows
+    /// no more than `limit` codes per `window` for `tenant`, or for the account when
+    /// `tenant` is `None`. The window resets in `retry_in`.
+    #[error(
+        "verification budget of {limit} per {}s exceeded, retry in {}s",
+        window.as_secs(),
+        retry_in.as_secs()
+    )]
+    BudgetExceeded {
+        tenant: Option<String>,
+        limit: u64,
+        window: Duration,
+        retry_in: Duration,
+    },
+
+    /// The code was rejected locally and never sent to Twilio.
+    #[error("invalid verification code: {0}")]
+    InvalidCode(String),
+
+    /// The [`Session`](crate::verify::Session) expired, ended or never existed.
+    #[error("verification session not found")]
+    SessionNotFound,
+
+    /// The [`SessionStore`](crate::verify::SessionStore) failed.
+    #[error("verification session store failed: {0}")]
+    SessionStore(String),
+}
+
+impl VerifyError {
+    #[cfg_attr(
+        not(any(feature = "sms", feature = "verify", feature = "content")),
+        allow(dead_code)
+    )]
+    pub(crate) fn from_body(body: &str, retry_after: Option<Duration>) -> Option<Self> {
+        let error = serde_json::from_str::<TwilioError>(body).ok()?;
+        match error.code {
+            Some(60202) => Some(VerifyError::MaxCheckAttempts { error, retry_after }),
+            Some(60203) => Some(VerifyError::MaxSendAttempts { error, retry_after }),
+            _ => None,
+        }
+    }
+}
+
+/// `ContentVariables` that don't fit the template, caught before sending.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ContentVariablesError {
+    #[error("variables must be a JSON object")]
+    NotAnObject,
+
+    /// Placeholders without a value or default, variables the template doesn't use,
+    /// and variables whose value isn't a string.
+    #[error("{}", describe_mismatch(.missing, .unknown, .not_strings))]
+    Mismatch {
+        missing: Vec<String>,
+        unknown: Vec<String>,
+        not_strings: Vec<String>,
+    },
+}
+
+fn describe_mismatch(missing: &[String], unknown: &[String], not_strings: &[String]) -> String {
+    [
+        ("missing", missing),
+        ("not in the template", unknown),
+        ("not strings", not_strings),
+    ]
+    .iter()
+    .filter(|(_, names)| !names.is_empty())
+    .map(|(problem, names)| format!("{problem}: {}", names.join(", ")))
+    .collect::<Vec<_>>()
+    .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{AuthenticationError, TwilioError};
+
+    #[test]
+    fn invalid_credentials_are_detected() {
+        let body = r#"{"code": 20003, "message": "Authenticate", "more_info": "https://www.twilio.com/docs/errors/20003", "status": 401}"#;
+
+        assert!(matches!(
+            AuthenticationError::from_body(body),
+            AuthenticationError::InvalidCredentials(_)
+        ));
+    }
+
+    #[test]
+    fn suspended_accounts_are_detected() {
+        let body = r#"{"code": 20005, "message": "Account not active", "status": 401}"#;
+
+        assert!(matches!(
+            AuthenticationError::from_body(body),
+            AuthenticationError::AccountSuspended(_)
+        ));
+    }
+
+    #[test]
+    fn ip_acl_rejections_are_detected() {
+        let body = r#"{"code": 20003, "message": "Request from IP address 10.0.0.1 is not allowed", "status": 401}"#;
+
+        assert!(matches!(
+            AuthenticationError::from_body(body),
+            AuthenticationError::IpNotAllowed(_)
+        ));
+    }
+
+    #[test]
+    fn non_json_body_is_kept_as_message() {
+        let error = TwilioError::from_body("Unauthorized");
+
+        assert_eq!(error.message.as_deref(), Some("Unauthorized"));
+        assert_eq!(error.code, None);
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The Twilio error codes a message can fail with, as found in its `error_code`.
+///
+/// Codes without a variant of their own are kept as [`ErrorCode::Other`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "i32", into = "i32")]
+pub enum ErrorCode {
+    /// 21211: the `To` number isn't a valid phone number.
+    InvalidToNumber,
+    /// 21212: the `From` number isn't a valid phone number.
+    InvalidFromNumber,
+    /// 21408: the account isn't allowed to send to the destination's region.
+    RegionNotEnabled,
+    /// 21606: the `From` number isn't an SMS-capable number on the account.
+    SenderNotSmsCapable,
+    /// 21610: the recipient replied STOP.
+    Unsubscribed,
+    /// 21611: the `From` number has too many messages queued.
+    SenderQueueFull,
+    /// 21612: Twilio can't route between the `From` and `To` numbers.
+    CannotRoute,
+    /// 21614: the `To` number isn't a mobile number.
+    NotMobile,
+    /// 30001: too many messages were queued for the sender.
+    QueueOverflow,
+    /// 30002: the account was suspended.
+    AccountSuspended,
+    /// 30003: the handset is off or out of coverage.
+    UnreachableHandset,
+    /// 30004: the recipient blocked the sender or can't receive messages.
+    MessageBlocked,
+    /// 30005: the number doesn't exist.
+    UnknownDestination,
+    /// 30006: a landline, or a carrier that can't be reached.
+    LandlineOrUnreachableCarrier,
+    /// 30007: the carrier filtered the message as spam.
+    CarrierViolation,
+    /// 30008: the carrier gave no reason.
+    Unknown,
+    /// 30010: the message would cost more than its `MaxPrice`.
+    MaxPriceExceeded,
+    /// 30034: the sender isn't registered for US A2P 10DLC.
+    UnregisteredSender,
+    Other(i32),
+}
+
+impl ErrorCode {
+    pub fn code(self) -> i32 {
+        match self {
+            ErrorCode::InvalidToNumber => 21211,
+            ErrorCode::InvalidFromNumber => 21212,
+            ErrorCode::RegionNotEnabled => 21408,
+            ErrorCode::SenderNotSmsCapable => 21606,
+            ErrorCode::Unsubscribed => 21610,
+            ErrorCode::SenderQueueFull => 21611,
+            ErrorCode::CannotRoute => 21612,
+            ErrorCode::NotMobile => 21614,
+            ErrorCode::QueueOverflow => 30001,
+            ErrorCode::AccountSuspended => 30002,
+            ErrorCode::UnreachableHandset => 30003,
+            ErrorCode::MessageBlocked => 30004,
+            ErrorCode::UnknownDestination => 30005,
+            ErrorCode::LandlineOrUnreachableCarrier => 30006,
+            ErrorCode::CarrierViolation => 30007,
+            ErrorCode::Unknown => 30008,
+            ErrorCode::MaxPriceExceeded => 30010,
+            ErrorCode::UnregisteredSender => 30034,
+            ErrorCode::Other(code) => code,
+        }
+    }
+
+    /// A short, human readable reason, or `None` for codes without a variant.
+    pub fn description(self) -> Option<&'static str> {
+        let description = match self {
+            ErrorCode::InvalidToNumber => "invalid recipient number",
+            ErrorCode::InvalidFromNumber => "invalid sender number",
+            ErrorCode::RegionNotEnabled => "destination region not enabled",
+            ErrorCode::SenderNotSmsCapable => "sender can't send SMS",
+            ErrorCode::Unsubscribed => "recipient unsubscribed",
+            ErrorCode::SenderQueueFull => "sender queue full",
+            ErrorCode::CannotRoute => "no route between sender and recipient",
+            ErrorCode::NotMobile => "recipient is not a mobile number",
+            ErrorCode::QueueOverflow => "sender queue overflow",
+            ErrorCode::AccountSuspended => "account suspended",
+            ErrorCode::UnreachableHandset => "handset unreachable",
+            ErrorCode::MessageBlocked => "message blocked",
+            ErrorCode::UnknownDestination => "unknown destination",
+            ErrorCode::LandlineOrUnreachableCarrier => "landline or unreachable carrier",
+            ErrorCode::CarrierViolation => "filtered by the carrier",
+            ErrorCode::Unknown => "unknown carrier error",
+            ErrorCode::MaxPriceExceeded => "max price exceeded",
+            ErrorCode::UnregisteredSender => "sender not registered for A2P 10DLC",
+            ErrorCode::Other(_) => return None,
+        };
+        Some(description)
+    }
+
+    /// Whether resending to the same number will keep failing.
+    pub fn is_permanent(self) -> bool {
+        matches!(
+            self,
+            ErrorCode::Unsubscribed
+                | ErrorCode::NotMobile
+                | ErrorCode::UnknownDestination
+                | ErrorCode::LandlineOrUnreachableCarrier
+                | ErrorCode::CarrierViolation
+        )
+    }
+}
+
+impl From<i32> for ErrorCode {
+    fn from(code: i32) -> Self {
+        match code {
+            21211 => ErrorCode::InvalidToNumber,
+            21212 => ErrorCode::InvalidFromNumber,
+            21408 => ErrorCode::RegionNotEnabled,
+            21606 => ErrorCode::SenderNotSmsCapable,
+            21610 => ErrorCode::Unsubscribed,
+            21611 => ErrorCode::SenderQueueFull,
+            21612 => ErrorCode::CannotRoute,
+            21614 => ErrorCode::NotMobile,
+            30001 => ErrorCode::QueueOverflow,
+            30002 => ErrorCode::AccountSuspended,
+            30003 => ErrorCode::UnreachableHandset,
+            30004 => ErrorCode::MessageBlocked,
+            30005 => ErrorCode::UnknownDestination,
+            30006 => ErrorCode::LandlineOrUnreachableCarrier,
+            30007 => ErrorCode::CarrierViolation,
+            30008 => ErrorCode::Unknown,
+            30010 => ErrorCode::MaxPriceExceeded,
+            30034 => ErrorCode::UnregisteredSender,
+            code => ErrorCode::Other(code),
+        }
+    }
+}
+
+impl From<ErrorCode> for i32 {
+    fn from(code: ErrorCode) -> Self {
+        code.code()
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.description() {
+            Some(description) => write!(f, "{} ({description})", self.code()),
+            None => write!(f, "{}", self.code()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErrorCode;
+
+    #[test]
+    fn codes_round_trip() {
+        for code in [21610, 30003, 30034, 12345] {
+            assert_eq!(ErrorCode::from(code).code(), code);
+        }
+        assert_eq!(ErrorCode::from(12345), ErrorCode::Other(12345));
+    }
+
+    #[test]
+    fn deserializes_from_the_numeric_code() {
+        let code: ErrorCode = serde_json::from_str("30007").unwrap();
+
+        assert_eq!(code, ErrorCode::CarrierViolation);
+        assert!(code.is_permanent());
+    }
+}
@@ -0,0 +1,289 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::{Params, Phone};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+
+/// Filters for the Messages list endpoint.
+///
+/// Twilio expresses date ranges with the key itself (`DateSent<`, `DateSent>`), so the
+/// date helpers here are inclusive of the given day.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MessageFilter {
+    to: Option<String>,
+    from: Option<String>,
+    date_sent: Option<NaiveDate>,
+    date_sent_before: Option<NaiveDate>,
+    date_sent_after: Option<NaiveDate>,
+    page_size: Option<u32>,
+}
+
+impl MessageFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn to(mut self, to: &Phone) -> Self {
+        self.to = Some(to.e164_number());
+        self
+    }
+
+    pub fn from(mut self, from: &Phone) -> Self {
+        self.from = Some(from.e164_number());
+        self
+    }
+
+    /// Only messages sent on `date` (UTC).
+    pub fn sent_on_date(mut self, date: NaiveDate) -> Self {
+        self.date_sent = Some(date);
+        self
+    }
+
+    /// Only messages sent on or before `date` (UTC).
+    pub fn sent_before(mut self, date: NaiveDate) -> Self {
+        self.date_sent_before = Some(date);
+        self
+    }
+
+    /// Only messages sent on or after `date` (UTC).
+    pub fn sent_after(mut self, date: NaiveDate) -> Self {
+        self.date_sent_after = Some(date);
+        self
+    }
+
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// The query parameters this filter serializes to.
+    pub fn to_params(&self) -> Params {
+        let mut params = Params::new();
+        if let Some(to) = &self.to {
+            params.insert("To", to.clone());
+        }
+        if let Some(from) = &self.from {
+            params.insert("From", from.clone());
+        }
+        if let Some(date) = self.date_sent {
+            params.insert("DateSent", date.format(DATE_FORMAT).to_string());
+        }
+        if let Some(date) = self.date_sent_before {
+            params.insert("DateSent<", date.format(DATE_FORMAT).to_string());
+        }
+        if let Some(date) = self.date_sent_after {
+            params.insert("DateSent>", date.format(DATE_FORMAT).to_string());
+        }
+        if let Some(page_size) = self.page_size {
+            params.insert("PageSize", page_size.to_string());
+        }
+        params
+    }
+}
+
+/// Filters for the Calls list endpoint.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CallFilter {
+    to: Option<String>,
+    from: Option<String>,
+    status: Option<String>,
+    start_time: Option<NaiveDate>,
+    started_before: Option<NaiveDate>,
+    started_after: Option<NaiveDate>,
+    page_size: Option<u32>,
+}
+
+impl CallFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn to(mut self, to: &Phone) -> Self {
+        self.to = Some(to.e164_number());
+        self
+    }
+
+    pub fn from(mut self, from: &Phone) -> Self {
+        self.from = Some(from.e164_number());
+        self
+    }
+
+    /// The call status, e.g. `completed` or `no-answer`.
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.status = Some(status.into());
+        self
+    }
+
+    /// Only calls started on `date` (UTC).
+    pub fn started_on_date(mut self, date: NaiveDate) -> Self {
+        self.start_time = Some(date);
+        self
+    }
+
+    /// Only calls started on or before `date` (UTC).
+    pub fn started_before(mut self, date: NaiveDate) -> Self {
+        self.started_before = Some(date);
+        self
+    }
+
+    /// Only calls started on or after `date` (UTC).
+    pub fn started_after(mut self, date: NaiveDate) -> Self {
+        self.started_after = Some(date);
+        self
+    }
+
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// The query parameters this filter serializes to.
+    pub fn to_params(&self) -> Params {
+        let mut params = Params::new();
+        if let Some(to) = &self.to {
+            params.insert("To", to.clone());
+        }
+        if let Some(from) = &self.from {
+            params.insert("From", from.clone());
+        }
+        if let Some(status) = &self.status {
+            params.insert("Status", status.clone());
+        }
+        if let Some(date) = self.start_time {
+            params.insert("StartTime", date.format(DATE_FORMAT).to_string());
+        }
+        if let Some(date) = self.started_before {
+            params.insert("StartTime<", date.format(DATE_FORMAT).to_string());
+        }
+        if let Some(date) = self.started_after {
+            params.insert("StartTime>", date.format(DATE_FORMAT).to_string());
+        }
+        if let Some(page_size) = self.page_size {
+            params.insert("PageSize", page_size.to_string());
+        }
+        params
+    }
+}
+
+/// Filters for the Verify Attempts list endpoint.
+///
+/// Unlike the 2010 API, Verify takes plain `DateCreatedAfter`/`DateCreatedBefore` keys
+/// with full ISO 8601 timestamps.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AttemptFilter {
+    to: Option<String>,
+    channel: Option<String>,
+    service_sid: Option<String>,
+    verification_sid: Option<String>,
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
+    page_size: Option<u32>,
+}
+
+impl AttemptFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn to(mut self, to: &Phone) -> Self {
+        self.to = Some(to.e164_number());
+        self
+    }
+
+    /// The delivery channel, e.g. `sms`.
+    pub fn channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = Some(channel.into());
+        self
+    }
+
+    pub fn service_sid(mut self, service_sid: impl Into<String>) -> Self {
+        self.service_sid = Some(service_sid.into());
+        self
+    }
+
+    pub fn verification_sid(mut self, verification_sid: impl Into<String>) -> Self {
+        self.verification_sid = Some(verification_sid.into());
+        self
+    }
+
+    pub fn created_after(mut self, time: DateTime<Utc>) -> Self {
+        self.created_after = Some(time);
+        self
+    }
+
+    pub fn created_before(mut self, time: DateTime<Utc>) -> Self {
+        self.created_before = Some(time);
+        self
+    }
+
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// The query parameters this filter serializes to.
+    pub fn to_params(&self) -> Params {
+        let mut params = Params::new();
+        if let Some(to) = &self.to {
+            params.insert_nested("ChannelData", "To", to.clone());
+        }
+        if let Some(channel) = &self.channel {
+            params.insert("Channel", channel.clone());
+        }
+        if let Some(service_sid) = &self.service_sid {
+            params.insert("VerifyServiceSid", service_sid.clone());
+        }
+        if let Some(verification_sid) = &self.verification_sid {
+            params.insert("VerificationSid", verification_sid.clone());
+        }
+        if let Some(time) = self.created_after {
+            params.insert(
+                "DateCreatedAfter",
+                time.format(DATE_TIME_FORMAT).to_string(),
+            );
+        }
+        if let Some(time) = self.created_before {
+            params.insert(
+                "DateCreatedBefore",
+                time.format(DATE_TIME_FORMAT).to_string(),
+            );
+        }
+        if let Some(page_size) = self.page_size {
+            params.insert("PageSize", page_size.to_string());
+        }
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    use crate::models::{AttemptFilter, MessageFilter};
+
+    #[test]
+    fn message_date_range_uses_twilio_comparison_keys() {
+        let params = MessageFilter::new()
+            .sent_after(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+            .sent_before(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+            .to_params();
+
+        assert_eq!(params.get("DateSent>"), Some("2024-01-01"));
+        assert_eq!(params.get("DateSent<"), Some("2024-01-31"));
+        assert!(!params.contains_key("DateSent"));
+    }
+
+    #[test]
+    fn empty_filter_has_no_params() {
+        assert!(MessageFilter::new().to_params().is_empty());
+    }
+
+    #[test]
+    fn attempt_dates_are_iso_8601() {
+        let time = Utc.with_ymd_and_hms(2024, 5, 6, 7, 8, 9).unwrap();
+        let params = AttemptFilter::new().created_after(time).to_params();
+
+        assert_eq!(params.get("DateCreatedAfter"), Some("2024-05-06T07:08:09Z"));
+    }
+}
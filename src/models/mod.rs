@@ -2,3 +2,27 @@ pub mod error;
 
 mod phone;
 pub use phone::*;
+
+mod filter;
+pub use filter::*;
+
+mod page;
+pub use page::*;
+
+mod params;
+pub use params::*;
+
+mod datetime;
+pub use datetime::*;
+
+mod raw;
+pub use raw::*;
+
+mod address;
+pub use address::*;
+
+mod pseudonym;
+pub use pseudonym::*;
+
+mod error_code;
+pub use error_code::*;
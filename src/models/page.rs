@@ -0,0 +1,126 @@
+use serde::de::{self, DeserializeOwned, Deserializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+// Envelope keys that aren't the item list, across the 2010 API and the v1/v2 APIs.
+const METADATA_KEYS: [&str; 11] = [
+    "meta",
+    "page",
+    "page_size",
+    "first_page_uri",
+    "next_page_uri",
+    "previous_page_uri",
+    "uri",
+    "start",
+    "end",
+    "num_pages",
+    "total",
+];
+
+/// One page of a Twilio list response.
+///
+/// Twilio names the item list after the resource (`messages`, `attempts`, ...) and
+/// moves the paging fields into `meta` on the newer APIs; both shapes end up here.
+/// Pass a page to the client's `next_page`/`previous_page` to fetch its neighbours.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub page_size: u32,
+    pub first_page_uri: Option<String>,
+    pub next_page_uri: Option<String>,
+    pub previous_page_uri: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn has_next(&self) -> bool {
+        self.next_page_uri.is_some()
+    }
+
+    pub fn has_previous(&self) -> bool {
+        self.previous_page_uri.is_some()
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Page<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut envelope = Map::<String, Value>::deserialize(deserializer)?;
+
+        let (meta, suffix) = match envelope.get("meta") {
+            Some(Value::Object(meta)) => (meta.clone(), "url"),
+            _ => (envelope.clone(), "uri"),
+        };
+        let number = |key: &str| meta.get(key).and_then(Value::as_u64).unwrap_or(0) as u32;
+        let link = |key: &str| {
+            meta.get(&format!("{key}_page_{suffix}"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+
+        let items_key = envelope
+            .iter()
+            .find(|(k, v)| v.is_array() && !METADATA_KEYS.contains(&k.as_str()))
+            .map(|(k, _)| k.clone())
+            .ok_or_else(|| de::Error::custom("list response has no item array"))?;
+        let items = serde_json::from_value(envelope.remove(&items_key).unwrap())
+            .map_err(de::Error::custom)?;
+
+        Ok(Page {
+            items,
+            page: number("page"),
+            page_size: number("page_size"),
+            first_page_uri: link("first"),
+            next_page_uri: link("next"),
+            previous_page_uri: link("previous"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::Page;
+
+    #[test]
+    fn v2010_envelope_is_parsed() {
+        let page: Page<serde_json::Value> = serde_json::from_value(serde_json::json!({
+            "messages": [{"sid": "SM1"}, {"sid": "SM2"}],
+            "page": 1,
+            "page_size": 2,
+            "first_page_uri": "/2010-04-01/Accounts/AC1/Messages.json?Page=0",
+            "next_page_uri": "/2010-04-01/Accounts/AC1/Messages.json?Page=2",
+            "previous_page_uri": null,
+            "uri": "/2010-04-01/Accounts/AC1/Messages.json?Page=1"
+        }))
+        .unwrap();
+
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.page, 1);
+        assert!(page.has_next());
+        assert!(!page.has_previous());
+    }
+
+    #[test]
+    fn meta_envelope_is_parsed() {
+        let page: Page<serde_json::Value> = serde_json::from_value(serde_json::json!({
+            "attempts": [],
+            "meta": {
+                "page": 0,
+                "page_size": 50,
+                "next_page_url": "https://verify.twilio.com/v2/Attempts?Page=1",
+                "previous_page_url": null,
+                "key": "attempts"
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(page.page_size, 50);
+        assert_eq!(
+            page.next_page_uri.as_deref(),
+            Some("https://verify.twilio.com/v2/Attempts?Page=1")
+        );
+    }
+}
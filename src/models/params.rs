@@ -0,0 +1,130 @@
+use serde::{Serialize, Serializer};
+
+/// Form or query parameters for a Twilio request.
+///
+/// Unlike a map, keys can repeat (Twilio takes one `MediaUrl` per attachment) and
+/// insertion order is kept. Nested parameters use Twilio's dotted names, e.g.
+/// `ChannelData.To`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    pairs: Vec<(String, String)>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `key` to `value`, replacing any earlier values for `key`.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let key = key.into();
+        self.pairs.retain(|(k, _)| *k != key);
+        self.pairs.push((key, value.into()));
+    }
+
+    /// Adds another value for `key`, keeping the earlier ones.
+    pub fn append(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.pairs.push((key.into(), value.into()));
+    }
+
+    /// Sets the nested parameter `parent.key` to `value`.
+    pub fn insert_nested(&mut self, parent: &str, key: &str, value: impl Into<String>) {
+        self.insert(format!("{parent}.{key}"), value);
+    }
+
+    /// The first value for `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Every value for `key`, in insertion order.
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.pairs
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.pairs.iter().any(|(k, _)| k == key)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Params {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self {
+            pairs: iter
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        }
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> Extend<(K, V)> for Params {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.pairs
+            .extend(iter.into_iter().map(|(k, v)| (k.into(), v.into())));
+    }
+}
+
+impl Serialize for Params {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.pairs.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::Params;
+
+    #[test]
+    fn repeated_keys_are_encoded_separately() {
+        let mut params = Params::new();
+        params.append("MediaUrl", "https://a.example/1.png");
+        params.append("MediaUrl", "https://a.example/2.png");
+
+        let encoded = serde_urlencoded::to_string(&params).unwrap();
+
+        assert_eq!(
+            encoded,
+            "MediaUrl=https%3A%2F%2Fa.example%2F1.png&MediaUrl=https%3A%2F%2Fa.example%2F2.png"
+        );
+    }
+
+    #[test]
+    fn insert_replaces_earlier_values() {
+        let mut params = Params::new();
+        params.append("To", "a");
+        params.append("To", "b");
+        params.insert("To", "c");
+
+        assert_eq!(params.get_all("To").collect::<Vec<_>>(), vec!["c"]);
+    }
+
+    #[test]
+    fn nested_keys_use_dotted_names() {
+        let mut params = Params::new();
+        params.insert_nested("ChannelData", "To", "+254700123456");
+
+        assert_eq!(params.get("ChannelData.To"), Some("+254700123456"));
+    }
+}
@@ -6,6 +6,7 @@ use phonenumber::{Mode, PhoneNumber};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RawPhone {
     pub number: String,
     pub country_code: String,
@@ -68,6 +69,12 @@ impl Phone {
         hasher.finalize().to_string()
     }
 
+    /// The number's ISO 3166 country code.
+    ///
+    /// # Panics
+    ///
+    /// On non-geographic numbers such as `+800` freephone ones, which have no country;
+    /// use [`Phone::country_id`] for those.
     pub fn country_iso(&self) -> String {
         self.phone_number
             .country()
@@ -76,6 +83,22 @@ impl Phone {
             .as_ref()
             .to_string()
     }
+
+    /// The number's ISO 3166 country code, or `None` for a non-geographic number such
+    /// as `+800` or `+882`.
+    pub fn country_id(&self) -> Option<String> {
+        self.phone_number
+            .country()
+            .id()
+            .map(|id| id.as_ref().to_string())
+    }
+
+    /// The kind of line the country's numbering plan assigns the number to.
+    #[cfg(feature = "sms")]
+    pub(crate) fn number_type(&self) -> phonenumber::Type {
+        self.phone_number
+            .number_type(&phonenumber::metadata::DATABASE)
+    }
 }
 
 impl PartialEq<Phone> for Phone {
@@ -110,6 +133,18 @@ impl<'de> Deserialize<'de> for Phone {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Phone {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Phone".into()
+    }
+
+    // Serialized through RawPhone
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        RawPhone::json_schema(generator)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use claim::assert_err;
@@ -133,7 +168,7 @@ mod tests {
     }
 
     fn generate_phone() -> (String, String) {
-        let num = (1..9).fake::<u64>();
+        let num = (10_000_000..99_999_999).fake::<u64>();
         let number = format!("+2547{num}");
 
         (number, "KE".to_string())
@@ -172,6 +207,6 @@ mod tests {
     #[quickcheck_macros::quickcheck]
     fn a_valid_phone_with_nc_has_correct_country(valid_phone: ValidPhoneFixture) -> bool {
         let phone = Phone::parse_with_no_country(&valid_phone.number).unwrap();
-        phone.country_iso() == "KE".to_string()
+        phone.country_iso() == "KE"
     }
 }
@@ -0,0 +1,68 @@
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::Sha256;
+
+use crate::Phone;
+
+/// Turns phone numbers into stable pseudonyms, the hex HMAC-SHA256 of the E.164 number
+/// under a caller-provided key.
+///
+/// Unlike [`Phone::hash`], a pseudonym can't be reversed by hashing every possible
+/// number without the key, so it is safe to join analytics on. Keep the key stable:
+/// rotating it changes every pseudonym.
+///
+/// ```
+/// # use secrecy::SecretString;
+/// # use twilio_client::{Phone, Pseudonymizer};
+/// let pseudonymizer = Pseudonymizer::new(SecretString::from("analytics-key"));
+/// let phone = Phone::parse("0700123456", "KE").unwrap();
+/// assert_eq!(pseudonymizer.pseudonym(&phone), pseudonymizer.pseudonym(&phone));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pseudonymizer {
+    key: SecretString,
+}
+
+impl Pseudonymizer {
+    pub fn new(key: SecretString) -> Self {
+        Self { key }
+    }
+
+    pub fn pseudonym(&self, phone: &Phone) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.key.expose_secret().as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(phone.e164_number().as_bytes());
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use secrecy::SecretString;
+
+    use crate::{Phone, Pseudonymizer};
+
+    #[test]
+    fn pseudonym_is_the_hex_hmac_of_the_e164_number() {
+        let pseudonymizer = Pseudonymizer::new(SecretString::from("key"));
+        let phone = Phone::parse("0700123456", "KE").unwrap();
+
+        assert_eq!(
+            pseudonymizer.pseudonym(&phone),
+            "0c7894f6bf1633c0819f49f2c5628786230ed380e372167bc257c1c957437079"
+        );
+    }
+
+    #[test]
+    fn different_keys_give_different_pseudonyms() {
+        let phone = Phone::parse("0700123456", "KE").unwrap();
+        let first = Pseudonymizer::new(SecretString::from("first"));
+        let second = Pseudonymizer::new(SecretString::from("second"));
+
+        assert_ne!(first.pseudonym(&phone), second.pseudonym(&phone));
+    }
+}
@@ -0,0 +1,60 @@
+use std::ops::Deref;
+
+use serde::de::{self, DeserializeOwned};
+use serde::{Deserialize, Deserializer};
+
+/// A typed response together with the JSON it was parsed from.
+///
+/// Useful for reaching fields the models don't cover yet, or for logging new fields
+/// Twilio adds so the models can catch up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Raw<T> {
+    pub value: T,
+    pub json: serde_json::Value,
+}
+
+impl<T> Raw<T> {
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Raw<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Raw<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let json = serde_json::Value::deserialize(deserializer)?;
+        let value = T::deserialize(&json).map_err(de::Error::custom)?;
+        Ok(Raw { value, json })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::Raw;
+
+    #[derive(Debug, Deserialize)]
+    struct Message {
+        sid: String,
+    }
+
+    #[test]
+    fn keeps_fields_the_model_does_not_know() {
+        let body = r#"{"sid": "SM1", "price_unit": "USD"}"#;
+        let raw: Raw<Message> = serde_json::from_str(body).unwrap();
+
+        assert_eq!(raw.sid, "SM1");
+        assert_eq!(raw.json["price_unit"], "USD");
+    }
+}
@@ -0,0 +1,170 @@
+//! Loosely typed response models that mirror the schemas in Twilio's OpenAPI
+//! specification (`twilio_api_v2010.json`, `twilio_verify_v2.json`).
+//!
+//! Every field is optional and uses the spec's wire name, and anything the spec doesn't
+//! list ends up in `extra`, so these deserialize whatever Twilio returns. Prefer the
+//! handwritten models where they exist; these are for fields and endpoints the crate
+//! doesn't cover yet.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// `api.v2010.account`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ApiV2010Account {
+    pub auth_token: Option<String>,
+    pub date_created: Option<String>,
+    pub date_updated: Option<String>,
+    pub friendly_name: Option<String>,
+    pub owner_account_sid: Option<String>,
+    pub sid: Option<String>,
+    pub status: Option<String>,
+    pub subresource_uris: Option<HashMap<String, String>>,
+    #[serde(rename = "type")]
+    pub account_type: Option<String>,
+    pub uri: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// `api.v2010.account.message`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ApiV2010AccountMessage {
+    pub body: Option<String>,
+    pub num_segments: Option<String>,
+    pub direction: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub date_updated: Option<String>,
+    pub price: Option<String>,
+    pub error_message: Option<String>,
+    pub uri: Option<String>,
+    pub account_sid: Option<String>,
+    pub num_media: Option<String>,
+    pub status: Option<String>,
+    pub messaging_service_sid: Option<String>,
+    pub sid: Option<String>,
+    pub date_sent: Option<String>,
+    pub date_created: Option<String>,
+    pub error_code: Option<i32>,
+    pub price_unit: Option<String>,
+    pub api_version: Option<String>,
+    pub subresource_uris: Option<HashMap<String, String>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// `api.v2010.account.message.media`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ApiV2010AccountMessageMedia {
+    pub account_sid: Option<String>,
+    pub content_type: Option<String>,
+    pub date_created: Option<String>,
+    pub date_updated: Option<String>,
+    pub parent_sid: Option<String>,
+    pub sid: Option<String>,
+    pub uri: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// `api.v2010.account.call`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ApiV2010AccountCall {
+    pub sid: Option<String>,
+    pub date_created: Option<String>,
+    pub date_updated: Option<String>,
+    pub parent_call_sid: Option<String>,
+    pub account_sid: Option<String>,
+    pub to: Option<String>,
+    pub to_formatted: Option<String>,
+    pub from: Option<String>,
+    pub from_formatted: Option<String>,
+    pub phone_number_sid: Option<String>,
+    pub status: Option<String>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub duration: Option<String>,
+    pub price: Option<String>,
+    pub price_unit: Option<String>,
+    pub direction: Option<String>,
+    pub answered_by: Option<String>,
+    pub api_version: Option<String>,
+    pub forwarded_from: Option<String>,
+    pub group_sid: Option<String>,
+    pub caller_name: Option<String>,
+    pub queue_time: Option<String>,
+    pub trunk_sid: Option<String>,
+    pub uri: Option<String>,
+    pub subresource_uris: Option<HashMap<String, String>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// `verify.v2.service`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VerifyV2Service {
+    pub sid: Option<String>,
+    pub account_sid: Option<String>,
+    pub friendly_name: Option<String>,
+    pub code_length: Option<i32>,
+    pub lookup_enabled: Option<bool>,
+    pub psd2_enabled: Option<bool>,
+    pub skip_sms_to_landlines: Option<bool>,
+    pub dtmf_input_required: Option<bool>,
+    pub tts_name: Option<String>,
+    pub do_not_share_warning_enabled: Option<bool>,
+    pub custom_code_enabled: Option<bool>,
+    pub push: Option<Value>,
+    pub totp: Option<Value>,
+    pub default_template_sid: Option<String>,
+    pub date_created: Option<String>,
+    pub date_updated: Option<String>,
+    pub url: Option<String>,
+    pub links: Option<HashMap<String, String>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// `verify.v2.verification_attempt`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VerifyV2VerificationAttempt {
+    pub sid: Option<String>,
+    pub account_sid: Option<String>,
+    pub service_sid: Option<String>,
+    pub verification_sid: Option<String>,
+    pub date_created: Option<String>,
+    pub date_updated: Option<String>,
+    pub conversion_status: Option<String>,
+    pub channel: Option<String>,
+    pub price: Option<Value>,
+    pub channel_data: Option<Value>,
+    pub url: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::openapi::ApiV2010AccountMessage;
+
+    #[test]
+    fn unknown_fields_are_kept() {
+        let message: ApiV2010AccountMessage = serde_json::from_value(serde_json::json!({
+            "sid": "SM123",
+            "brand_new_field": 42
+        }))
+        .unwrap();
+
+        assert_eq!(message.sid.as_deref(), Some("SM123"));
+        assert_eq!(message.extra["brand_new_field"], 42);
+    }
+}
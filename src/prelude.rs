@@ -0,0 +1,40 @@
+//! The types most code needs, under names that don't clash across services.
+//!
+//! ```
+//! use twilio_client::prelude::*;
+//! ```
+//!
+//! Each service's `Client`, `ClientBuilder` and `Status` are renamed after the
+//! service, e.g. [`SmsClient`] and [`VerifyClientBuilder`].
+
+pub use crate::clock::{Clock, SystemClock};
+pub use crate::error::{ClientError, ParseError, VerifyError};
+pub use crate::{ErrorCode, MessageFilter, Page, Params, Phone};
+
+#[cfg(any(feature = "sms", feature = "verify", feature = "content"))]
+pub use crate::limiter::{ConcurrencyLimiter, Priority};
+#[cfg(any(feature = "sms", feature = "verify", feature = "content"))]
+pub use crate::retry::RetryPolicy;
+
+#[cfg(feature = "sms")]
+pub use crate::sms::{
+    Client as SmsClient, ClientBuilder as SmsClientBuilder, Direction, MessageBuilder, Price,
+    ReceiptStore, SendOptions, SendSmsResponse, SmsSender, Status as MessageStatus,
+};
+
+#[cfg(feature = "verify")]
+pub use crate::verify::{
+    Channel, Client as VerifyClient, ClientBuilder as VerifyClientBuilder, OtpFlowOptions,
+    Status as VerificationStatus, VerifyEventSink, VerifyOutcome,
+};
+
+#[cfg(feature = "content")]
+pub use crate::content::{
+    Client as ContentClient, ClientBuilder as ContentClientBuilder, ContentTemplate,
+};
+
+#[cfg(feature = "webhooks")]
+pub use crate::webhooks::{DuplicateDetector, IdempotencyStore, IncomingMessage, RequestValidator};
+
+#[cfg(all(feature = "webhooks", feature = "sms"))]
+pub use crate::webhooks::MessageStatusCallback;
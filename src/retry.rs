@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use crate::error::ClientError;
+
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Retries for transient failures: connection errors, timeouts, 429s and 5xxs.
+///
+/// Retries follow each operation's idempotency: reads are retried on any of these
+/// failures, but anything else, e.g. a message create or a verification check, only
+/// when the connection failed and the request never went out. Twilio doesn't drop
+/// duplicate creates, so one that timed out or got a 5xx may have been sent and is
+/// not retried.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Allows up to `max_attempts` attempts per call, including the first.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    /// Waits `initial` before the first retry, doubling up to `max`. Defaults to 200ms
+    /// and 5s.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// The wait before retry number `retry` (starting at 1).
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    pub(crate) fn should_retry(
+        &self,
+        attempt: u32,
+        err: &ClientError,
+        idempotency: Idempotency,
+    ) -> bool {
+        let retryable = match idempotency {
+            Idempotency::Safe => is_transient(err),
+            Idempotency::UntilSuccess | Idempotency::Unsafe => never_sent(err),
+        };
+        attempt < self.max_attempts && retryable
+    }
+}
+
+/// How a request may be repeated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Idempotency {
+    /// Repeating the request has no further effect.
+    Safe,
+    /// Repeating the request is harmless until a copy succeeds, e.g. a verification
+    /// check, which deletes an approved verification so a repeat gets a 404. Hedged,
+    /// since the other copy's outcome is still awaited, but only retried when the
+    /// request never went out.
+    #[cfg_attr(not(feature = "verify"), allow(dead_code))]
+    UntilSuccess,
+    /// Only retried when the request never went out.
+    Unsafe,
+}
+
+fn is_transient(err: &ClientError) -> bool {
+    match err {
+        ClientError::Timeout(_) | ClientError::UpstreamUnavailable { .. } => true,
+        ClientError::Reqwest(err) => err.is_connect() || err.is_timeout(),
+        ClientError::ServerResponse { status_code, .. } => {
+            *status_code == StatusCode::TOO_MANY_REQUESTS || status_code.is_server_error()
+        }
+        _ => false,
+    }
+}
+
+/// Whether the request failed to connect, so Twilio never saw it.
+pub(crate) fn never_sent(err: &ClientError) -> bool {
+    matches!(err, ClientError::Reqwest(err) if err.is_connect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::StatusCode;
+
+    use crate::error::ClientError;
+    use crate::retry::{Idempotency, RetryPolicy};
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy =
+            RetryPolicy::new(5).backoff(Duration::from_millis(100), Duration::from_millis(300));
+
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn only_transient_errors_are_retried() {
+        let policy = RetryPolicy::new(3);
+        let unavailable = ClientError::ServerResponse {
+            status_code: StatusCode::SERVICE_UNAVAILABLE,
+            message: String::new(),
+        };
+        let bad_request = ClientError::ServerResponse {
+            status_code: StatusCode::BAD_REQUEST,
+            message: String::new(),
+        };
+
+        let config = ClientError::Configuration("bad".to_string());
+
+        assert!(policy.should_retry(1, &unavailable, Idempotency::Safe));
+        assert!(!policy.should_retry(3, &unavailable, Idempotency::Safe));
+        assert!(!policy.should_retry(1, &bad_request, Idempotency::Safe));
+        assert!(!policy.should_retry(1, &config, Idempotency::Safe));
+    }
+
+    #[test]
+    fn requests_that_may_have_been_sent_are_only_retried_when_safe() {
+        let policy = RetryPolicy::new(3);
+        let timeout = ClientError::Timeout(5);
+        let unavailable = ClientError::ServerResponse {
+            status_code: StatusCode::SERVICE_UNAVAILABLE,
+            message: String::new(),
+        };
+
+        assert!(policy.should_retry(1, &timeout, Idempotency::Safe));
+        for idempotency in [Idempotency::UntilSuccess, Idempotency::Unsafe] {
+            assert!(!policy.should_retry(1, &timeout, idempotency));
+            assert!(!policy.should_retry(1, &unavailable, idempotency));
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use serde_json::Value;
+
+const REDACTED: &str = "[REDACTED]";
+
+// Fields that carry personal data or secrets in Twilio requests and responses
+const DEFAULT_REDACTED_FIELDS: [&str; 14] = [
+    "to",
+    "from",
+    "body",
+    "code",
+    "phonenumber",
+    "phone_number",
+    "to_formatted",
+    "from_formatted",
+    "caller_name",
+    "contentvariables",
+    "content_variables",
+    "customcode",
+    "identity",
+    "ip",
+];
+
+// Path segments whose next segment names a person: Lookup's phone number and Verify's
+// entity identity
+const IDENTIFYING_PARENTS: [&str; 2] = ["PhoneNumbers", "Entities"];
+
+/// A sampled request and the response it got, with sensitive fields redacted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SampledExchange {
+    /// The client that made the request, e.g. `Twilio SMS`.
+    pub service: &'static str,
+    pub method: String,
+    /// The path and redacted query string.
+    pub path: String,
+    pub request_body: Option<String>,
+    pub status: u16,
+    pub response_body: String,
+}
+
+/// Receives the exchanges picked by a [`BodySampler`]. Called on the request path, so
+/// hand the exchange off rather than doing slow work here.
+pub trait SampleSink: Debug + Send + Sync {
+    fn record(&self, exchange: SampledExchange);
+}
+
+#[derive(Debug)]
+struct Rule {
+    path_contains: String,
+    rate: f64,
+    seen: AtomicU64,
+}
+
+/// Records a fraction of full request/response pairs to a [`SampleSink`], e.g. for
+/// offline QA or to catch a response format change between releases.
+///
+/// Sampling is spread evenly rather than random, so a rate of `0.01` records exactly
+/// every hundredth request. Bodies are redacted before they reach the sink: form
+/// fields, query parameters and JSON string values named `To`, `From`, `Body`, `Code`
+/// and the like are replaced, case-insensitively. So are path segments holding a
+/// phone number or an identity, e.g. Lookup's `/v2/PhoneNumbers/{number}`.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use twilio_client::sampling::{BodySampler, SampleSink, SampledExchange};
+/// # #[derive(Debug)]
+/// # struct QaBucket;
+/// # impl SampleSink for QaBucket {
+/// #     fn record(&self, _: SampledExchange) {}
+/// # }
+/// // 1% of traffic, but 10% of Verify checks
+/// let sampler = BodySampler::new(Arc::new(QaBucket), 0.01).path_rate("/VerificationCheck", 0.1);
+/// ```
+#[derive(Debug)]
+pub struct BodySampler {
+    sink: Arc<dyn SampleSink>,
+    // Checked in order; the last rule matches every path
+    rules: Vec<Rule>,
+    redacted_fields: HashSet<String>,
+}
+
+impl BodySampler {
+    /// Samples `rate` (0.0 to 1.0) of all requests.
+    pub fn new(sink: Arc<dyn SampleSink>, rate: f64) -> Self {
+        Self {
+            sink,
+            rules: vec![Rule::new(String::new(), rate)],
+            redacted_fields: DEFAULT_REDACTED_FIELDS
+                .iter()
+                .map(|field| field.to_string())
+                .collect(),
+        }
+    }
+
+    /// Samples `rate` of the requests whose path contains `path_contains` instead. The
+    /// first matching override wins.
+    pub fn path_rate(mut self, path_contains: impl Into<String>, rate: f64) -> Self {
+        let index = self.rules.len() - 1;
+        self.rules
+            .insert(index, Rule::new(path_contains.into(), rate));
+        self
+    }
+
+    /// Also redacts `field`, matched case-insensitively.
+    pub fn redact_field(mut self, field: impl AsRef<str>) -> Self {
+        self.redacted_fields
+            .insert(field.as_ref().to_ascii_lowercase());
+        self
+    }
+
+    /// Whether to sample the next request to `path`.
+    pub(crate) fn should_sample(&self, path: &str) -> bool {
+        let rule = self
+            .rules
+            .iter()
+            .find(|rule| path.contains(&rule.path_contains))
+            .unwrap_or_else(|| &self.rules[self.rules.len() - 1]);
+        let n = rule.seen.fetch_add(1, Ordering::Relaxed);
+        // True whenever `rate * n` crosses an integer, i.e. `rate` of the time
+        (rule.rate * (n + 1) as f64).floor() > (rule.rate * n as f64).floor()
+    }
+
+    pub(crate) fn record(
+        &self,
+        service: &'static str,
+        method: &str,
+        url: &reqwest::Url,
+        request_body: Option<&[u8]>,
+        status: u16,
+        response_body: &str,
+    ) {
+        let path = redact_path(url.path());
+        let path = match url.query() {
+            Some(query) => format!("{path}?{}", self.redact_form(query)),
+            None => path,
+        };
+        let request_body = request_body
+            .map(String::from_utf8_lossy)
+            .map(|body| self.redact_body(&body));
+        self.sink.record(SampledExchange {
+            service,
+            method: method.to_string(),
+            path,
+            request_body,
+            status,
+            response_body: self.redact_body(response_body),
+        });
+    }
+
+    fn is_redacted(&self, field: &str) -> bool {
+        self.redacted_fields.contains(&field.to_ascii_lowercase())
+    }
+
+    // JSON bodies (responses, Content API requests) or form bodies (2010 API requests)
+    fn redact_body(&self, body: &str) -> String {
+        match serde_json::from_str::<Value>(body) {
+            Ok(mut value) => {
+                self.redact_json(&mut value);
+                value.to_string()
+            }
+            Err(_) => self.redact_form(body),
+        }
+    }
+
+    fn redact_json(&self, value: &mut Value) {
+        match value {
+            Value::Object(fields) => {
+                for (key, field) in fields.iter_mut() {
+                    // Strings only, so numeric fields such as an error's `code` survive
+                    if self.is_redacted(key) && field.is_string() {
+                        *field = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_json(field);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_json(item)),
+            _ => {}
+        }
+    }
+
+    fn redact_form(&self, form: &str) -> String {
+        let pairs = url::form_urlencoded::parse(form.as_bytes()).map(|(key, value)| {
+            if self.is_redacted(&key) {
+                (key, REDACTED.into())
+            } else {
+                (key, value)
+            }
+        });
+        url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(pairs)
+            .finish()
+    }
+}
+
+/// Replaces the segments of `path` that hold an E.164 number or follow one of the
+/// [`IDENTIFYING_PARENTS`].
+fn redact_path(path: &str) -> String {
+    let mut previous = "";
+    path.split('/')
+        .map(|segment| {
+            let identifying = IDENTIFYING_PARENTS.contains(&previous) || is_e164(segment);
+            previous = segment;
+            if identifying && !segment.is_empty() {
+                "%5BREDACTED%5D"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn is_e164(segment: &str) -> bool {
+    let digits = segment
+        .strip_prefix('+')
+        .or_else(|| segment.strip_prefix("%2B"))
+        .or_else(|| segment.strip_prefix("%2b"));
+    digits.is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
+impl Rule {
+    fn new(path_contains: String, rate: f64) -> Self {
+        Self {
+            path_contains,
+            rate: rate.clamp(0.0, 1.0),
+            seen: AtomicU64::new(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::sampling::{BodySampler, SampleSink, SampledExchange};
+
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<SampledExchange>>);
+
+    impl SampleSink for Recorder {
+        fn record(&self, exchange: SampledExchange) {
+            self.0.lock().unwrap().push(exchange);
+        }
+    }
+
+    #[test]
+    fn samples_the_configured_fraction_per_path() {
+        let sampler =
+            BodySampler::new(Arc::new(Recorder::default()), 0.25).path_rate("/Verifications", 1.0);
+
+        let sampled = (0..100)
+            .filter(|_| sampler.should_sample("/Messages.json"))
+            .count();
+        assert_eq!(sampled, 25);
+        assert!((0..10).all(|_| sampler.should_sample("/v2/Services/VA1/Verifications")));
+    }
+
+    #[test]
+    fn personal_fields_are_redacted() {
+        let recorder = Arc::new(Recorder::default());
+        let sampler = BodySampler::new(recorder.clone(), 1.0).redact_field("friendly_name");
+        let url = reqwest::Url::parse("https://api.twilio.com/Messages.json?To=%2B1555&PageSize=5")
+            .unwrap();
+
+        sampler.record(
+            "Twilio SMS",
+            "POST",
+            &url,
+            Some(b"To=%2B254700123456&Body=Your+code+is+1234&SmartEncoded=true"),
+            201,
+            r#"{"sid":"SM1","to":"+254700123456","body":"Your code is 1234","friendly_name":"x","error_code":null}"#,
+        );
+
+        let exchange = recorder.0.lock().unwrap().remove(0);
+        assert_eq!(exchange.path, "/Messages.json?To=%5BREDACTED%5D&PageSize=5");
+        assert_eq!(
+            exchange.request_body.as_deref(),
+            Some("To=%5BREDACTED%5D&Body=%5BREDACTED%5D&SmartEncoded=true")
+        );
+        assert_eq!(
+            exchange.response_body,
+            r#"{"body":"[REDACTED]","error_code":null,"friendly_name":"[REDACTED]","sid":"SM1","to":"[REDACTED]"}"#
+        );
+    }
+
+    #[test]
+    fn numbers_and_identities_in_paths_are_redacted() {
+        let recorder = Arc::new(Recorder::default());
+        let sampler = BodySampler::new(recorder.clone(), 1.0);
+        let lookup = reqwest::Url::parse(
+            "https://lookups.twilio.com/v2/PhoneNumbers/%2B254700123456?Fields=line_type_intelligence",
+        )
+        .unwrap();
+        let entity = reqwest::Url::parse(
+            "https://verify.twilio.com/v2/Services/VA1/Entities/user-42/Factors",
+        )
+        .unwrap();
+
+        sampler.record(
+            "Twilio SMS",
+            "GET",
+            &lookup,
+            None,
+            200,
+            r#"{"phone_number":"+254700123456","caller_name":{"caller_name":"JANE DOE"}}"#,
+        );
+        sampler.record("Twilio Verify", "GET", &entity, None, 200, "{}");
+
+        let exchanges = recorder.0.lock().unwrap();
+        assert_eq!(
+            exchanges[0].path,
+            "/v2/PhoneNumbers/%5BREDACTED%5D?Fields=line_type_intelligence"
+        );
+        assert_eq!(
+            exchanges[0].response_body,
+            r#"{"caller_name":{"caller_name":"[REDACTED]"},"phone_number":"[REDACTED]"}"#
+        );
+        assert_eq!(
+            exchanges[1].path,
+            "/v2/Services/VA1/Entities/%5BREDACTED%5D/Factors"
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+use crate::sms::Client;
+use crate::{Page, Params, Phone, TwilioDateTime};
+
+/// A customer-owned number verified on the account (an OutgoingCallerId resource).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OutgoingCallerId {
+    pub sid: String,
+    pub phone_number: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub friendly_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_created: Option<TwilioDateTime>,
+}
+
+/// A pending verification of a number. Twilio calls the number and the owner has to
+/// enter `validation_code` on the keypad.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ValidationRequest {
+    pub phone_number: String,
+    pub validation_code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub call_sid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub friendly_name: Option<String>,
+}
+
+impl Client {
+    /// Starts verifying that the customer owns `phone`, so it can be used as a sender
+    /// or caller ID. Show the returned code to the customer before Twilio calls.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Request caller id validation",
+        skip(self, phone)
+    )]
+    pub async fn request_caller_id_validation(
+        &self,
+        phone: &Phone,
+        friendly_name: Option<&str>,
+    ) -> Result<ValidationRequest, ClientError> {
+        let url = self.account_url("OutgoingCallerIds.json");
+        let mut body = Params::new();
+        body.insert("PhoneNumber", phone.e164_number());
+        if let Some(friendly_name) = friendly_name {
+            body.insert("FriendlyName", friendly_name.to_string());
+        }
+        self.transport.request(Method::POST, &url, &body).await
+    }
+
+    /// Lists the first page of verified caller IDs on the account.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: List outgoing caller ids",
+        skip(self)
+    )]
+    pub async fn list_outgoing_caller_ids(&self) -> Result<Page<OutgoingCallerId>, ClientError> {
+        let url = self.account_url("OutgoingCallerIds.json");
+        self.transport
+            .request(Method::GET, &url, &Params::new())
+            .await
+    }
+}
@@ -0,0 +1,111 @@
+use reqwest::Method;
+use secrecy::ExposeSecret;
+
+use crate::health::{credentials_finding, ConfigReport};
+use crate::sms::{urlencode_from_string, Client, IncomingPhoneNumber, SenderConfig};
+use crate::{Page, Params, Phone};
+
+impl Client {
+    /// Checks the client against the account: that the credentials are accepted and
+    /// that the sender is a number or short code on the account that can send SMS.
+    /// Meant for startup or a deploy step. Problems, including Twilio being
+    /// unreachable, are reported as findings rather than errors.
+    ///
+    /// Messaging services live on another API host, so only the format of a
+    /// `messaging_service_sid` is checked.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Validate configuration",
+        skip(self)
+    )]
+    pub async fn validate_configuration(&self) -> ConfigReport {
+        let mut report = ConfigReport::default();
+
+        let account_sid = self.transport.account_sid.expose_secret();
+        let url = format!(
+            "/2010-04-01/Accounts/{}.json",
+            urlencode_from_string(account_sid)
+        );
+        if let Err(err) = self.transport.probe(&url).await {
+            report.error(credentials_finding(&err));
+            return report;
+        }
+
+        match &self.sender {
+            SenderConfig::Phone(phone) => self.check_sender_number(phone, &mut report).await,
+            SenderConfig::Pool(pool) => {
+                for phone in pool.phones() {
+                    self.check_sender_number(phone, &mut report).await;
+                }
+            }
+            SenderConfig::ShortCode(short_code) => {
+                self.check_short_code(short_code, &mut report).await
+            }
+            SenderConfig::MessagingService(service_sid) => {
+                let service_sid = service_sid.expose_secret();
+                if !service_sid.starts_with("MG") || service_sid.len() != 34 {
+                    report.error(
+                        "messaging_service_sid is not a Messaging Service SID (MG followed by \
+                         32 characters)",
+                    );
+                }
+            }
+        }
+
+        if self.transport.dry_run {
+            report.warning("dry_run is on; no messages will be sent");
+        }
+        if let Some(url) = &self.status_callback {
+            if url.scheme() != "https" {
+                report.warning(format!(
+                    "status_callback {url} is not HTTPS; Twilio's signature doesn't protect \
+                     the callback body from being read in transit"
+                ));
+            }
+        }
+
+        report
+    }
+
+    async fn check_sender_number(&self, phone: &Phone, report: &mut ConfigReport) {
+        let number = phone.e164_number();
+        let url = self.account_url("IncomingPhoneNumbers.json");
+        let mut params = Params::new();
+        params.insert("PhoneNumber", number.clone());
+
+        let page: Page<IncomingPhoneNumber> =
+            match self.transport.request(Method::GET, &url, &params).await {
+                Ok(page) => page,
+                Err(err) => {
+                    report.error(format!("could not look up sender {number}: {err}"));
+                    return;
+                }
+            };
+        match page.items.iter().find(|item| item.phone_number == number) {
+            None => report.error(format!(
+                "sender {number} is not a number on this account; buy or port it, or fix \
+                 the sender"
+            )),
+            Some(incoming) if !incoming.supports_sms() => report.error(format!(
+                "sender {number} can't send SMS; enable SMS on the number or pick another"
+            )),
+            Some(_) => {}
+        }
+    }
+
+    async fn check_short_code(&self, short_code: &str, report: &mut ConfigReport) {
+        let url = self.account_url("SMS/ShortCodes.json");
+        let mut params = Params::new();
+        params.insert("ShortCode", short_code.to_string());
+
+        let page: Result<Page<serde_json::Value>, _> =
+            self.transport.request(Method::GET, &url, &params).await;
+        match page {
+            Ok(page) if page.items.is_empty() => report.error(format!(
+                "short code {short_code} is not provisioned on this account"
+            )),
+            Ok(_) => {}
+            Err(err) => report.error(format!("could not look up short code {short_code}: {err}")),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use futures_util::TryStreamExt;
+use serde::Serialize;
+
+use crate::error::ClientError;
+use crate::sms::Client;
+use crate::{MessageFilter, Phone};
+
+/// Something [`UserErasure::purge_user`] couldn't delete.
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ErasureFailure {
+    /// The message SID or Verify entity identity.
+    pub resource: String,
+    pub error: String,
+}
+
+/// What [`UserErasure::purge_user`] removed, to keep as the record of an erasure
+/// request.
+#[derive(Clone, Debug, Default, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ErasureReport {
+    pub messages_deleted: Vec<String>,
+    pub verify_entities_deleted: Vec<String>,
+    pub failures: Vec<ErasureFailure>,
+}
+
+impl ErasureReport {
+    /// Whether everything found was deleted. Rerun the erasure otherwise, e.g. once
+    /// messages still being sent have settled.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Deletes everything Twilio holds about one person. Create one with
+/// [`Client::erasure`].
+///
+/// ```no_run
+/// # async fn run(
+/// #     sms: twilio_client::sms::Client,
+/// #     phone: twilio_client::Phone,
+/// # ) -> Result<(), twilio_client::error::ClientError> {
+/// let report = sms.erasure().purge_user(&phone).await?;
+/// assert!(report.is_complete());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct UserErasure<'a> {
+    client: &'a Client,
+    #[cfg(feature = "verify")]
+    verify_entities: Vec<(&'a crate::verify::Client, String)>,
+}
+
+impl<'a> UserErasure<'a> {
+    /// Also deletes Verify entity `identity`, and with it the person's factors, from
+    /// `client`'s service. Call once per service.
+    ///
+    /// ```no_run
+    /// # async fn run(
+    /// #     sms: twilio_client::sms::Client,
+    /// #     verify: twilio_client::verify::Client,
+    /// #     phone: twilio_client::Phone,
+    /// # ) -> Result<(), twilio_client::error::ClientError> {
+    /// let report = sms
+    ///     .erasure()
+    ///     .verify_entity(&verify, "user-42")
+    ///     .purge_user(&phone)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "verify")]
+    pub fn verify_entity(
+        mut self,
+        client: &'a crate::verify::Client,
+        identity: impl Into<String>,
+    ) -> Self {
+        self.verify_entities.push((client, identity.into()));
+        self
+    }
+
+    /// Deletes every message to or from `phone`, then the Verify entities. A failed
+    /// deletion is reported and the rest carry on; a failed listing stops the purge.
+    #[tracing::instrument(
+        parent = self.client.transport.parent_span(),
+        name = "Twilio SMS: Purge user",
+        skip(self, phone)
+    )]
+    pub async fn purge_user(&self, phone: &Phone) -> Result<ErasureReport, ClientError> {
+        let mut report = ErasureReport::default();
+
+        // Collected before deleting anything, as deleting shifts the pages
+        let mut sids = Vec::new();
+        for filter in [
+            MessageFilter::new().to(phone),
+            MessageFilter::new().from(phone),
+        ] {
+            let messages: Vec<_> = self.client.list_stream(&filter).try_collect().await?;
+            sids.extend(messages.into_iter().filter_map(|message| message.sid));
+        }
+        sids.sort();
+        sids.dedup();
+
+        for sid in sids {
+            match self.client.delete(&sid).await {
+                Ok(()) => report.messages_deleted.push(sid),
+                Err(err) => report.failures.push(ErasureFailure {
+                    resource: sid,
+                    error: err.to_string(),
+                }),
+            }
+        }
+
+        #[cfg(feature = "verify")]
+        for (client, identity) in &self.verify_entities {
+            match client.delete_entity(identity).await {
+                Ok(true) => report.verify_entities_deleted.push(identity.clone()),
+                Ok(false) => {}
+                Err(err) => report.failures.push(ErasureFailure {
+                    resource: identity.clone(),
+                    error: err.to_string(),
+                }),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+impl Client {
+    /// Starts a data erasure for one person, e.g. a GDPR request.
+    pub fn erasure(&self) -> UserErasure<'_> {
+        UserErasure {
+            client: self,
+            #[cfg(feature = "verify")]
+            verify_entities: Vec::new(),
+        }
+    }
+
+    /// Deletes every message to or from `phone`. A shorthand for
+    /// [`UserErasure::purge_user`] without Verify entities.
+    pub async fn purge_user(&self, phone: &Phone) -> Result<ErasureReport, ClientError> {
+        self.erasure().purge_user(phone).await
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Method;
+
+use crate::error::{ClientError, TwilioError};
+use crate::sms::{sandbox, Client, SendSmsResponse, Status};
+use crate::{ErrorCode, Params, Phone};
+
+/// Looks up the WhatsApp number of a recipient, if they have one.
+#[async_trait]
+pub trait WhatsAppDirectory: Debug + Send + Sync {
+    async fn whatsapp_number(&self, to: &Phone) -> Option<Phone>;
+}
+
+/// Resends an SMS that failed permanently as a WhatsApp template message.
+#[derive(Clone, Debug)]
+pub struct WhatsAppFallback {
+    from: Phone,
+    content_sid: String,
+    variable: String,
+    directory: Arc<dyn WhatsAppDirectory>,
+    // How often and how long to poll the SMS for its final status
+    delivery_wait: Option<(Duration, Duration)>,
+}
+
+impl WhatsAppFallback {
+    /// Sends template `content_sid` from the WhatsApp sender `from`, to the numbers
+    /// `directory` knows about. The SMS text fills the template's `{{1}}` variable.
+    pub fn new(
+        from: Phone,
+        content_sid: impl Into<String>,
+        directory: Arc<dyn WhatsAppDirectory>,
+    ) -> Self {
+        Self {
+            from,
+            content_sid: content_sid.into(),
+            variable: "1".to_string(),
+            directory,
+            delivery_wait: None,
+        }
+    }
+
+    /// The template variable the SMS text fills instead of `1`.
+    pub fn variable(mut self, variable: impl Into<String>) -> Self {
+        self.variable = variable.into();
+        self
+    }
+
+    /// Once Twilio accepts the SMS, fetch it every `interval` for up to `max_wait`
+    /// and fall back if it ends up failed or undelivered. Without this, only SMS
+    /// that Twilio refuses outright fall back: landlines, unreachable handsets and
+    /// carrier blocks are only reported after the send.
+    ///
+    /// If fetching the message fails, the accepted SMS is returned with
+    /// [`FallbackStep::DeliveryUnknown`] rather than an error, so it isn't resent.
+    pub fn wait_for_delivery(mut self, interval: Duration, max_wait: Duration) -> Self {
+        self.delivery_wait = Some((interval, max_wait));
+        self
+    }
+}
+
+/// One decision taken by [`Client::send_with_fallback`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FallbackStep {
+    SmsAccepted,
+    /// Checking whether the accepted SMS was delivered failed with this error, so it
+    /// isn't known to have failed and isn't resent.
+    DeliveryUnknown(String),
+    /// The SMS failed with `error_code`; `permanent` failures trigger the fallback.
+    SmsFailed {
+        error_code: Option<i32>,
+        permanent: bool,
+    },
+    /// No fallback is configured, or the recipient has no known WhatsApp number.
+    NoFallback,
+    WhatsAppAccepted,
+}
+
+/// The result of [`Client::send_with_fallback`].
+#[derive(Clone, Debug)]
+pub struct FallbackOutcome {
+    /// The response for the last message sent, SMS or WhatsApp.
+    pub response: SendSmsResponse,
+    pub trail: Vec<FallbackStep>,
+}
+
+impl FallbackOutcome {
+    pub fn fell_back(&self) -> bool {
+        self.trail.contains(&FallbackStep::WhatsAppAccepted)
+    }
+}
+
+impl Client {
+    /// Sends `content` like [`Client::send`]. If the SMS fails permanently and the
+    /// client has a [`WhatsAppFallback`], it is resent over WhatsApp. Errors that
+    /// aren't permanent are returned as usual.
+    ///
+    /// Twilio refuses some SMS straight away, e.g. to numbers that can't take SMS
+    /// (21614). Most permanent failures, e.g. landlines and unreachable carriers
+    /// (30006) or unknown numbers (30005), only show in the message's status later,
+    /// and fall back only with [`WhatsAppFallback::wait_for_delivery`].
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Send sms with fallback",
+        skip(self, to, content)
+    )]
+    pub async fn send_with_fallback(
+        &self,
+        to: &Phone,
+        content: &str,
+    ) -> Result<FallbackOutcome, ClientError> {
+        let mut trail = Vec::new();
+        let (sms, error_code) = match self.send(to, content, None, None).await {
+            Ok(resp) if matches!(resp.status, Some(Status::Failed | Status::Undelivered)) => {
+                let code = resp.error_code;
+                (Ok(resp), code)
+            }
+            Ok(resp) => {
+                trail.push(FallbackStep::SmsAccepted);
+                match self.undelivered(&resp).await {
+                    Ok(Some(failed)) => {
+                        let code = failed.error_code;
+                        (Ok(failed), code)
+                    }
+                    Ok(None) => {
+                        return Ok(FallbackOutcome {
+                            response: resp,
+                            trail,
+                        })
+                    }
+                    // Twilio has the message, so an error here must not lead the
+                    // caller to send it again
+                    Err(err) => {
+                        tracing::warn!(
+                            "Twilio SMS: failed to check delivery of {:?}: {}",
+                            resp.sid,
+                            err
+                        );
+                        trail.push(FallbackStep::DeliveryUnknown(err.to_string()));
+                        return Ok(FallbackOutcome {
+                            response: resp,
+                            trail,
+                        });
+                    }
+                }
+            }
+            Err(err) => {
+                let code = match &err {
+                    ClientError::ServerResponse { message, .. } => {
+                        TwilioError::from_body(message).code
+                    }
+                    _ => None,
+                };
+                (Err(err), code)
+            }
+        };
+
+        let permanent = error_code.is_some_and(|code| ErrorCode::from(code).is_permanent());
+        trail.push(FallbackStep::SmsFailed {
+            error_code,
+            permanent,
+        });
+
+        let whatsapp_to = match (&self.fallback, permanent) {
+            (Some(fallback), true) => fallback
+                .directory
+                .whatsapp_number(to)
+                .await
+                .map(|number| (fallback, number)),
+            _ => None,
+        };
+        let Some((fallback, number)) = whatsapp_to else {
+            trail.push(FallbackStep::NoFallback);
+            return sms.map(|response| FallbackOutcome { response, trail });
+        };
+
+        let response = self.send_whatsapp(fallback, &number, content).await?;
+        trail.push(FallbackStep::WhatsAppAccepted);
+        Ok(FallbackOutcome { response, trail })
+    }
+
+    /// Waits for an accepted SMS to reach its final status when the fallback asks to,
+    /// and returns the message if it failed.
+    async fn undelivered(
+        &self,
+        accepted: &SendSmsResponse,
+    ) -> Result<Option<SendSmsResponse>, ClientError> {
+        let wait = self.fallback.as_ref().and_then(|f| f.delivery_wait);
+        let (Some((interval, max_wait)), Some(sid)) = (wait, &accepted.sid) else {
+            return Ok(None);
+        };
+        let lifecycle = self.wait_for_final_status(sid, interval, max_wait).await?;
+        match lifecycle.terminal() {
+            // Fetched again for the error code, which the lifecycle doesn't keep
+            Some(Status::Failed | Status::Undelivered) => self.fetch(sid).await.map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    async fn send_whatsapp(
+        &self,
+        fallback: &WhatsAppFallback,
+        to: &Phone,
+        content: &str,
+    ) -> Result<SendSmsResponse, ClientError> {
+        let url = self.account_url("Messages.json");
+
+        let mut body = Params::new();
+        body.insert("To", format!("whatsapp:{}", to.e164_number()));
+        match &self.whatsapp_sandbox {
+            // The sandbox has no access to the account's templates
+            Some(sandbox) => {
+                body.insert(
+                    "From",
+                    format!("whatsapp:{}", sandbox.sender().e164_number()),
+                );
+                body.insert("Body", content.to_string());
+            }
+            None => {
+                let variables = serde_json::json!({ fallback.variable.as_str(): content });
+                self.validate_content_variables(&fallback.content_sid, &variables)
+                    .await?;
+                body.insert("From", format!("whatsapp:{}", fallback.from.e164_number()));
+                body.insert("ContentSid", fallback.content_sid.clone());
+                body.insert("ContentVariables", variables.to_string());
+            }
+        }
+        self.apply_status_callback(&mut body);
+
+        let resp = self.transport.request(Method::POST, &url, &body).await;
+        if let (Some(sandbox), Err(ClientError::ServerResponse { message, .. })) =
+            (&self.whatsapp_sandbox, &resp)
+        {
+            if TwilioError::from_body(message).code == Some(sandbox::NOT_JOINED_ERROR_CODE) {
+                tracing::warn!(
+                    "Twilio SMS: recipient hasn't joined the WhatsApp sandbox; they must send \"{}\" first",
+                    sandbox.join_message()
+                );
+            }
+        }
+        resp
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::error::ClientError;
+use crate::sms::{Client, Status};
+
+// How many messages a client remembers the statuses of
+const TRACKED_MESSAGES: usize = 10_000;
+
+impl Status {
+    /// Whether Twilio is done with the message. Only a WhatsApp read receipt can
+    /// follow a final status.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Status::Delivered
+                | Status::Undelivered
+                | Status::Failed
+                | Status::Read
+                | Status::Received
+                | Status::PartiallyDelivered
+                | Status::Canceled
+        )
+    }
+
+    // How far along a message is; a later status never has a lower rank
+    fn rank(&self) -> u8 {
+        match self {
+            // Ranked lowest so an unknown status never moves a known one backwards
+            Status::Unknown(_) | Status::Scheduled | Status::Accepted => 0,
+            Status::Queued | Status::Receiving => 1,
+            Status::Sending => 2,
+            Status::Sent => 3,
+            Status::Delivered
+            | Status::Undelivered
+            | Status::Failed
+            | Status::Received
+            | Status::PartiallyDelivered
+            | Status::Canceled => 4,
+            Status::Read => 5,
+        }
+    }
+}
+
+/// How [`MessageLifecycle::observe`] treated a status.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Transition {
+    /// The status moved the message forward.
+    Advanced,
+    /// The message already had this status, e.g. a retried callback.
+    Repeated,
+    /// The status is behind the current one, or contradicts a final one, e.g. `sent`
+    /// arriving after `delivered`. It was ignored.
+    OutOfOrder,
+}
+
+/// The statuses of one message, in the order it really went through them.
+///
+/// Feed it every status seen, from [`Client::fetch`] or from status callbacks, which
+/// Twilio doesn't guarantee to deliver in order. Late and duplicate statuses are
+/// flagged and don't move the message backwards.
+#[derive(Clone, Debug, Default)]
+pub struct MessageLifecycle {
+    history: Vec<Status>,
+    out_of_order: Vec<Status>,
+}
+
+impl MessageLifecycle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&mut self, status: Status) -> Transition {
+        let Some(current) = self.history.last() else {
+            self.history.push(status);
+            return Transition::Advanced;
+        };
+        if status == *current {
+            return Transition::Repeated;
+        }
+        if status.rank() > current.rank() && (!current.is_final() || status == Status::Read) {
+            self.history.push(status);
+            return Transition::Advanced;
+        }
+        tracing::debug!("Twilio SMS: ignoring {status:?} after {current:?}");
+        self.out_of_order.push(status);
+        Transition::OutOfOrder
+    }
+
+    pub fn current(&self) -> Option<Status> {
+        self.history.last().cloned()
+    }
+
+    /// The final status, once the message has one.
+    pub fn terminal(&self) -> Option<Status> {
+        self.current().filter(|status| status.is_final())
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.terminal().is_some()
+    }
+
+    /// The statuses accepted, oldest first.
+    pub fn history(&self) -> &[Status] {
+        &self.history
+    }
+
+    /// The statuses that were ignored as late or contradictory.
+    pub fn out_of_order(&self) -> &[Status] {
+        &self.out_of_order
+    }
+}
+
+/// The statuses a client has seen of its recent messages, so one fetched or called
+/// back again is acted on once.
+#[derive(Debug, Default)]
+pub(crate) struct StatusTransitions {
+    // The lifecycles, and their message SIDs oldest first to evict the oldest
+    seen: Mutex<(HashMap<String, MessageLifecycle>, VecDeque<String>)>,
+}
+
+impl StatusTransitions {
+    /// Whether `status` moves message `sid` forward, rather than repeating or
+    /// trailing a status already seen: `None` if not, otherwise whether it is the
+    /// message's first final status.
+    pub(crate) fn advance(&self, sid: &str, status: Status) -> Option<bool> {
+        let mut seen = self.seen.lock().unwrap();
+        let (lifecycles, order) = &mut *seen;
+        if !lifecycles.contains_key(sid) {
+            if order.len() >= TRACKED_MESSAGES {
+                if let Some(oldest) = order.pop_front() {
+                    lifecycles.remove(&oldest);
+                }
+            }
+            order.push_back(sid.to_string());
+        }
+        let lifecycle = lifecycles.entry(sid.to_string()).or_default();
+        let was_final = lifecycle.is_final();
+        (lifecycle.observe(status) == Transition::Advanced)
+            .then(|| !was_final && lifecycle.is_final())
+    }
+}
+
+impl Client {
+    /// Fetches the message every `interval` until it reaches a final status or
+    /// `max_wait` runs out. Check [`MessageLifecycle::is_final`] to tell the two
+    /// apart.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Wait for final status",
+        skip(self)
+    )]
+    pub async fn wait_for_final_status(
+        &self,
+        message_sid: &str,
+        interval: Duration,
+        max_wait: Duration,
+    ) -> Result<MessageLifecycle, ClientError> {
+        let clock = &self.transport.clock;
+        let deadline = clock.now() + max_wait;
+        let mut lifecycle = MessageLifecycle::new();
+        loop {
+            if let Some(status) = self.fetch(message_sid).await?.status {
+                lifecycle.observe(status);
+            }
+            if lifecycle.is_final() || clock.now() + interval > deadline {
+                return Ok(lifecycle);
+            }
+            clock.sleep(interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sms::{MessageLifecycle, Status, Transition};
+
+    #[test]
+    fn statuses_advance_in_order() {
+        let mut lifecycle = MessageLifecycle::new();
+        for status in [Status::Queued, Status::Sent, Status::Delivered] {
+            assert_eq!(lifecycle.observe(status), Transition::Advanced);
+        }
+
+        assert_eq!(lifecycle.terminal(), Some(Status::Delivered));
+        assert_eq!(lifecycle.observe(Status::Read), Transition::Advanced);
+    }
+
+    #[test]
+    fn late_and_repeated_callbacks_are_flagged() {
+        let mut lifecycle = MessageLifecycle::new();
+        lifecycle.observe(Status::Delivered);
+
+        assert_eq!(lifecycle.observe(Status::Sent), Transition::OutOfOrder);
+        assert_eq!(lifecycle.observe(Status::Delivered), Transition::Repeated);
+        assert_eq!(lifecycle.observe(Status::Failed), Transition::OutOfOrder);
+        assert_eq!(lifecycle.current(), Some(Status::Delivered));
+        assert_eq!(lifecycle.out_of_order(), [Status::Sent, Status::Failed]);
+    }
+}
@@ -0,0 +1,251 @@
+use futures_util::stream::{self, Stream, TryStreamExt};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+use crate::retry::Idempotency;
+use crate::sms::{Client, SendSmsResponse, Status};
+#[cfg(feature = "webhooks")]
+use crate::webhooks::MessageStatusCallback;
+use crate::{Address, MessageFilter, Page, Params, TwilioDateTime};
+
+/// The message returned by [`Client::cancel`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CancelResponse {
+    pub sid: String,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_updated: Option<TwilioDateTime>,
+}
+
+impl CancelResponse {
+    /// Whether Twilio canceled the message. A message that already left the
+    /// schedule keeps its status.
+    pub fn is_canceled(&self) -> bool {
+        self.status == Status::Canceled
+    }
+}
+
+/// Whether the recipient acted on a message sent with
+/// [`SendOptions::provide_feedback`](crate::sms::SendOptions::provide_feedback).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum FeedbackOutcome {
+    Confirmed,
+    Unconfirmed,
+}
+
+/// The feedback recorded by [`Client::confirm_feedback`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MessageFeedback {
+    pub message_sid: String,
+    pub outcome: FeedbackOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_created: Option<TwilioDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_updated: Option<TwilioDateTime>,
+}
+
+/// A file attached to an MMS, as listed by [`Client::list_media`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MediaResource {
+    pub sid: String,
+    pub content_type: String,
+    /// The media resource, relative to `https://api.twilio.com`.
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_sid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_created: Option<TwilioDateTime>,
+}
+
+impl MediaResource {
+    /// Where the file itself is served, i.e. `uri` without the `.json` suffix. Fetch it
+    /// with the account credentials.
+    pub fn content_uri(&self) -> &str {
+        self.uri.strip_suffix(".json").unwrap_or(&self.uri)
+    }
+}
+
+impl Client {
+    /// Fetches a message by SID, e.g. to poll its delivery status after sending.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Fetch message",
+        skip(self)
+    )]
+    pub async fn fetch(&self, message_sid: &str) -> Result<SendSmsResponse, ClientError> {
+        let url = self.account_url(&format!("Messages/{message_sid}.json"));
+        let message: SendSmsResponse = self
+            .transport
+            .request(Method::GET, &url, &Params::new())
+            .await?;
+        if let Some(sid) = &message.sid {
+            let from = message
+                .from
+                .as_deref()
+                .and_then(|from| Address::parse(from).ok());
+            let to = message.to.as_deref().and_then(|to| Address::parse(to).ok());
+            self.observe_status(
+                sid,
+                from.as_ref().and_then(Address::phone),
+                to.as_ref().and_then(Address::phone),
+                message.status.clone(),
+                message.error_code,
+            )
+            .await;
+        }
+        Ok(message)
+    }
+
+    /// Records a status callback as [`Client::fetch`] does the message it fetches: in
+    /// the receipt store and, once final, in the pooled sender's health. Callbacks
+    /// Twilio retries, or for a status already fetched, are recorded once.
+    #[cfg(feature = "webhooks")]
+    pub async fn record_status_callback(&self, callback: &MessageStatusCallback) {
+        self.observe_status(
+            &callback.message_sid,
+            callback.from.as_ref().and_then(Address::phone),
+            callback.to.phone(),
+            Some(callback.status.clone()),
+            callback.error_code.map(i32::from),
+        )
+        .await;
+    }
+
+    /// Cancels a message scheduled with [`SendOptions::send_at`](crate::sms::SendOptions::send_at).
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Cancel message",
+        skip(self)
+    )]
+    pub async fn cancel(&self, message_sid: &str) -> Result<CancelResponse, ClientError> {
+        let url = self.account_url(&format!("Messages/{message_sid}.json"));
+        let mut body = Params::new();
+        body.insert("Status", "canceled".to_string());
+        self.transport
+            .request_idempotent(Method::POST, &url, &body, Idempotency::Safe)
+            .await
+    }
+
+    /// Tells Twilio the recipient acted on the message, e.g. entered the code it
+    /// carried, so it counts as converted. The message must have been sent with
+    /// `provide_feedback`.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Confirm feedback",
+        skip(self)
+    )]
+    pub async fn confirm_feedback(
+        &self,
+        message_sid: &str,
+    ) -> Result<MessageFeedback, ClientError> {
+        let url = self.account_url(&format!("Messages/{message_sid}/Feedback.json"));
+        let mut body = Params::new();
+        body.insert("Outcome", "confirmed".to_string());
+        self.transport
+            .request_idempotent(Method::POST, &url, &body, Idempotency::Safe)
+            .await
+    }
+
+    /// Redacts the body of a sent message by overwriting it with an empty one, e.g. to
+    /// stop keeping PII at Twilio once the message is delivered.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Redact message",
+        skip(self)
+    )]
+    pub async fn redact(&self, message_sid: &str) -> Result<SendSmsResponse, ClientError> {
+        let url = self.account_url(&format!("Messages/{message_sid}.json"));
+        let mut body = Params::new();
+        body.insert("Body", String::new());
+        // Redacting twice leaves the message as it was, so retries are harmless
+        self.transport
+            .request_idempotent(Method::POST, &url, &body, Idempotency::Safe)
+            .await
+    }
+
+    /// Deletes a message and its media from the account. Twilio refuses to delete
+    /// messages that are still being sent.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Delete message",
+        skip(self)
+    )]
+    pub async fn delete(&self, message_sid: &str) -> Result<(), ClientError> {
+        let url = self.account_url(&format!("Messages/{message_sid}.json"));
+        self.transport
+            .request(Method::DELETE, &url, &Params::new())
+            .await
+    }
+
+    /// Lists the first page of media attached to a message. Follow the rest with
+    /// [`Client::next_page`].
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: List media",
+        skip(self)
+    )]
+    pub async fn list_media(&self, message_sid: &str) -> Result<Page<MediaResource>, ClientError> {
+        let url = self.account_url(&format!("Messages/{message_sid}/Media.json"));
+        self.transport
+            .request(Method::GET, &url, &Params::new())
+            .await
+    }
+
+    /// Deletes a file attached to a message, e.g. to purge MMS content under a
+    /// retention policy. The message itself is kept.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Delete media",
+        skip(self)
+    )]
+    pub async fn delete_media(
+        &self,
+        message_sid: &str,
+        media_sid: &str,
+    ) -> Result<(), ClientError> {
+        let url = self.account_url(&format!("Messages/{message_sid}/Media/{media_sid}.json"));
+        self.transport
+            .request(Method::DELETE, &url, &Params::new())
+            .await
+    }
+
+    /// Lists the first page of messages matching `filter`, newest first. Follow the
+    /// rest with [`Client::next_page`].
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: List messages",
+        skip(self, filter)
+    )]
+    pub async fn list(&self, filter: &MessageFilter) -> Result<Page<SendSmsResponse>, ClientError> {
+        let url = self.account_url("Messages.json");
+        self.transport
+            .request(Method::GET, &url, &filter.to_params())
+            .await
+    }
+
+    /// Every message matching `filter`, fetching further pages as the stream is polled.
+    pub fn list_stream<'a>(
+        &'a self,
+        filter: &'a MessageFilter,
+    ) -> impl Stream<Item = Result<SendSmsResponse, ClientError>> + 'a {
+        // `None` before the first page, then the link to the next one
+        let first: Option<Option<String>> = None;
+        stream::try_unfold(first, move |next| async move {
+            let page: Page<SendSmsResponse> = match next {
+                None => self.list(filter).await?,
+                Some(Some(uri)) => self.transport.fetch_page(&uri).await?,
+                Some(None) => return Ok(None),
+            };
+            let next = Some(page.next_page_uri);
+            let items = stream::iter(page.items.into_iter().map(Ok::<_, ClientError>));
+            Ok::<_, ClientError>(Some((items, next)))
+        })
+        .try_flatten()
+    }
+}
@@ -1,37 +1,171 @@
+mod archive;
+mod caller_ids;
+mod config_check;
+mod erasure;
+mod fallback;
+mod lifecycle;
+mod messages;
+mod numbers;
+mod options;
+mod preflight;
+mod price;
+mod receipts;
+mod report;
+mod rotation;
+mod sandbox;
+pub mod segments;
+mod sender;
+mod sender_health;
+mod shadow;
+mod split;
+mod test_numbers;
+pub use archive::*;
+pub use caller_ids::*;
+pub use erasure::*;
+pub use fallback::*;
+pub use lifecycle::*;
+pub use messages::*;
+pub use numbers::*;
+pub use options::*;
+pub use preflight::*;
+pub use price::*;
+pub use receipts::*;
+pub use report::*;
+pub use rotation::*;
+pub use sandbox::*;
+pub use sender::*;
+pub use sender_health::*;
+pub use shadow::*;
+pub use split::*;
+pub use test_numbers::*;
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::cache::ResponseCache;
+use crate::clock::{Clock, SystemClock};
 use crate::error::ClientError;
-use crate::make_request::make_request;
-use crate::Phone;
-use reqwest::Url;
+use crate::health::HealthCheck;
+use crate::limiter::{ConcurrencyLimiter, Priority};
+use crate::make_request::{Transport, STUB_BASE_URL};
+use crate::retry::RetryPolicy;
+use crate::sampling::BodySampler;
+use crate::stub::StubResponses;
+use crate::upload::Upload;
+use crate::{ErrorCode, Page, Params, Phone, Pseudonymizer, Raw, TwilioDateTime};
+use reqwest::{Method, Url};
 use secrecy::{ExposeSecret, SecretString};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+pub use crate::make_request::{DEFAULT_MAX_RESPONSE_SIZE, DEFAULT_TIMEOUT};
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SendSmsResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none", alias = "Body")]
     pub body: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub date_created: Option<String>,
+    pub date_created: Option<TwilioDateTime>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub date_sent: Option<String>,
+    pub date_sent: Option<TwilioDateTime>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub date_updated: Option<String>,
+    pub date_updated: Option<TwilioDateTime>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_code: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// What the message cost, e.g. `-0.00750`. Twilio fills it in after the send;
+    /// [`SendSmsResponse::cost`] normalizes the sign.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_unit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<Status>,
     #[serde(skip_serializing_if = "Option::is_none", alias = "To")]
     pub to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "From")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+    /// How many segments the body was sent as, which is what carriers bill for.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_count",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub num_segments: Option<u32>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_count",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub num_media: Option<u32>,
+    /// Set when the message was sent through a Messaging Service.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messaging_service_sid: Option<String>,
+    /// The message resource's path, relative to `https://api.twilio.com`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    /// Paths of related resources by name, e.g. `media` and `feedback`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subresource_uris: Option<HashMap<String, String>>,
+    /// Set on responses synthesized by a [`ClientBuilder::dry_run`] client.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+}
+
+impl SendSmsResponse {
+    /// The message's `error_code` decoded, if it has one.
+    pub fn error(&self) -> Option<ErrorCode> {
+        self.error_code.map(ErrorCode::from)
+    }
+
+    /// Why the message failed, e.g. `"landline or unreachable carrier"`, falling back to
+    /// Twilio's `error_message` for codes without a description. `None` when the
+    /// message has no error.
+    pub fn failure_reason(&self) -> Option<String> {
+        let error = self.error()?;
+        error
+            .description()
+            .map(str::to_string)
+            .or_else(|| self.error_message.clone())
+            .or_else(|| Some(error.to_string()))
+    }
+}
+
+// Twilio sends counts such as num_segments as strings, e.g. `"2"`
+fn deserialize_count<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Count {
+        Number(u32),
+        Text(String),
+    }
+
+    match Option::<Count>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Count::Number(count)) => Ok(Some(count)),
+        Some(Count::Text(count)) => count.parse().map(Some).map_err(serde::de::Error::custom),
+    }
 }
 
 /// The status of the message
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+///
+/// Statuses Twilio adds later parse as [`Status::Unknown`], which keeps the status as
+/// sent, rather than failing the response.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
 pub enum Status {
     Queued,
     Sending,
@@ -44,18 +178,141 @@ pub enum Status {
     Accepted,
     Scheduled,
     Read,
-    #[serde(rename = "partially_delivered")]
     PartiallyDelivered,
     Canceled,
+    /// A status this version of the crate doesn't know, as Twilio sent it.
+    Unknown(String),
+}
+
+impl Status {
+    /// The status as Twilio spells it, e.g. `"partially_delivered"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Status::Queued => "queued",
+            Status::Sending => "sending",
+            Status::Sent => "sent",
+            Status::Failed => "failed",
+            Status::Delivered => "delivered",
+            Status::Undelivered => "undelivered",
+            Status::Receiving => "receiving",
+            Status::Received => "received",
+            Status::Accepted => "accepted",
+            Status::Scheduled => "scheduled",
+            Status::Read => "read",
+            Status::PartiallyDelivered => "partially_delivered",
+            Status::Canceled => "canceled",
+            Status::Unknown(status) => status,
+        }
+    }
+}
+
+impl From<String> for Status {
+    fn from(status: String) -> Self {
+        match status.as_str() {
+            "queued" => Status::Queued,
+            "sending" => Status::Sending,
+            "sent" => Status::Sent,
+            "failed" => Status::Failed,
+            "delivered" => Status::Delivered,
+            "undelivered" => Status::Undelivered,
+            "receiving" => Status::Receiving,
+            "received" => Status::Received,
+            "accepted" => Status::Accepted,
+            "scheduled" => Status::Scheduled,
+            "read" => Status::Read,
+            "partially_delivered" => Status::PartiallyDelivered,
+            "canceled" => Status::Canceled,
+            _ => Status::Unknown(status),
+        }
+    }
+}
+
+impl From<Status> for String {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Unknown(status) => status,
+            status => status.as_str().to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Status {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Status".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "anyOf": [
+                {
+                    "enum": [
+                        "queued", "sending", "sent", "failed", "delivered", "undelivered",
+                        "receiving", "received", "accepted", "scheduled", "read",
+                        "partially_delivered", "canceled"
+                    ]
+                },
+                {
+                    "type": "string",
+                    "description": "A status added by Twilio after this version of the crate"
+                }
+            ]
+        })
+    }
+}
+
+/// Who sent the message
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum Direction {
+    /// Received by one of the account's numbers.
+    Inbound,
+    /// Sent through the REST API.
+    OutboundApi,
+    /// Sent during a call, e.g. by TwiML `<Sms>`.
+    OutboundCall,
+    /// Sent as the TwiML reply to an inbound message.
+    OutboundReply,
+}
+
+impl Direction {
+    pub fn is_outbound(self) -> bool {
+        self != Direction::Inbound
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct ClientBuilder {
     base_url: Option<Url>,
     sender: Option<Phone>,
+    short_code: Option<String>,
+    sender_pool: Option<Arc<SenderPool>>,
+    messaging_service_sid: Option<SecretString>,
     account_sid: Option<SecretString>,
     auth_token: Option<SecretString>,
     timeout: Option<Duration>,
+    max_response_size: Option<usize>,
+    stub: Option<StubResponses>,
+    local_address: Option<IpAddr>,
+    cache: Option<Arc<ResponseCache>>,
+    sampler: Option<Arc<BodySampler>>,
+    hedge_after: Option<Duration>,
+    limiter: Option<ConcurrencyLimiter>,
+    retry: Option<RetryPolicy>,
+    priority: Priority,
+    dry_run: bool,
+    read_only: bool,
+    validity_period: Option<Duration>,
+    receipt_store: Option<Arc<dyn ReceiptStore>>,
+    pseudonymizer: Option<Pseudonymizer>,
+    fallback: Option<WhatsAppFallback>,
+    whatsapp_sandbox: Option<WhatsAppSandbox>,
+    status_callback: Option<Url>,
+    preflight: Option<Arc<PreflightPolicy>>,
+    clock: Option<Arc<dyn Clock>>,
+    #[cfg(feature = "content")]
+    content_validation: Option<crate::content::Client>,
 }
 
 impl ClientBuilder {
@@ -73,6 +330,26 @@ impl ClientBuilder {
         self
     }
 
+    /// Send from a short code, e.g. `"894546"`, instead of a `sender` phone. Short
+    /// codes are 3 to 8 digits; anything else fails [`ClientBuilder::build`].
+    pub fn short_code(mut self, short_code: impl Into<String>) -> Self {
+        self.short_code = Some(short_code.into());
+        self
+    }
+
+    /// Rotate across several sender phones instead of a single `sender`.
+    pub fn sender_pool(mut self, pool: SenderPool) -> Self {
+        self.sender_pool = Some(Arc::new(pool));
+        self
+    }
+
+    /// Send through a Messaging Service instead of a `sender` phone, leaving the choice
+    /// of number to Twilio. Ignored when a `sender` or `sender_pool` is set.
+    pub fn messaging_service_sid(mut self, service_sid: SecretString) -> Self {
+        self.messaging_service_sid = Some(service_sid);
+        self
+    }
+
     pub fn account_sid(mut self, account_sid: SecretString) -> Self {
         self.account_sid = Some(account_sid);
         self
@@ -88,13 +365,182 @@ impl ClientBuilder {
         self
     }
 
-    pub fn build(self) -> Result<Client, ClientError> {
+    /// The largest response body, in bytes, the client will buffer before giving up.
+    pub fn max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
+    /// Bind outgoing connections to `address`, e.g. to leave through the egress IP that
+    /// is allowed by the account's IP access control list.
+    pub fn local_address(mut self, address: IpAddr) -> Self {
+        self.local_address = Some(address);
+        self
+    }
+
+    /// Cache `GET` responses (message, service, template fetches) in `cache`.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Record a sample of redacted request/response pairs with `sampler`.
+    pub fn sampler(mut self, sampler: BodySampler) -> Self {
+        self.sampler = Some(Arc::new(sampler));
+        self
+    }
+
+    /// Send a second copy of a read, e.g. [`Client::fetch`], that has no response
+    /// after `delay`, and use whichever copy succeeds first. Sends are never hedged.
+    pub fn hedge_after(mut self, delay: Duration) -> Self {
+        self.hedge_after = Some(delay);
+        self
+    }
+
+    /// Share `limiter` with other clients to cap in-flight requests across all of them.
+    pub fn concurrency_limiter(mut self, limiter: ConcurrencyLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// Record a [`Receipt`] in `store` for every message Twilio accepts.
+    pub fn receipt_store(mut self, store: Arc<dyn ReceiptStore>) -> Self {
+        self.receipt_store = Some(store);
+        self
+    }
+
+    /// Store recipients in [`Receipt`]s as their keyed pseudonym rather than the
+    /// plain number.
+    pub fn pseudonymizer(mut self, pseudonymizer: Pseudonymizer) -> Self {
+        self.pseudonymizer = Some(pseudonymizer);
+        self
+    }
+
+    /// Validate, rate limit and log sends as usual but skip the call to Twilio,
+    /// returning a synthesized response with `dry_run` set. Useful for rehearsing
+    /// campaign jobs against a production configuration.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Allow only reads, e.g. fetches and lists, and fail every other call with
+    /// [`ClientError::ReadOnlyMode`] before it reaches Twilio. Lets disaster recovery
+    /// drills and game days run against production credentials without texting real users.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// The priority of this client's requests when a shared `concurrency_limiter` is
+    /// saturated. Defaults to [`Priority::Normal`].
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Retry transient failures according to `policy`. Off by default.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Have Twilio post status changes of every message to `url`, unless the send sets
+    /// its own [`SendOptions::status_callback`].
+    pub fn status_callback(mut self, url: Url) -> Self {
+        self.status_callback = Some(url);
+        self
+    }
+
+    /// The validity period of every message that doesn't set its own
+    /// [`SendOptions::validity_period`].
+    pub fn default_validity_period(mut self, period: Duration) -> Self {
+        self.validity_period = Some(period);
+        self
+    }
+
+    /// Resend permanently failed messages over WhatsApp from
+    /// [`Client::send_with_fallback`].
+    pub fn whatsapp_fallback(mut self, fallback: WhatsAppFallback) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// The clock that [`SendOptions::send_at`] is checked against and receipts are
+    /// dated by, and that retries, hedged reads, [`Client::wait_for_final_status`]
+    /// polls and pauses between message parts or archive pages wait on. Defaults to
+    /// the system clock.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// What [`Client::preflight`] checks beyond the number itself. By default it
+    /// checks only the number's line type.
+    pub fn preflight(mut self, policy: PreflightPolicy) -> Self {
+        self.preflight = Some(Arc::new(policy));
+        self
+    }
+
+    /// Check the variables of every Content API template send against the template,
+    /// fetched through `content`, before sending.
+    #[cfg(feature = "content")]
+    pub fn content_validation(mut self, content: crate::content::Client) -> Self {
+        self.content_validation = Some(content);
+        self
+    }
+
+    /// Send WhatsApp messages through Twilio's sandbox instead of the fallback's
+    /// sender and template. Meant for development environments only.
+    pub fn whatsapp_sandbox(mut self, sandbox: WhatsAppSandbox) -> Self {
+        self.whatsapp_sandbox = Some(sandbox);
+        self
+    }
+
+    /// Build a client that never touches the network and answers every call from
+    /// `responses`. Credentials and the base url become optional.
+    pub fn stub(mut self, responses: StubResponses) -> Self {
+        self.stub = Some(responses);
+        self
+    }
+
+    pub fn build(mut self) -> Result<Client, ClientError> {
+        if self.stub.is_some() {
+            // Stub clients never send anything, so placeholders are enough
+            self.base_url = self.base_url.or_else(|| Url::parse(STUB_BASE_URL).ok());
+            self.account_sid = self
+                .account_sid
+                .or_else(|| Some(SecretString::from("stub")));
+            self.auth_token = self.auth_token.or_else(|| Some(SecretString::from("stub")));
+        }
         let base_url = self.base_url.ok_or_else(|| {
             ClientError::Configuration("Twilio sms base_url is required".to_string())
         })?;
-        let sender = self.sender.ok_or_else(|| {
-            ClientError::Configuration("Twilio sms sender phone is required".to_string())
-        })?;
+        if let Some(short_code) = &self.short_code {
+            let digits = short_code.bytes().all(|b| b.is_ascii_digit());
+            if !digits || !(3..=8).contains(&short_code.len()) {
+                return Err(ClientError::Configuration(format!(
+                    "Twilio sms short_code must be 3 to 8 digits, got {short_code}"
+                )));
+            }
+        }
+        let sender = match (
+            self.sender,
+            self.short_code,
+            self.sender_pool,
+            self.messaging_service_sid,
+        ) {
+            (_, _, Some(pool), _) if !pool.is_empty() => SenderConfig::Pool(pool),
+            (Some(phone), _, _, _) => SenderConfig::Phone(phone),
+            (_, Some(short_code), _, _) => SenderConfig::ShortCode(short_code),
+            (_, _, _, Some(service_sid)) => SenderConfig::MessagingService(service_sid),
+            _ => {
+                return Err(ClientError::Configuration(
+                    "Twilio sms sender phone, short_code or messaging_service_sid is required"
+                        .to_string(),
+                ))
+            }
+        };
         let account_sid = self.account_sid.ok_or_else(|| {
             ClientError::Configuration("Twilio sms account_sid is required".to_string())
         })?;
@@ -103,31 +549,73 @@ impl ClientBuilder {
         })?;
 
         let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        let max_response_size = self.max_response_size.unwrap_or(DEFAULT_MAX_RESPONSE_SIZE);
 
         let http_client = reqwest::Client::builder()
             .timeout(timeout)
+            .local_address(self.local_address)
             .build()
             .map_err(ClientError::Reqwest)?;
 
         Ok(Client {
-            http_client,
-            base_url,
-            account_sid,
+            transport: Transport {
+                http_client,
+                base_url,
+                account_sid,
+                auth_token,
+                timeout,
+                max_response_size,
+                stub: self.stub.map(Arc::new),
+                local_address: self.local_address,
+                cache: self.cache,
+                limiter: self.limiter,
+                dry_run: self.dry_run,
+                span: None,
+                retry: self.retry,
+                priority: self.priority,
+                sampler: self.sampler,
+                hedge_after: self.hedge_after,
+                read_only: self.read_only,
+                service_name: "Twilio SMS",
+                clock: self.clock.unwrap_or_else(|| Arc::new(SystemClock)),
+            },
             sender,
-            auth_token,
-            timeout,
+            receipt_store: self.receipt_store,
+            pseudonymizer: self.pseudonymizer,
+            fallback: self.fallback,
+            whatsapp_sandbox: self.whatsapp_sandbox,
+            status_callback: self.status_callback,
+            validity_period: self.validity_period,
+            preflight: self.preflight.unwrap_or_default(),
+            transitions: Arc::default(),
+            #[cfg(feature = "content")]
+            content_validation: self.content_validation,
         })
     }
 }
 
+#[derive(Clone, Debug)]
+enum SenderConfig {
+    Phone(Phone),
+    ShortCode(String),
+    Pool(Arc<SenderPool>),
+    MessagingService(SecretString),
+}
+
 #[derive(Clone, Debug)]
 pub struct Client {
-    http_client: reqwest::Client,
-    base_url: Url,
-    sender: Phone,
-    account_sid: SecretString,
-    auth_token: SecretString,
-    timeout: Duration,
+    transport: Transport,
+    sender: SenderConfig,
+    receipt_store: Option<Arc<dyn ReceiptStore>>,
+    pseudonymizer: Option<Pseudonymizer>,
+    fallback: Option<WhatsAppFallback>,
+    whatsapp_sandbox: Option<WhatsAppSandbox>,
+    status_callback: Option<Url>,
+    validity_period: Option<Duration>,
+    preflight: Arc<PreflightPolicy>,
+    transitions: Arc<StatusTransitions>,
+    #[cfg(feature = "content")]
+    content_validation: Option<crate::content::Client>,
 }
 
 impl Client {
@@ -135,10 +623,46 @@ impl Client {
         ClientBuilder::new()
     }
 
-    #[tracing::instrument(
-        name = "Twilio SMS: Send sms",
-        skip(self, to, content, send_as_mms, media_url)
-    )]
+    /// A builder preset for one-time codes: a 5 second timeout, retries with short
+    /// backoff, [`Priority::High`], and messages that expire after 10 minutes so a
+    /// code never arrives once it is useless. Set the sender and credentials as usual.
+    ///
+    /// Sends are only retried when they never reached Twilio, so a send that timed
+    /// out doesn't text the code twice.
+    pub fn for_otp() -> ClientBuilder {
+        ClientBuilder::new()
+            .timeout(Duration::from_secs(5))
+            .retry_policy(
+                RetryPolicy::new(3).backoff(Duration::from_millis(200), Duration::from_secs(1)),
+            )
+            .priority(Priority::High)
+            .default_validity_period(Duration::from_secs(10 * 60))
+    }
+
+    /// A builder preset for campaigns and notification bursts: requests go through
+    /// `limiter` and wait in its queue when it is saturated, at [`Priority::Low`] so
+    /// clients sharing it with higher priorities go first, with patient retries. As
+    /// with [`Client::for_otp`], a send that may have reached Twilio is never retried.
+    pub fn for_bulk(limiter: ConcurrencyLimiter) -> ClientBuilder {
+        ClientBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .concurrency_limiter(limiter)
+            .priority(Priority::Low)
+            .retry_policy(
+                RetryPolicy::new(5).backoff(Duration::from_secs(1), Duration::from_secs(30)),
+            )
+    }
+
+    /// A copy of the client whose calls are traced as children of `span`, e.g. one
+    /// carrying `order_id` or `tenant_id` fields, instead of the current span.
+    pub fn with_span(&self, span: tracing::Span) -> Self {
+        let mut client = self.clone();
+        client.transport.span = Some(span);
+        client
+    }
+
+    /// Sends `content` to `to`. A shorthand for [`Client::send_message`] kept for
+    /// existing callers.
     pub async fn send(
         &self,
         to: &Phone,
@@ -146,40 +670,341 @@ impl Client {
         send_as_mms: Option<bool>,
         media_url: Option<Vec<String>>,
     ) -> Result<SendSmsResponse, ClientError> {
-        let account_sid = self.account_sid.expose_secret();
-        let url = format!(
-            "/2010-04-01/Accounts/{AccountSid}/Messages.json",
-            AccountSid = urlencode_from_string(account_sid)
-        );
+        self.send_message(&positional_message(to, content, send_as_mms, media_url))
+            .await
+    }
+
+    /// Sends like [`Client::send`], also returning the response JSON as received.
+    pub async fn send_raw(
+        &self,
+        to: &Phone,
+        content: &str,
+        send_as_mms: Option<bool>,
+        media_url: Option<Vec<String>>,
+    ) -> Result<Raw<SendSmsResponse>, ClientError> {
+        self.deliver_message(&positional_message(to, content, send_as_mms, media_url))
+            .await
+    }
+
+    /// Sends `content` to `to` with the optional parameters in `options`, e.g. to
+    /// schedule the message. A shorthand for [`Client::send_message`].
+    pub async fn send_with_options(
+        &self,
+        to: &Phone,
+        content: &str,
+        options: &SendOptions,
+    ) -> Result<SendSmsResponse, ClientError> {
+        self.send_message(&MessageBuilder::new(to, content).options(options.clone()))
+            .await
+    }
+
+    /// Sends `message`. Prefer this over the positional [`Client::send`], which only
+    /// covers media.
+    pub async fn send_message(
+        &self,
+        message: &MessageBuilder,
+    ) -> Result<SendSmsResponse, ClientError> {
+        self.deliver_message(message).await.map(Raw::into_inner)
+    }
+
+    /// Spawns a task that fetches the account every `interval` to check the
+    /// credentials and connectivity. Must be called within a Tokio runtime.
+    pub fn spawn_healthcheck(&self, interval: Duration) -> HealthCheck {
+        let client = self.clone();
+        HealthCheck::spawn("Twilio SMS: Health check", interval, move || {
+            let client = client.clone();
+            async move {
+                let account_sid = client.transport.account_sid.expose_secret();
+                let url = format!(
+                    "/2010-04-01/Accounts/{}.json",
+                    urlencode_from_string(account_sid)
+                );
+                client.transport.probe(&url).await
+            }
+        })
+    }
+
+    /// The client's sender pool, if it rotates senders, e.g. to feed it status
+    /// callbacks with [`SenderPool::record_status`].
+    pub fn sender_pool(&self) -> Option<&SenderPool> {
+        match &self.sender {
+            SenderConfig::Pool(pool) => Some(pool),
+            _ => None,
+        }
+    }
+
+    /// The local address outgoing connections are bound to, if any.
+    pub fn local_address(&self) -> Option<IpAddr> {
+        self.transport.local_address
+    }
+
+    /// Establishes a connection to Twilio ahead of the first request, e.g. during
+    /// service startup, so the first send doesn't pay for a cold connection.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Warm up connection",
+        skip(self)
+    )]
+    pub async fn warm_up(&self) -> Result<(), ClientError> {
+        self.transport.warm_up().await
+    }
+
+    /// Uploads a file to an arbitrary Twilio endpoint as `multipart/form-data`, e.g.
+    /// a regulatory supporting document. `path` may be an absolute url on another
+    /// Twilio host.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Raw upload",
+        skip(self, upload)
+    )]
+    pub async fn raw_upload(
+        &self,
+        method: Method,
+        path: &str,
+        upload: Upload,
+    ) -> Result<serde_json::Value, ClientError> {
+        self.transport.upload(method, path, upload).await
+    }
 
-        let mut body = std::collections::HashMap::new();
-        body.insert("From", self.sender.e164_number());
+    /// Fetches the page after `page`, or `None` on the last page.
+    pub async fn next_page<T: DeserializeOwned>(
+        &self,
+        page: &Page<T>,
+    ) -> Result<Option<Page<T>>, ClientError> {
+        match &page.next_page_uri {
+            Some(uri) => self.transport.fetch_page(uri).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetches the page before `page`, or `None` on the first page.
+    pub async fn previous_page<T: DeserializeOwned>(
+        &self,
+        page: &Page<T>,
+    ) -> Result<Option<Page<T>>, ClientError> {
+        match &page.previous_page_uri {
+            Some(uri) => self.transport.fetch_page(uri).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Sends an authenticated request to an arbitrary Twilio endpoint.
+    ///
+    /// `path` is resolved against the configured base url. This is an escape hatch for
+    /// endpoints the crate doesn't model yet.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Raw request",
+        skip(self, params)
+    )]
+    pub async fn raw_request(
+        &self,
+        method: Method,
+        path: &str,
+        params: &Params,
+    ) -> Result<serde_json::Value, ClientError> {
+        self.transport.request(method, path, params).await
+    }
+}
+
+impl Client {
+    // Where every send entry point ends up
+    async fn deliver_message(
+        &self,
+        message: &MessageBuilder,
+    ) -> Result<Raw<SendSmsResponse>, ClientError> {
+        self.deliver(&message.to, &message.body, &message.options)
+            .await
+    }
+
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Send sms",
+        skip(self, to, content, options)
+    )]
+    async fn deliver(
+        &self,
+        to: &Phone,
+        content: &str,
+        options: &SendOptions,
+    ) -> Result<Raw<SendSmsResponse>, ClientError> {
+        let url = self.account_url("Messages.json");
+        options.check_max_price()?;
+        if options.shortens_urls() && !matches!(self.sender, SenderConfig::MessagingService(_)) {
+            return Err(ClientError::Configuration(
+                "Twilio sms shorten_urls requires a messaging_service_sid".to_string(),
+            ));
+        }
+        if options.is_scheduled() {
+            if !matches!(self.sender, SenderConfig::MessagingService(_)) {
+                return Err(ClientError::Configuration(
+                    "Twilio sms send_at requires a messaging_service_sid".to_string(),
+                ));
+            }
+            options.check_send_at(self.transport.clock.now())?;
+        }
+
+        let mut body = Params::new();
+        // The pooled sender's send, taken back unless Twilio accepts the message
+        let mut reservation = None;
+        match &self.sender {
+            SenderConfig::Phone(phone) => body.insert("From", phone.e164_number()),
+            SenderConfig::ShortCode(short_code) => body.insert("From", short_code.clone()),
+            SenderConfig::Pool(pool) => {
+                let reserved = pool.reserve().ok_or(ClientError::NoSenderAvailable)?;
+                body.insert("From", reserved.phone().e164_number());
+                reservation = Some(reserved);
+            }
+            SenderConfig::MessagingService(service_sid) => body.insert(
+                "MessagingServiceSid",
+                service_sid.expose_secret().to_string(),
+            ),
+        }
         body.insert("To", to.e164_number());
-        body.insert("Body", content.to_string());
-
-        if let Some(urls) = media_url {
-            body.insert(
-                "MediaUrl",
-                urls.into_iter().collect::<Vec<String>>().join(","),
-            );
-        }
-        if let Some(param_value) = send_as_mms {
-            body.insert("SendAsMms", param_value.to_string());
-        }
-
-        make_request(
-            &self.http_client,
-            (&self.base_url, &url),
-            &self.account_sid,
-            &self.auth_token,
-            self.timeout,
-            &body,
-            "Twilio SMS",
+        // A template send doesn't need a body
+        if !content.is_empty() || options.template().is_none() {
+            body.insert("Body", content.to_string());
+        }
+        options.apply(&mut body);
+        if let (Some(period), false) = (self.validity_period, body.contains_key("ValidityPeriod")) {
+            body.insert("ValidityPeriod", options::validity_seconds(period));
+        }
+        if let Some((content_sid, variables)) = options.template() {
+            self.validate_content_variables(content_sid, variables)
+                .await?;
+        }
+        self.apply_status_callback(&mut body);
+
+        let transport = match options.limiter_priority() {
+            Some(priority) if priority != self.transport.priority => Cow::Owned(Transport {
+                priority,
+                ..self.transport.clone()
+            }),
+            _ => Cow::Borrowed(&self.transport),
+        };
+        if transport.dry_run {
+            let status = if options.is_scheduled() {
+                Status::Scheduled
+            } else {
+                Status::Queued
+            };
+            let response = serde_json::json!({
+                "body": content,
+                "status": status,
+                "to": to.e164_number(),
+                "dry_run": true,
+            });
+            return transport.simulate(Method::POST, &url, response).await;
+        }
+
+        // Twilio doesn't drop duplicate creates, so this is only retried when the
+        // request never went out
+        let resp: Raw<SendSmsResponse> = transport.request(Method::POST, &url, &body).await?;
+        if let Some(reservation) = reservation {
+            reservation.commit();
+        }
+        if let Some(sid) = &resp.sid {
+            let from = resp
+                .from
+                .as_deref()
+                .and_then(|from| Phone::parse_with_no_country(from).ok());
+            self.observe_status(
+                sid,
+                from.as_ref(),
+                Some(to),
+                resp.status.clone(),
+                resp.error_code,
+            )
+            .await;
+        }
+        Ok(resp)
+    }
+
+    /// Checks template variables when the client has `content_validation`.
+    #[cfg_attr(not(feature = "content"), allow(unused_variables))]
+    async fn validate_content_variables(
+        &self,
+        content_sid: &str,
+        variables: &serde_json::Value,
+    ) -> Result<(), ClientError> {
+        #[cfg(feature = "content")]
+        if let Some(content) = &self.content_validation {
+            content.validate_variables(content_sid, variables).await?;
+        }
+        Ok(())
+    }
+
+    fn apply_status_callback(&self, body: &mut Params) {
+        if let (Some(url), false) = (&self.status_callback, body.contains_key("StatusCallback")) {
+            body.insert("StatusCallback", url.to_string());
+        }
+    }
+
+    /// Acts on a status of message `sid` sent, fetched or called back, unless it was
+    /// seen already: counts its first final status against the pooled sender `from`
+    /// and records a receipt for it when `to` is known.
+    async fn observe_status(
+        &self,
+        sid: &str,
+        from: Option<&Phone>,
+        to: Option<&Phone>,
+        status: Option<Status>,
+        error_code: Option<i32>,
+    ) {
+        if let Some(status) = &status {
+            let Some(first_final) = self.transitions.advance(sid, status.clone()) else {
+                return;
+            };
+            if let (SenderConfig::Pool(pool), Some(from), true) = (&self.sender, from, first_final)
+            {
+                pool.record_status(from, status.clone(), error_code.map(ErrorCode::from));
+            }
+        }
+        let (Some(store), Some(to)) = (&self.receipt_store, to) else {
+            return;
+        };
+        let receipt = Receipt {
+            message_sid: sid.to_string(),
+            to: match &self.pseudonymizer {
+                Some(pseudonymizer) => pseudonymizer.pseudonym(to),
+                None => to.e164_number(),
+            },
+            status,
+            error_code,
+            recorded_at: self.transport.clock.now(),
+        };
+        if let Err(err) = store.record(receipt).await {
+            tracing::error!("Twilio SMS: failed to record receipt for {sid}: {}", err);
+        }
+    }
+
+    /// The url of `resource` under the configured account, e.g. `Messages.json`.
+    fn account_url(&self, resource: &str) -> String {
+        let account_sid = self.transport.account_sid.expose_secret();
+        format!(
+            "/2010-04-01/Accounts/{AccountSid}/{resource}",
+            AccountSid = urlencode_from_string(account_sid)
         )
-        .await
     }
 }
 
+// The message [`Client::send`]'s positional arguments describe
+fn positional_message(
+    to: &Phone,
+    content: &str,
+    send_as_mms: Option<bool>,
+    media_url: Option<Vec<String>>,
+) -> MessageBuilder {
+    let mut message = MessageBuilder::new(to, content);
+    for url in media_url.into_iter().flatten() {
+        message = message.media_url(url);
+    }
+    if let Some(send_as_mms) = send_as_mms {
+        message = message.send_as_mms(send_as_mms);
+    }
+    message
+}
+
 fn urlencode_from_string<T: AsRef<str>>(s: T) -> String {
     url::form_urlencoded::byte_serialize(s.as_ref().as_bytes()).collect()
 }
@@ -0,0 +1,80 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+use crate::sms::Client;
+use crate::{Page, Params};
+
+/// What a provisioned number can be used for.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Capabilities {
+    #[serde(default)]
+    pub voice: bool,
+    #[serde(default, alias = "SMS")]
+    pub sms: bool,
+    #[serde(default, alias = "MMS")]
+    pub mms: bool,
+    #[serde(default)]
+    pub fax: bool,
+}
+
+/// A phone number provisioned on the account (an IncomingPhoneNumber resource).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct IncomingPhoneNumber {
+    pub sid: String,
+    pub phone_number: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub friendly_name: Option<String>,
+    #[serde(default)]
+    pub capabilities: Capabilities,
+}
+
+impl IncomingPhoneNumber {
+    pub fn supports_sms(&self) -> bool {
+        self.capabilities.sms
+    }
+
+    pub fn supports_mms(&self) -> bool {
+        self.capabilities.mms
+    }
+
+    pub fn supports_voice(&self) -> bool {
+        self.capabilities.voice
+    }
+
+    pub fn supports_fax(&self) -> bool {
+        self.capabilities.fax
+    }
+}
+
+impl Client {
+    /// Lists the first page of numbers provisioned on the account.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: List incoming numbers",
+        skip(self)
+    )]
+    pub async fn list_incoming_numbers(&self) -> Result<Page<IncomingPhoneNumber>, ClientError> {
+        let url = self.account_url("IncomingPhoneNumbers.json");
+        self.transport
+            .request(Method::GET, &url, &Params::new())
+            .await
+    }
+
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Fetch incoming number",
+        skip(self)
+    )]
+    pub async fn fetch_incoming_number(
+        &self,
+        number_sid: &str,
+    ) -> Result<IncomingPhoneNumber, ClientError> {
+        let url = self.account_url(&format!("IncomingPhoneNumbers/{number_sid}.json"));
+        self.transport
+            .request(Method::GET, &url, &Params::new())
+            .await
+    }
+}
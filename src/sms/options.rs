@@ -0,0 +1,529 @@
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::Url;
+
+use crate::error::ClientError;
+use crate::limiter::Priority;
+use crate::sms::price::is_decimal;
+use crate::{Params, Phone};
+
+const SEND_AT_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+// The range Twilio accepts for ValidityPeriod, in seconds
+const MAX_VALIDITY_PERIOD: u64 = 36_000;
+// How far ahead Twilio accepts a SendAt
+const MIN_SCHEDULE_AHEAD: TimeDelta = TimeDelta::minutes(15);
+const MAX_SCHEDULE_AHEAD: TimeDelta = TimeDelta::days(35);
+
+/// Optional parameters for [`Client::send_with_options`](super::Client::send_with_options).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SendOptions {
+    send_as_mms: Option<bool>,
+    media_urls: Vec<String>,
+    send_at: Option<DateTime<Utc>>,
+    status_callback: Option<Url>,
+    provide_feedback: bool,
+    validity_period: Option<Duration>,
+    content_template: Option<(String, serde_json::Value)>,
+    max_price: Option<String>,
+    smart_encoded: Option<bool>,
+    shorten_urls: bool,
+    discard_content: bool,
+    obfuscate_address: bool,
+    risk_check: Option<bool>,
+    force_delivery: bool,
+    priority: Option<Priority>,
+}
+
+impl SendOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deliver the message as a single MMS, even without media, rather than SMS segments.
+    pub fn send_as_mms(mut self, send_as_mms: bool) -> Self {
+        self.send_as_mms = Some(send_as_mms);
+        self
+    }
+
+    /// Attach the media at `url`. Call once per attachment.
+    pub fn media_url(mut self, url: impl Into<String>) -> Self {
+        self.media_urls.push(url.into());
+        self
+    }
+
+    /// Schedule the message for `time` instead of sending it now.
+    ///
+    /// Twilio only schedules messages sent through a Messaging Service, between 15
+    /// minutes and 35 days ahead. The send fails with [`ClientError::Configuration`]
+    /// otherwise, before anything reaches Twilio.
+    pub fn send_at(mut self, time: DateTime<Utc>) -> Self {
+        self.send_at = Some(time);
+        self
+    }
+
+    /// Have Twilio post this message's status changes to `url`, overriding the
+    /// client's `status_callback`.
+    pub fn status_callback(mut self, url: Url) -> Self {
+        self.status_callback = Some(url);
+        self
+    }
+
+    /// Track whether the message converted, e.g. an OTP was used, by reporting it
+    /// with [`Client::confirm_feedback`](super::Client::confirm_feedback).
+    pub fn provide_feedback(mut self, provide_feedback: bool) -> Self {
+        self.provide_feedback = provide_feedback;
+        self
+    }
+
+    /// Drop the message if it is still queued after `period`, e.g. so an OTP doesn't
+    /// arrive once it has expired. Twilio accepts 1 second to 10 hours; longer or
+    /// shorter periods are clamped.
+    pub fn validity_period(mut self, period: Duration) -> Self {
+        self.validity_period = Some(period);
+        self
+    }
+
+    /// Send Content API template `content_sid` filled in with `variables`, a JSON
+    /// object of strings keyed by placeholder, instead of a plain body.
+    pub fn content_template(
+        mut self,
+        content_sid: impl Into<String>,
+        variables: serde_json::Value,
+    ) -> Self {
+        self.content_template = Some((content_sid.into(), variables));
+        self
+    }
+
+    /// The most the message may cost, in USD, as a decimal string such as `"0.0075"`.
+    /// It is sent as given, so no float rounding creeps in, and sends fail with
+    /// [`ClientError::Configuration`] if it isn't a plain decimal. Twilio fails messages
+    /// to destinations that would cost more instead of sending them.
+    pub fn max_price(mut self, max_price: impl Into<String>) -> Self {
+        self.max_price = Some(max_price.into());
+        self
+    }
+
+    /// Have Twilio replace Unicode look-alikes (curly quotes, long dashes) with GSM-7
+    /// characters, so a stray character doesn't switch the message to UCS-2 and
+    /// multiply its segments.
+    pub fn smart_encoded(mut self, smart_encoded: bool) -> Self {
+        self.smart_encoded = Some(smart_encoded);
+        self
+    }
+
+    /// Have Twilio shorten the links in the body and track their clicks. Needs a
+    /// Messaging Service with a verified link shortening domain; sending from a
+    /// plain number fails before anything is sent.
+    pub fn shorten_urls(mut self, shorten_urls: bool) -> Self {
+        self.shorten_urls = shorten_urls;
+        self
+    }
+
+    /// Have Twilio drop the message body once it is delivered
+    /// (`ContentRetention=discard`), for messages that mustn't be stored.
+    pub fn discard_content(mut self, discard_content: bool) -> Self {
+        self.discard_content = discard_content;
+        self
+    }
+
+    /// Have Twilio mask the recipient's number in its logs and the message resource
+    /// (`AddressRetention=obfuscate`).
+    pub fn obfuscate_address(mut self, obfuscate_address: bool) -> Self {
+        self.obfuscate_address = obfuscate_address;
+        self
+    }
+
+    /// Turn Twilio's SMS pumping risk check on or off for this message
+    /// (`RiskCheck=enable` or `disable`). Twilio checks by default; turn it off only
+    /// for traffic it wrongly blocks, e.g. OTPs to a known user.
+    pub fn risk_check(mut self, risk_check: bool) -> Self {
+        self.risk_check = Some(risk_check);
+        self
+    }
+
+    /// Have Twilio try the carrier even when it considers the recipient unreachable,
+    /// e.g. a landline or a number that looks invalid (`ForceDelivery=true`). Meant
+    /// for trial accounts and numbers Twilio's checks get wrong; the message may still
+    /// fail and is billed as usual.
+    pub fn force_delivery(mut self, force_delivery: bool) -> Self {
+        self.force_delivery = force_delivery;
+        self
+    }
+
+    /// The priority of this message when a shared `concurrency_limiter` is saturated,
+    /// overriding the client's `priority`.
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    pub(crate) fn limiter_priority(&self) -> Option<Priority> {
+        self.priority
+    }
+
+    pub(crate) fn template(&self) -> Option<(&str, &serde_json::Value)> {
+        self.content_template
+            .as_ref()
+            .map(|(content_sid, variables)| (content_sid.as_str(), variables))
+    }
+
+    pub(crate) fn is_scheduled(&self) -> bool {
+        self.send_at.is_some()
+    }
+
+    /// Fails unless `send_at` is within the window Twilio schedules messages in, as of
+    /// `now`.
+    pub(crate) fn check_send_at(&self, now: DateTime<Utc>) -> Result<(), ClientError> {
+        match self.send_at {
+            Some(time) if time - now < MIN_SCHEDULE_AHEAD || time - now > MAX_SCHEDULE_AHEAD => {
+                Err(ClientError::Configuration(format!(
+                    "Twilio sms send_at must be between 15 minutes and 35 days ahead, got {}",
+                    time.format(SEND_AT_FORMAT)
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails unless `max_price` is a plain decimal such as `0.0075`.
+    pub(crate) fn check_max_price(&self) -> Result<(), ClientError> {
+        match &self.max_price {
+            Some(max_price) if !is_decimal(max_price) => Err(ClientError::Configuration(format!(
+                "Twilio sms max_price must be a decimal such as 0.0075, got {max_price:?}"
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn shortens_urls(&self) -> bool {
+        self.shorten_urls
+    }
+
+    /// The same options without the attachments, for the later parts of a split
+    /// message.
+    pub(crate) fn without_media(&self) -> Self {
+        Self {
+            media_urls: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn apply(&self, body: &mut Params) {
+        for url in &self.media_urls {
+            body.append("MediaUrl", url.clone());
+        }
+        if let Some(send_as_mms) = self.send_as_mms {
+            body.insert("SendAsMms", send_as_mms.to_string());
+        }
+        if let Some(time) = self.send_at {
+            body.insert("SendAt", time.format(SEND_AT_FORMAT).to_string());
+            body.insert("ScheduleType", "fixed".to_string());
+        }
+        if let Some(url) = &self.status_callback {
+            body.insert("StatusCallback", url.to_string());
+        }
+        if self.provide_feedback {
+            body.insert("ProvideFeedback", "true".to_string());
+        }
+        if let Some((content_sid, variables)) = &self.content_template {
+            body.insert("ContentSid", content_sid.clone());
+            body.insert("ContentVariables", variables.to_string());
+        }
+        if self.shorten_urls {
+            body.insert("ShortenUrls", "true".to_string());
+        }
+        if self.discard_content {
+            body.insert("ContentRetention", "discard".to_string());
+        }
+        if self.obfuscate_address {
+            body.insert("AddressRetention", "obfuscate".to_string());
+        }
+        if let Some(risk_check) = self.risk_check {
+            let value = if risk_check { "enable" } else { "disable" };
+            body.insert("RiskCheck", value.to_string());
+        }
+        if self.force_delivery {
+            body.insert("ForceDelivery", "true".to_string());
+        }
+        if let Some(smart_encoded) = self.smart_encoded {
+            body.insert("SmartEncoded", smart_encoded.to_string());
+        }
+        if let Some(max_price) = &self.max_price {
+            body.insert("MaxPrice", max_price.clone());
+        }
+        if let Some(period) = self.validity_period {
+            body.insert("ValidityPeriod", validity_seconds(period));
+        }
+    }
+}
+
+/// `period` in whole seconds, within the range Twilio accepts for ValidityPeriod.
+pub(crate) fn validity_seconds(period: Duration) -> String {
+    period.as_secs().clamp(1, MAX_VALIDITY_PERIOD).to_string()
+}
+
+/// A message to send with [`Client::send_message`](super::Client::send_message): the
+/// recipient and body plus any [`SendOptions`].
+///
+/// ```
+/// # use twilio_client::sms::MessageBuilder;
+/// # use twilio_client::Phone;
+/// let to = Phone::parse("0700123456", "KE").unwrap();
+/// let message = MessageBuilder::new(&to, "Your order has shipped")
+///     .media_url("https://example.com/receipt.pdf")
+///     .provide_feedback(true);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MessageBuilder {
+    pub(crate) to: Phone,
+    pub(crate) body: String,
+    pub(crate) options: SendOptions,
+}
+
+impl MessageBuilder {
+    pub fn new(to: &Phone, body: impl Into<String>) -> Self {
+        Self {
+            to: to.clone(),
+            body: body.into(),
+            options: SendOptions::new(),
+        }
+    }
+
+    /// A message that sends Content API template `content_sid` instead of a body, e.g.
+    /// a WhatsApp business-initiated message. `variables` fill in its placeholders.
+    ///
+    /// ```
+    /// # use twilio_client::sms::MessageBuilder;
+    /// # use twilio_client::Phone;
+    /// let to = Phone::parse("0700123456", "KE").unwrap();
+    /// let message = MessageBuilder::template(&to, "HX123", [("1", "A-100"), ("2", "Friday")]);
+    /// ```
+    pub fn template<K, V>(
+        to: &Phone,
+        content_sid: impl Into<String>,
+        variables: impl IntoIterator<Item = (K, V)>,
+    ) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let variables: serde_json::Map<String, serde_json::Value> = variables
+            .into_iter()
+            .map(|(name, value)| (name.into(), serde_json::Value::String(value.into())))
+            .collect();
+        Self::new(to, "").content_template(content_sid, variables.into())
+    }
+
+    /// Replaces every option set so far with `options`.
+    pub fn options(mut self, options: SendOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// See [`SendOptions::send_as_mms`].
+    pub fn send_as_mms(mut self, send_as_mms: bool) -> Self {
+        self.options = self.options.send_as_mms(send_as_mms);
+        self
+    }
+
+    /// Attach the media at `url`. Call once per attachment.
+    pub fn media_url(mut self, url: impl Into<String>) -> Self {
+        self.options = self.options.media_url(url);
+        self
+    }
+
+    /// See [`SendOptions::send_at`].
+    pub fn send_at(mut self, time: DateTime<Utc>) -> Self {
+        self.options = self.options.send_at(time);
+        self
+    }
+
+    /// See [`SendOptions::status_callback`].
+    pub fn status_callback(mut self, url: Url) -> Self {
+        self.options = self.options.status_callback(url);
+        self
+    }
+
+    /// See [`SendOptions::provide_feedback`].
+    pub fn provide_feedback(mut self, provide_feedback: bool) -> Self {
+        self.options = self.options.provide_feedback(provide_feedback);
+        self
+    }
+
+    /// See [`SendOptions::content_template`]. The body can be left empty.
+    pub fn content_template(
+        mut self,
+        content_sid: impl Into<String>,
+        variables: serde_json::Value,
+    ) -> Self {
+        self.options = self.options.content_template(content_sid, variables);
+        self
+    }
+
+    /// See [`SendOptions::smart_encoded`].
+    pub fn smart_encoded(mut self, smart_encoded: bool) -> Self {
+        self.options = self.options.smart_encoded(smart_encoded);
+        self
+    }
+
+    /// See [`SendOptions::shorten_urls`].
+    pub fn shorten_urls(mut self, shorten_urls: bool) -> Self {
+        self.options = self.options.shorten_urls(shorten_urls);
+        self
+    }
+
+    /// See [`SendOptions::discard_content`].
+    pub fn discard_content(mut self, discard_content: bool) -> Self {
+        self.options = self.options.discard_content(discard_content);
+        self
+    }
+
+    /// See [`SendOptions::obfuscate_address`].
+    pub fn obfuscate_address(mut self, obfuscate_address: bool) -> Self {
+        self.options = self.options.obfuscate_address(obfuscate_address);
+        self
+    }
+
+    /// See [`SendOptions::risk_check`].
+    pub fn risk_check(mut self, risk_check: bool) -> Self {
+        self.options = self.options.risk_check(risk_check);
+        self
+    }
+
+    /// See [`SendOptions::force_delivery`].
+    pub fn force_delivery(mut self, force_delivery: bool) -> Self {
+        self.options = self.options.force_delivery(force_delivery);
+        self
+    }
+
+    /// See [`SendOptions::max_price`].
+    pub fn max_price(mut self, max_price: impl Into<String>) -> Self {
+        self.options = self.options.max_price(max_price);
+        self
+    }
+
+    /// See [`SendOptions::validity_period`].
+    pub fn validity_period(mut self, period: Duration) -> Self {
+        self.options = self.options.validity_period(period);
+        self
+    }
+
+    /// See [`SendOptions::priority`].
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.options = self.options.priority(priority);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{TimeDelta, TimeZone, Utc};
+    use claim::{assert_err, assert_ok};
+
+    use crate::sms::SendOptions;
+    use crate::Params;
+
+    #[test]
+    fn scheduling_sets_send_at_and_a_fixed_schedule_type() {
+        let time = Utc.with_ymd_and_hms(2024, 5, 6, 7, 8, 9).unwrap();
+        let mut body = Params::new();
+        SendOptions::new().send_at(time).apply(&mut body);
+
+        assert_eq!(body.get("SendAt"), Some("2024-05-06T07:08:09Z"));
+        assert_eq!(body.get("ScheduleType"), Some("fixed"));
+    }
+
+    #[test]
+    fn send_at_must_be_within_twilio_scheduling_window() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 6, 7, 0, 0).unwrap();
+        let ahead = |delta| SendOptions::new().send_at(now + delta).check_send_at(now);
+
+        assert_err!(ahead(TimeDelta::minutes(14)));
+        assert_ok!(ahead(TimeDelta::minutes(15)));
+        assert_ok!(ahead(TimeDelta::days(35)));
+        assert_err!(ahead(TimeDelta::days(35) + TimeDelta::seconds(1)));
+        assert_err!(ahead(-TimeDelta::hours(1)));
+        assert_ok!(SendOptions::new().check_send_at(now));
+    }
+
+    #[test]
+    fn max_price_is_sent_as_given() {
+        let mut body = Params::new();
+        SendOptions::new().max_price("0.0075").apply(&mut body);
+
+        assert_eq!(body.get("MaxPrice"), Some("0.0075"));
+    }
+
+    #[test]
+    fn max_price_must_be_a_decimal() {
+        for valid in ["0.0075", "1", "+0.5", "-0.01"] {
+            assert_ok!(SendOptions::new().max_price(valid).check_max_price());
+        }
+        for invalid in ["", "$0.05", "0,05", "1e-3", ".5", "5.", "0.0075 "] {
+            assert_err!(SendOptions::new().max_price(invalid).check_max_price());
+        }
+    }
+
+    #[test]
+    fn shorten_urls_is_only_sent_when_enabled() {
+        let mut body = Params::new();
+        SendOptions::new().apply(&mut body);
+        assert!(!body.contains_key("ShortenUrls"));
+
+        let mut body = Params::new();
+        SendOptions::new().shorten_urls(true).apply(&mut body);
+        assert_eq!(body.get("ShortenUrls"), Some("true"));
+    }
+
+    #[test]
+    fn retention_options_send_twilio_values() {
+        let mut body = Params::new();
+        SendOptions::new()
+            .discard_content(true)
+            .obfuscate_address(true)
+            .apply(&mut body);
+
+        assert_eq!(body.get("ContentRetention"), Some("discard"));
+        assert_eq!(body.get("AddressRetention"), Some("obfuscate"));
+    }
+
+    #[test]
+    fn risk_check_is_left_to_twilio_unless_set() {
+        let mut body = Params::new();
+        SendOptions::new().apply(&mut body);
+        assert!(!body.contains_key("RiskCheck"));
+
+        let mut body = Params::new();
+        SendOptions::new().risk_check(false).apply(&mut body);
+        assert_eq!(body.get("RiskCheck"), Some("disable"));
+    }
+
+    #[test]
+    fn force_delivery_is_only_sent_when_enabled() {
+        let mut body = Params::new();
+        SendOptions::new().force_delivery(false).apply(&mut body);
+        assert!(!body.contains_key("ForceDelivery"));
+
+        let mut body = Params::new();
+        SendOptions::new().force_delivery(true).apply(&mut body);
+        assert_eq!(body.get("ForceDelivery"), Some("true"));
+    }
+
+    #[test]
+    fn validity_period_is_sent_in_seconds_within_twilio_limits() {
+        let mut body = Params::new();
+        SendOptions::new()
+            .validity_period(Duration::from_secs(300))
+            .apply(&mut body);
+        assert_eq!(body.get("ValidityPeriod"), Some("300"));
+
+        let mut body = Params::new();
+        SendOptions::new()
+            .validity_period(Duration::from_secs(24 * 60 * 60))
+            .apply(&mut body);
+        assert_eq!(body.get("ValidityPeriod"), Some("36000"));
+    }
+}
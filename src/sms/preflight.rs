@@ -0,0 +1,370 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use reqwest::{Method, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+use crate::sms::{urlencode_from_string, Client};
+use crate::{Params, Phone};
+
+const LOOKUP_BASE_URL: &str = "https://lookups.twilio.com";
+// libphonenumber's region code for numbers that belong to no country
+const NON_GEOGRAPHIC: &str = "001";
+
+/// Numbers that must not be messaged, e.g. recipients who replied STOP or asked to be
+/// forgotten.
+#[async_trait]
+pub trait SuppressionList: Debug + Send + Sync {
+    async fn contains(&self, phone: &Phone) -> Result<bool, Box<dyn Error + Send + Sync>>;
+}
+
+/// A [`SuppressionList`] local to the process.
+#[derive(Debug, Default)]
+pub struct InMemorySuppressionList {
+    numbers: Mutex<HashSet<String>>,
+}
+
+impl InMemorySuppressionList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, phone: &Phone) {
+        self.numbers.lock().unwrap().insert(phone.e164_number());
+    }
+
+    pub fn remove(&self, phone: &Phone) {
+        self.numbers.lock().unwrap().remove(&phone.e164_number());
+    }
+}
+
+#[async_trait]
+impl SuppressionList for InMemorySuppressionList {
+    async fn contains(&self, phone: &Phone) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        Ok(self.numbers.lock().unwrap().contains(&phone.e164_number()))
+    }
+}
+
+/// What a [`Client::preflight`] checks beyond the number itself.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use twilio_client::sms::{InMemorySuppressionList, PreflightPolicy};
+/// let policy = PreflightPolicy::new()
+///     .suppression_list(Arc::new(InMemorySuppressionList::new()))
+///     .allowed_countries(["KE", "UG", "TZ"])
+///     .lookup(true);
+/// ```
+#[derive(Debug)]
+pub struct PreflightPolicy {
+    suppression_list: Option<Arc<dyn SuppressionList>>,
+    allowed_countries: HashSet<String>,
+    lookup_base_url: Option<Url>,
+    max_sms_pumping_risk: u8,
+}
+
+impl PreflightPolicy {
+    pub fn new() -> Self {
+        Self {
+            suppression_list: None,
+            allowed_countries: HashSet::new(),
+            lookup_base_url: None,
+            max_sms_pumping_risk: 80,
+        }
+    }
+
+    pub fn suppression_list(mut self, list: Arc<dyn SuppressionList>) -> Self {
+        self.suppression_list = Some(list);
+        self
+    }
+
+    /// The ISO countries messages may go to, e.g. those enabled in the account's
+    /// geo permissions. Every country is allowed when none are given.
+    pub fn allowed_countries<'a>(mut self, countries: impl IntoIterator<Item = &'a str>) -> Self {
+        self.allowed_countries = countries
+            .into_iter()
+            .map(|country| country.to_uppercase())
+            .collect();
+        self
+    }
+
+    /// Ask Twilio Lookup for the line type and SMS pumping risk of the number. Lookup
+    /// is billed per request, so it is off by default.
+    pub fn lookup(mut self, lookup: bool) -> Self {
+        self.lookup_base_url = lookup.then(|| Url::parse(LOOKUP_BASE_URL).unwrap());
+        self
+    }
+
+    /// Ask Lookup at `url` instead of `https://lookups.twilio.com`. Turns lookups on.
+    pub fn lookup_base_url(mut self, url: Url) -> Self {
+        self.lookup_base_url = Some(url);
+        self
+    }
+
+    /// The highest SMS pumping risk score, from 0 to 100, that Lookup may report before
+    /// a number is blocked. Defaults to 80.
+    pub fn max_sms_pumping_risk(mut self, score: u8) -> Self {
+        self.max_sms_pumping_risk = score;
+        self
+    }
+}
+
+impl Default for PreflightPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The kind of line behind a number, from Lookup when it was asked and from the
+/// country's numbering plan otherwise.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum LineType {
+    Mobile,
+    Landline,
+    /// The numbering plan doesn't tell mobile and landline numbers apart, as in the
+    /// US. Only known locally.
+    MobileOrLandline,
+    #[serde(alias = "fixedVoip", alias = "nonFixedVoip")]
+    Voip,
+    TollFree,
+    Premium,
+    SharedCost,
+    Personal,
+    Pager,
+    Uan,
+    Voicemail,
+    #[serde(other)]
+    Unknown,
+}
+
+impl From<phonenumber::Type> for LineType {
+    fn from(number_type: phonenumber::Type) -> Self {
+        use phonenumber::Type;
+
+        match number_type {
+            Type::Mobile => LineType::Mobile,
+            Type::FixedLine => LineType::Landline,
+            Type::FixedLineOrMobile => LineType::MobileOrLandline,
+            Type::Voip => LineType::Voip,
+            Type::TollFree => LineType::TollFree,
+            Type::PremiumRate => LineType::Premium,
+            Type::SharedCost => LineType::SharedCost,
+            Type::PersonalNumber => LineType::Personal,
+            Type::Pager => LineType::Pager,
+            Type::Uan => LineType::Uan,
+            Type::Voicemail => LineType::Voicemail,
+            _ => LineType::Unknown,
+        }
+    }
+}
+
+/// Whether to send to a number, from least to most severe.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Send,
+    /// The message may not arrive or may cost more than usual; send at the caller's
+    /// discretion.
+    Warn,
+    /// The message won't arrive, or must not be sent.
+    Block,
+}
+
+/// Why a [`Preflight`] is not a plain [`Verdict::Send`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum PreflightReason {
+    /// The number is a landline, pager or voicemail line, which can't take SMS.
+    NotMobile(LineType),
+    /// Premium rate and shared cost numbers bill the sender.
+    PremiumRate,
+    /// VoIP, toll-free and personal numbers take SMS only sometimes.
+    MaybeNotMobile(LineType),
+    UnknownLineType,
+    /// The number's country is not one of the policy's allowed countries. Numbers
+    /// without a country, e.g. `+800`, are reported as `"001"`.
+    CountryNotAllowed(String),
+    Suppressed,
+    /// The suppression list couldn't be read, so the number may be on it.
+    SuppressionListUnavailable(String),
+    /// Lookup's SMS pumping risk score is above the policy's maximum.
+    SmsPumpingRisk(u8),
+    /// Twilio's SMS pumping protection blocks the number.
+    BlockedByTwilio,
+    /// Lookup failed, so only local checks were made.
+    LookupFailed(String),
+}
+
+impl PreflightReason {
+    pub fn verdict(&self) -> Verdict {
+        match self {
+            PreflightReason::MaybeNotMobile(_)
+            | PreflightReason::UnknownLineType
+            | PreflightReason::LookupFailed(_) => Verdict::Warn,
+            _ => Verdict::Block,
+        }
+    }
+}
+
+/// The outcome of [`Client::preflight`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Preflight {
+    /// The most severe verdict of the `reasons`, or [`Verdict::Send`] without any.
+    pub verdict: Verdict,
+    pub line_type: LineType,
+    /// Lookup's SMS pumping risk score, when Lookup was asked.
+    pub sms_pumping_risk: Option<u8>,
+    pub reasons: Vec<PreflightReason>,
+}
+
+impl Preflight {
+    pub fn should_send(&self) -> bool {
+        self.verdict != Verdict::Block
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LookupResponse {
+    line_type_intelligence: Option<LineTypeIntelligence>,
+    sms_pumping_risk: Option<SmsPumpingRisk>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LineTypeIntelligence {
+    #[serde(rename = "type")]
+    line_type: Option<LineType>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SmsPumpingRisk {
+    sms_pumping_risk_score: Option<u8>,
+    #[serde(default)]
+    number_blocked: bool,
+}
+
+impl Client {
+    /// Checks whether `to` is worth sending to before an expensive send, e.g. a
+    /// campaign: its line type, its country against the allowed ones, the suppression
+    /// list and, when the client's [`PreflightPolicy`] turns it on, Twilio Lookup's
+    /// line type and SMS pumping risk. Every check runs, so the verdict comes with
+    /// all the reasons against the number.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Preflight",
+        skip(self, to)
+    )]
+    pub async fn preflight(&self, to: &Phone) -> Result<Preflight, ClientError> {
+        let policy = &self.preflight;
+        let mut reasons = Vec::new();
+        let mut line_type = LineType::from(to.number_type());
+        let mut sms_pumping_risk = None;
+
+        if let Some(base_url) = &policy.lookup_base_url {
+            match self.lookup(base_url, to).await {
+                Ok(response) => {
+                    if let Some(found) = response.line_type_intelligence.and_then(|i| i.line_type) {
+                        line_type = found;
+                    }
+                    if let Some(risk) = response.sms_pumping_risk {
+                        sms_pumping_risk = risk.sms_pumping_risk_score;
+                        if risk.number_blocked {
+                            reasons.push(PreflightReason::BlockedByTwilio);
+                        }
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!("Twilio SMS: preflight lookup failed: {}", err);
+                    reasons.push(PreflightReason::LookupFailed(err.to_string()));
+                }
+            }
+        }
+        if let Some(score) = sms_pumping_risk.filter(|s| *s > policy.max_sms_pumping_risk) {
+            reasons.push(PreflightReason::SmsPumpingRisk(score));
+        }
+
+        match line_type {
+            LineType::Mobile | LineType::MobileOrLandline => {}
+            LineType::Landline | LineType::Pager | LineType::Voicemail => {
+                reasons.push(PreflightReason::NotMobile(line_type))
+            }
+            LineType::Premium | LineType::SharedCost => reasons.push(PreflightReason::PremiumRate),
+            LineType::Voip | LineType::TollFree | LineType::Personal | LineType::Uan => {
+                reasons.push(PreflightReason::MaybeNotMobile(line_type))
+            }
+            LineType::Unknown => reasons.push(PreflightReason::UnknownLineType),
+        }
+
+        if !policy.allowed_countries.is_empty() {
+            // Non-geographic numbers, e.g. +800, have no country so are never allowed
+            let country = to
+                .country_id()
+                .unwrap_or_else(|| NON_GEOGRAPHIC.to_string());
+            if !policy.allowed_countries.contains(&country) {
+                reasons.push(PreflightReason::CountryNotAllowed(country));
+            }
+        }
+
+        if let Some(list) = &policy.suppression_list {
+            match list.contains(to).await {
+                Ok(true) => reasons.push(PreflightReason::Suppressed),
+                Ok(false) => {}
+                Err(err) => {
+                    tracing::error!("Twilio SMS: failed to read the suppression list: {}", err);
+                    reasons.push(PreflightReason::SuppressionListUnavailable(err.to_string()));
+                }
+            }
+        }
+
+        Ok(Preflight {
+            verdict: reasons
+                .iter()
+                .map(PreflightReason::verdict)
+                .max()
+                .unwrap_or(Verdict::Send),
+            line_type,
+            sms_pumping_risk,
+            reasons,
+        })
+    }
+
+    async fn lookup(&self, base_url: &Url, phone: &Phone) -> Result<LookupResponse, ClientError> {
+        let url = base_url
+            .join(&format!(
+                "/v2/PhoneNumbers/{}",
+                urlencode_from_string(phone.e164_number())
+            ))
+            .map_err(|e| {
+                ClientError::Configuration(format!("Twilio SMS: invalid lookup URL: {}", e))
+            })?;
+        let mut params = Params::new();
+        params.insert("Fields", "line_type_intelligence,sms_pumping_risk");
+        self.transport
+            .request(Method::GET, url.as_str(), &params)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sms::LineType;
+    use crate::Phone;
+
+    #[test]
+    fn line_types_come_from_the_numbering_plan() {
+        let mobile = Phone::parse("0700123456", "KE").unwrap();
+        let landline = Phone::parse("02079460000", "GB").unwrap();
+        let premium = Phone::parse("09098790000", "GB").unwrap();
+
+        assert_eq!(LineType::from(mobile.number_type()), LineType::Mobile);
+        assert_eq!(LineType::from(landline.number_type()), LineType::Landline);
+        assert_eq!(LineType::from(premium.number_type()), LineType::Premium);
+    }
+}
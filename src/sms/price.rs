@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+
+use crate::sms::SendSmsResponse;
+
+/// Which way money moved for a message.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum PriceSign {
+    /// The account was charged. Twilio reports these as negative prices.
+    Charge,
+    /// The account was credited, e.g. a refund.
+    Credit,
+    /// Nothing was charged or credited.
+    Zero,
+}
+
+/// A message price normalized from Twilio's signed string, e.g. `-0.00750` becomes a
+/// charge of `0.0075`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Price {
+    /// The absolute amount as a decimal string, kept exact rather than rounded to a
+    /// float.
+    pub amount: String,
+    pub sign: PriceSign,
+    /// The ISO 4217 currency, e.g. `USD`.
+    pub unit: Option<String>,
+}
+
+impl Price {
+    /// Parses a price string as Twilio returns it. `None` if it isn't a decimal number.
+    pub fn parse(price: &str, unit: Option<&str>) -> Option<Self> {
+        let price = price.trim();
+        if !is_decimal(price) {
+            return None;
+        }
+        let (negative, digits) = match price.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, price.strip_prefix('+').unwrap_or(price)),
+        };
+        let amount = normalize(digits);
+        // Twilio reports what an account pays as a negative amount
+        let sign = if amount == "0" {
+            PriceSign::Zero
+        } else if negative {
+            PriceSign::Charge
+        } else {
+            PriceSign::Credit
+        };
+        Some(Self {
+            amount,
+            sign,
+            unit: unit.map(str::to_string),
+        })
+    }
+
+    pub fn is_charge(&self) -> bool {
+        self.sign == PriceSign::Charge
+    }
+
+    /// The amount as Twilio signs it: negative for charges.
+    pub fn signed_amount(&self) -> String {
+        match self.sign {
+            PriceSign::Charge => format!("-{}", self.amount),
+            PriceSign::Credit | PriceSign::Zero => self.amount.clone(),
+        }
+    }
+}
+
+/// Whether `value` is a plain decimal number: an optional sign, digits and optionally
+/// a fraction, e.g. `-0.0075`.
+pub(crate) fn is_decimal(value: &str) -> bool {
+    let unsigned = value.strip_prefix(['-', '+']).unwrap_or(value);
+    let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, "0"));
+    let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    is_digits(whole) && is_digits(fraction)
+}
+
+// Drops redundant zeros, e.g. `00.00750` becomes `0.0075` and `1.0` becomes `1`
+fn normalize(digits: &str) -> String {
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let whole = whole.trim_start_matches('0');
+    let whole = if whole.is_empty() { "0" } else { whole };
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+impl SendSmsResponse {
+    /// The message's price, once Twilio has filled it in.
+    pub fn cost(&self) -> Option<Price> {
+        Price::parse(self.price.as_deref()?, self.price_unit.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sms::{Price, PriceSign};
+
+    #[test]
+    fn negative_prices_are_charges() {
+        let price = Price::parse("-0.00750", Some("USD")).unwrap();
+
+        assert_eq!(price.amount, "0.0075");
+        assert_eq!(price.sign, PriceSign::Charge);
+        assert_eq!(price.signed_amount(), "-0.0075");
+        assert_eq!(price.unit.as_deref(), Some("USD"));
+    }
+
+    #[test]
+    fn positive_prices_are_credits() {
+        let price = Price::parse("0.00750", None).unwrap();
+
+        assert!(!price.is_charge());
+        assert_eq!(price.amount, "0.0075");
+    }
+
+    #[test]
+    fn amounts_are_kept_exact() {
+        let price = Price::parse("-0.0075", None).unwrap();
+
+        assert_eq!(price.amount, "0.0075");
+        assert_eq!(price.signed_amount(), "-0.0075");
+    }
+
+    #[test]
+    fn zero_prices_are_neither_charges_nor_credits() {
+        for zero in ["0", "-0.00", "0.000"] {
+            let price = Price::parse(zero, Some("USD")).unwrap();
+
+            assert_eq!((price.amount.as_str(), price.sign), ("0", PriceSign::Zero));
+            assert_eq!(price.signed_amount(), "0");
+        }
+    }
+
+    #[test]
+    fn unparseable_prices_are_ignored() {
+        assert_eq!(Price::parse("", None), None);
+        assert_eq!(Price::parse("NaN", None), None);
+        assert_eq!(Price::parse("1e-3", None), None);
+        assert_eq!(Price::parse("-.5", None), None);
+    }
+}
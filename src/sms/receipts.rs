@@ -0,0 +1,136 @@
+use std::error::Error;
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::sms::Status;
+
+/// One observed state of an outbound message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Receipt {
+    pub message_sid: String,
+    /// The E.164 recipient, or its pseudonym when the client has a `pseudonymizer`.
+    pub to: String,
+    pub status: Option<Status>,
+    pub error_code: Option<i32>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Persists message receipts, e.g. to power a "message history" screen.
+///
+/// The sms client records a receipt for every accepted send and for every new status
+/// it fetches; feed status callbacks in with
+/// [`Client::record_status_callback`](crate::sms::Client::record_status_callback) as
+/// well to keep the full transition history. A failing store is logged and never
+/// fails the send.
+#[async_trait]
+pub trait ReceiptStore: Debug + Send + Sync {
+    async fn record(&self, receipt: Receipt) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresReceiptStore;
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use std::error::Error;
+    use std::fmt;
+
+    use async_trait::async_trait;
+
+    use crate::sms::{Receipt, ReceiptStore, Status};
+
+    /// A [`ReceiptStore`] writing to a Postgres table shaped like:
+    ///
+    /// ```sql
+    /// CREATE TABLE sms_receipts (
+    ///     message_sid TEXT NOT NULL,
+    ///     recipient TEXT NOT NULL,
+    ///     status TEXT,
+    ///     error_code INTEGER,
+    ///     recorded_at TIMESTAMPTZ NOT NULL
+    /// );
+    /// ```
+    pub struct PostgresReceiptStore {
+        client: tokio_postgres::Client,
+        table: String,
+    }
+
+    impl PostgresReceiptStore {
+        pub fn new(client: tokio_postgres::Client) -> Self {
+            Self {
+                client,
+                table: "sms_receipts".to_string(),
+            }
+        }
+
+        /// Write to `table` instead of `sms_receipts`. It may be schema-qualified, e.g.
+        /// `audit.sms_receipts`; each part is quoted, so it is taken literally.
+        pub fn table(mut self, table: impl Into<String>) -> Self {
+            self.table = table.into();
+            self
+        }
+    }
+
+    impl fmt::Debug for PostgresReceiptStore {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("PostgresReceiptStore")
+                .field("table", &self.table)
+                .finish()
+        }
+    }
+
+    #[async_trait]
+    impl ReceiptStore for PostgresReceiptStore {
+        async fn record(&self, receipt: Receipt) -> Result<(), Box<dyn Error + Send + Sync>> {
+            let status = receipt.status.as_ref().map(Status::as_str);
+            let query = format!(
+                "INSERT INTO {} (message_sid, recipient, status, error_code, recorded_at) \
+                 VALUES ($1, $2, $3, $4, $5)",
+                quote_table(&self.table)
+            );
+            self.client
+                .execute(
+                    &query,
+                    &[
+                        &receipt.message_sid,
+                        &receipt.to,
+                        &status,
+                        &receipt.error_code,
+                        &receipt.recorded_at,
+                    ],
+                )
+                .await?;
+            Ok(())
+        }
+    }
+
+    // Quotes each part of a possibly schema-qualified table name as an identifier, so
+    // it can't break out of the statement
+    fn quote_table(table: &str) -> String {
+        table
+            .split('.')
+            .map(|part| format!("\"{}\"", part.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::quote_table;
+
+        #[test]
+        fn table_names_are_quoted_identifiers() {
+            assert_eq!(quote_table("sms_receipts"), r#""sms_receipts""#);
+            assert_eq!(
+                quote_table("audit.sms_receipts"),
+                r#""audit"."sms_receipts""#
+            );
+            assert_eq!(
+                quote_table(r#"x" (a) VALUES (1); DROP TABLE users; --"#),
+                r#""x"" (a) VALUES (1); DROP TABLE users; --""#
+            );
+        }
+    }
+}
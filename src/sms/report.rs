@@ -0,0 +1,193 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::error::{ClientError, TwilioError};
+use crate::sms::{SendSmsResponse, Status};
+use crate::Phone;
+
+const CSV_HEADER: [&str; 7] = [
+    "to",
+    "message_sid",
+    "status",
+    "error_code",
+    "error_message",
+    "price",
+    "price_unit",
+];
+
+/// The outcome of one send in a [`SendReport`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReportRow {
+    pub to: String,
+    pub message_sid: Option<String>,
+    pub status: Option<Status>,
+    pub error_code: Option<i32>,
+    pub error_message: Option<String>,
+    /// What Twilio charged, once it is known. Usually missing right after the send.
+    pub price: Option<String>,
+    pub price_unit: Option<String>,
+}
+
+/// A per-recipient report of a campaign, exported as CSV or JSON to any writer.
+///
+/// ```no_run
+/// # async fn run(client: twilio_client::sms::Client, recipients: Vec<twilio_client::Phone>) -> std::io::Result<()> {
+/// use twilio_client::sms::SendReport;
+///
+/// let mut report = SendReport::new();
+/// for to in &recipients {
+///     let result = client.send(to, "Our sale starts today", None, None).await;
+///     report.record(to, &result);
+/// }
+/// report.write_csv(std::fs::File::create("campaign.csv")?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SendReport {
+    rows: Vec<ReportRow>,
+}
+
+impl SendReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the result of sending to `to`. Failed sends keep the Twilio error code
+    /// when the error came from Twilio.
+    pub fn record(&mut self, to: &Phone, result: &Result<SendSmsResponse, ClientError>) {
+        let row = match result {
+            Ok(resp) => ReportRow {
+                to: to.e164_number(),
+                message_sid: resp.sid.clone(),
+                status: resp.status.clone(),
+                error_code: resp.error_code,
+                error_message: resp.error_message.clone(),
+                price: resp.price.clone(),
+                price_unit: resp.price_unit.clone(),
+            },
+            Err(err) => {
+                let error_code = match err {
+                    ClientError::ServerResponse { message, .. } => {
+                        TwilioError::from_body(message).code
+                    }
+                    _ => None,
+                };
+                ReportRow {
+                    to: to.e164_number(),
+                    error_code,
+                    error_message: Some(err.to_string()),
+                    ..Default::default()
+                }
+            }
+        };
+        self.rows.push(row);
+    }
+
+    pub fn rows(&self) -> &[ReportRow] {
+        &self.rows
+    }
+
+    /// Writes the report as CSV with a header row.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", CSV_HEADER.join(","))?;
+        for row in &self.rows {
+            let status = row
+                .status
+                .as_ref()
+                .map(|status| status.as_str().to_string());
+            let fields = [
+                Some(row.to.clone()),
+                row.message_sid.clone(),
+                status,
+                row.error_code.map(|code| code.to_string()),
+                row.error_message.clone(),
+                row.price.clone(),
+                row.price_unit.clone(),
+            ];
+            let line: Vec<String> = fields
+                .iter()
+                .map(|field| csv_field(field.as_deref().unwrap_or_default()))
+                .collect();
+            writeln!(writer, "{}", line.join(","))?;
+        }
+        writer.flush()
+    }
+
+    /// Writes the report as a JSON array of rows.
+    pub fn write_json<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut writer, &self.rows)?;
+        writer.flush()
+    }
+}
+
+// Quotes fields containing separators, quotes or line breaks, as RFC 4180 expects
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+
+    use crate::error::ClientError;
+    use crate::sms::{SendReport, SendSmsResponse, Status};
+    use crate::Phone;
+
+    fn phone() -> Phone {
+        Phone::parse("0700123456", "KE").unwrap()
+    }
+
+    fn report() -> SendReport {
+        let mut report = SendReport::new();
+        let sent = SendSmsResponse {
+            sid: Some("SM123".to_string()),
+            status: Some(Status::Queued),
+            price: Some("-0.0075".to_string()),
+            price_unit: Some("USD".to_string()),
+            ..Default::default()
+        };
+        report.record(&phone(), &Ok(sent));
+        report.record(
+            &phone(),
+            &Err(ClientError::ServerResponse {
+                status_code: StatusCode::BAD_REQUEST,
+                message:
+                    r#"{"code": 21610, "message": "Attempt to send to unsubscribed recipient"}"#
+                        .to_string(),
+            }),
+        );
+        report
+    }
+
+    #[test]
+    fn csv_has_a_row_per_recipient() {
+        let mut csv = Vec::new();
+        report().write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "to,message_sid,status,error_code,error_message,price,price_unit"
+        );
+        assert_eq!(lines[1], "+254700123456,SM123,queued,,,-0.0075,USD");
+        assert!(lines[2].starts_with("+254700123456,,,21610,\"Server response error"));
+    }
+
+    #[test]
+    fn json_is_an_array_of_rows() {
+        let mut json = Vec::new();
+        report().write_json(&mut json).unwrap();
+        let rows: serde_json::Value = serde_json::from_slice(&json).unwrap();
+
+        assert_eq!(rows[0]["message_sid"], "SM123");
+        assert_eq!(rows[1]["error_code"], 21610);
+    }
+}
@@ -0,0 +1,467 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::clock::{Clock, SystemClock};
+use crate::sms::{
+    HealthWindow, SenderEvent, SenderEventKind, SenderEventSink, SenderHealth, SenderHealthPolicy,
+    Status,
+};
+use crate::{ErrorCode, Phone};
+
+/// How a [`SenderPool`] picks the next sender.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RotationStrategy {
+    /// Spread sends across senders in proportion to their weight.
+    #[default]
+    WeightedRoundRobin,
+    /// Always pick the sender that has been idle the longest.
+    LeastRecentlyUsed,
+}
+
+/// A sender phone with its share of traffic and optional daily cap.
+#[derive(Clone, Debug)]
+pub struct PooledSender {
+    phone: Phone,
+    weight: u32,
+    daily_cap: Option<u32>,
+}
+
+impl PooledSender {
+    pub fn new(phone: Phone) -> Self {
+        Self {
+            phone,
+            weight: 1,
+            daily_cap: None,
+        }
+    }
+
+    /// The relative share of traffic for weighted round-robin. Defaults to 1.
+    pub fn weight(mut self, weight: u32) -> Self {
+        self.weight = weight.max(1);
+        self
+    }
+
+    /// The maximum number of sends per UTC day.
+    pub fn daily_cap(mut self, cap: u32) -> Self {
+        self.daily_cap = Some(cap);
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct SenderState {
+    current_weight: i64,
+    last_used: Option<DateTime<Utc>>,
+    day: Option<NaiveDate>,
+    sent_today: u32,
+    health: HealthWindow,
+}
+
+impl SenderState {
+    // Starts a new count of the day's sends when `today` is a new day
+    fn roll_over(&mut self, today: NaiveDate) {
+        if self.day != Some(today) {
+            self.day = Some(today);
+            self.sent_today = 0;
+        }
+    }
+}
+
+/// Rotates sends across several sender phones, e.g. to stay under per-number carrier
+/// throughput limits on 10DLC.
+#[derive(Debug)]
+pub struct SenderPool {
+    senders: Vec<PooledSender>,
+    strategy: RotationStrategy,
+    clock: Arc<dyn Clock>,
+    health_policy: Option<SenderHealthPolicy>,
+    event_sink: Option<Arc<dyn SenderEventSink>>,
+    state: Mutex<Vec<SenderState>>,
+}
+
+impl SenderPool {
+    pub fn new(senders: Vec<PooledSender>, strategy: RotationStrategy) -> Self {
+        let state = senders.iter().map(|_| SenderState::default()).collect();
+        Self {
+            senders,
+            strategy,
+            clock: Arc::new(SystemClock),
+            health_policy: None,
+            event_sink: None,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// The clock used for daily cap resets and recency.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Take senders whose deliveries keep failing out of rotation according to
+    /// `policy`. Feed statuses in with [`SenderPool::record_status`]; the sms client
+    /// does for every message it fetches.
+    ///
+    /// If every sender is quarantined, sends go through them anyway: the problem is
+    /// then more likely the content or the account than the numbers.
+    pub fn health_policy(mut self, policy: SenderHealthPolicy) -> Self {
+        self.health_policy = Some(policy);
+        self
+    }
+
+    /// Receives an event whenever a sender is quarantined or restored.
+    pub fn event_sink(mut self, sink: Arc<dyn SenderEventSink>) -> Self {
+        self.event_sink = Some(sink);
+        self
+    }
+
+    /// Counts a message status against the sender `from`, e.g. from a status
+    /// callback's `From`, `MessageStatus` and `ErrorCode`. Statuses that aren't final
+    /// and senders outside the pool are ignored.
+    pub fn record_status(&self, from: &Phone, status: Status, error: Option<ErrorCode>) {
+        let Some(policy) = &self.health_policy else {
+            return;
+        };
+        let Some(index) = self.senders.iter().position(|sender| sender.phone == *from) else {
+            return;
+        };
+        let now = self.clock.now();
+        let event = self.state.lock().unwrap()[index]
+            .health
+            .record(status, error, policy, now);
+        if let Some(kind) = event {
+            if let SenderEventKind::Quarantined { score, until } = &kind {
+                tracing::warn!(
+                    "Twilio SMS: quarantining sender {} until {until}, score {score:.2}",
+                    from.e164_number()
+                );
+            }
+            self.emit(kind, from);
+        }
+    }
+
+    /// The health of every sender, in the order they were added.
+    pub fn health(&self) -> Vec<SenderHealth> {
+        let state = self.state.lock().unwrap();
+        self.senders
+            .iter()
+            .zip(state.iter())
+            .map(|(sender, state)| state.health.snapshot(&sender.phone))
+            .collect()
+    }
+
+    fn emit(&self, kind: SenderEventKind, phone: &Phone) {
+        if let Some(sink) = &self.event_sink {
+            sink.record(SenderEvent {
+                kind,
+                phone: phone.clone(),
+            });
+        }
+    }
+
+    pub(crate) fn phones(&self) -> impl Iterator<Item = &Phone> {
+        self.senders.iter().map(|sender| &sender.phone)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.senders.is_empty()
+    }
+
+    /// Picks the next sender and counts a send against it, or returns `None` when
+    /// every sender has reached its daily cap.
+    pub fn next(&self) -> Option<Phone> {
+        let reservation = self.reserve()?;
+        let phone = reservation.phone().clone();
+        reservation.commit();
+        Some(phone)
+    }
+
+    /// Picks the next sender and counts a send against it under the same lock, so
+    /// concurrent sends can't overrun a cap. The send is taken back when the
+    /// reservation is dropped without [`SenderReservation::commit`], e.g. because the
+    /// message failed or was a dry run.
+    pub(crate) fn reserve(&self) -> Option<SenderReservation<'_>> {
+        let now = self.clock.now();
+        let today = now.date_naive();
+        self.restore_quarantined(now);
+        let mut state = self.state.lock().unwrap();
+
+        for s in state.iter_mut() {
+            s.roll_over(today);
+        }
+
+        let mut available: Vec<usize> = (0..self.senders.len())
+            .filter(|i| {
+                self.senders[*i]
+                    .daily_cap
+                    .is_none_or(|cap| state[*i].sent_today < cap)
+            })
+            .collect();
+        if available.is_empty() {
+            return None;
+        }
+        if available.iter().any(|i| !state[*i].health.is_quarantined()) {
+            available.retain(|i| !state[*i].health.is_quarantined());
+        }
+
+        let mut weights = Vec::new();
+        let chosen = match self.strategy {
+            // Smooth weighted round-robin: every candidate gains its weight, the
+            // heaviest is picked and pays back the total.
+            RotationStrategy::WeightedRoundRobin => {
+                let total: i64 = available
+                    .iter()
+                    .map(|i| self.senders[*i].weight as i64)
+                    .sum();
+                for i in &available {
+                    state[*i].current_weight += self.senders[*i].weight as i64;
+                    weights.push((*i, self.senders[*i].weight as i64));
+                }
+                let chosen = *available
+                    .iter()
+                    .max_by_key(|i| (state[**i].current_weight, std::cmp::Reverse(**i)))
+                    .unwrap();
+                state[chosen].current_weight -= total;
+                weights.push((chosen, -total));
+                chosen
+            }
+            RotationStrategy::LeastRecentlyUsed => *available
+                .iter()
+                .min_by_key(|i| (state[**i].last_used, **i))
+                .unwrap(),
+        };
+
+        let previous_use = state[chosen].last_used.replace(now);
+        state[chosen].sent_today += 1;
+        Some(SenderReservation {
+            pool: self,
+            index: chosen,
+            day: today,
+            used_at: now,
+            previous_use,
+            weights,
+            committed: false,
+        })
+    }
+
+    fn restore_quarantined(&self, now: DateTime<Utc>) {
+        let restored: Vec<usize> = {
+            let mut state = self.state.lock().unwrap();
+            (0..state.len())
+                .filter(|i| state[*i].health.restore_if_due(now))
+                .collect()
+        };
+        for i in restored {
+            tracing::info!(
+                "Twilio SMS: sender {} is back in rotation",
+                self.senders[i].phone.e164_number()
+            );
+            self.emit(SenderEventKind::Restored, &self.senders[i].phone);
+        }
+    }
+}
+
+/// A send counted against a pooled sender by [`SenderPool::reserve`], taken back on
+/// drop unless committed.
+#[derive(Debug)]
+pub(crate) struct SenderReservation<'a> {
+    pool: &'a SenderPool,
+    index: usize,
+    day: NaiveDate,
+    used_at: DateTime<Utc>,
+    previous_use: Option<DateTime<Utc>>,
+    // What the pick added to each sender's round-robin weight
+    weights: Vec<(usize, i64)>,
+    committed: bool,
+}
+
+impl SenderReservation<'_> {
+    pub(crate) fn phone(&self) -> &Phone {
+        &self.pool.senders[self.index].phone
+    }
+
+    /// Keeps the send counted, once Twilio has accepted the message.
+    pub(crate) fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for SenderReservation<'_> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        let mut state = self.pool.state.lock().unwrap();
+        // Weights only ever change by addition, so this undoes the pick whatever
+        // happened since
+        for (i, delta) in &self.weights {
+            state[*i].current_weight -= delta;
+        }
+        let sender = &mut state[self.index];
+        if sender.day == Some(self.day) {
+            sender.sent_today = sender.sent_today.saturating_sub(1);
+        }
+        if sender.last_used == Some(self.used_at) {
+            sender.last_used = self.previous_use;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use chrono::Duration;
+
+    use crate::clock::MockClock;
+    use crate::sms::{
+        PooledSender, RotationStrategy, SenderEvent, SenderEventKind, SenderEventSink,
+        SenderHealthPolicy, SenderPool, Status,
+    };
+    use crate::{ErrorCode, Phone};
+
+    fn phone(number: &str) -> Phone {
+        Phone::parse(number, "KE").unwrap()
+    }
+
+    #[derive(Debug, Default)]
+    struct Events(Mutex<Vec<SenderEvent>>);
+
+    impl SenderEventSink for Events {
+        fn record(&self, event: SenderEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    #[test]
+    fn weighted_round_robin_follows_weights() {
+        let a = phone("0700782326");
+        let b = phone("0700123456");
+        let pool = SenderPool::new(
+            vec![
+                PooledSender::new(a.clone()).weight(3),
+                PooledSender::new(b.clone()),
+            ],
+            RotationStrategy::WeightedRoundRobin,
+        );
+
+        let picks: Vec<Phone> = (0..8).map(|_| pool.next().unwrap()).collect();
+
+        assert_eq!(picks.iter().filter(|p| **p == a).count(), 6);
+        assert_eq!(picks.iter().filter(|p| **p == b).count(), 2);
+    }
+
+    #[test]
+    fn least_recently_used_alternates() {
+        let a = phone("0700782326");
+        let b = phone("0700123456");
+        let clock = MockClock::default();
+        let pool = SenderPool::new(
+            vec![PooledSender::new(a.clone()), PooledSender::new(b.clone())],
+            RotationStrategy::LeastRecentlyUsed,
+        )
+        .clock(Arc::new(clock.clone()));
+
+        let first = pool.next().unwrap();
+        clock.advance(Duration::seconds(1));
+        let second = pool.next().unwrap();
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn daily_caps_exhaust_and_reset_the_next_day() {
+        let clock = MockClock::default();
+        let pool = SenderPool::new(
+            vec![PooledSender::new(phone("0700782326")).daily_cap(1)],
+            RotationStrategy::WeightedRoundRobin,
+        )
+        .clock(Arc::new(clock.clone()));
+
+        assert!(pool.next().is_some());
+        assert!(pool.next().is_none());
+
+        clock.advance(Duration::days(1));
+        assert!(pool.next().is_some());
+    }
+
+    #[test]
+    fn reservations_hold_the_cap_until_dropped() {
+        let a = phone("0700782326");
+        let pool = SenderPool::new(
+            vec![PooledSender::new(a.clone()).daily_cap(1)],
+            RotationStrategy::LeastRecentlyUsed,
+        );
+
+        let reservation = pool.reserve().unwrap();
+        assert_eq!(reservation.phone(), &a);
+        assert!(pool.reserve().is_none());
+        drop(reservation);
+
+        pool.reserve().unwrap().commit();
+        assert!(pool.reserve().is_none());
+    }
+
+    #[test]
+    fn dropped_reservations_leave_the_rotation_unchanged() {
+        let (a, b) = (phone("0700782326"), phone("0700123456"));
+        let pool = SenderPool::new(
+            vec![
+                PooledSender::new(a.clone()).weight(2),
+                PooledSender::new(b.clone()),
+            ],
+            RotationStrategy::WeightedRoundRobin,
+        );
+
+        for _ in 0..5 {
+            assert_eq!(pool.reserve().unwrap().phone(), &a);
+        }
+
+        let picks: Vec<Phone> = (0..3).map(|_| pool.next().unwrap()).collect();
+        assert_eq!(picks, vec![a.clone(), b, a]);
+    }
+
+    #[test]
+    fn filtered_senders_are_quarantined_then_restored() {
+        let (a, b) = (phone("0700782326"), phone("0700123456"));
+        let clock = MockClock::default();
+        let events = Arc::new(Events::default());
+        let pool = SenderPool::new(
+            vec![PooledSender::new(a.clone()), PooledSender::new(b.clone())],
+            RotationStrategy::LeastRecentlyUsed,
+        )
+        .clock(Arc::new(clock.clone()))
+        .health_policy(
+            SenderHealthPolicy::new()
+                .min_samples(4)
+                .quarantine_for(std::time::Duration::from_secs(60)),
+        )
+        .event_sink(events.clone());
+
+        pool.record_status(&a, Status::Delivered, None);
+        // Recipients that can't receive messages don't count against the sender
+        pool.record_status(&a, Status::Undelivered, Some(ErrorCode::Unsubscribed));
+        pool.record_status(&a, Status::Failed, Some(ErrorCode::CarrierViolation));
+        pool.record_status(&a, Status::Failed, Some(ErrorCode::CarrierViolation));
+        assert!(events.0.lock().unwrap().is_empty());
+        pool.record_status(&a, Status::Undelivered, None);
+
+        let health = &pool.health()[0];
+        assert_eq!((health.samples, health.filtered), (4, 2));
+        assert_eq!(health.score, Some(0.25));
+        assert!(matches!(
+            events.0.lock().unwrap()[0].kind,
+            SenderEventKind::Quarantined { score, .. } if score == 0.25
+        ));
+        for _ in 0..3 {
+            clock.advance(Duration::seconds(1));
+            assert_eq!(pool.next(), Some(b.clone()));
+        }
+
+        clock.advance(Duration::seconds(60));
+        assert_eq!(pool.next(), Some(a.clone()));
+        assert_eq!(events.0.lock().unwrap()[1].kind, SenderEventKind::Restored);
+        assert_eq!(pool.health()[0].samples, 0);
+    }
+}
@@ -0,0 +1,69 @@
+use reqwest::Url;
+
+use crate::Phone;
+
+/// The number every Twilio account's WhatsApp sandbox sends from.
+pub const WHATSAPP_SANDBOX_SENDER: &str = "+14155238886";
+
+// Twilio error for a recipient that hasn't joined the sandbox
+pub(crate) const NOT_JOINED_ERROR_CODE: i32 = 63015;
+
+/// Twilio's WhatsApp sandbox, for trying WhatsApp flows in development without an
+/// approved sender or templates.
+///
+/// A client built with [`ClientBuilder::whatsapp_sandbox`](crate::sms::ClientBuilder::whatsapp_sandbox)
+/// sends WhatsApp messages from [`WHATSAPP_SANDBOX_SENDER`] with the text as a plain
+/// body. Recipients first have to send `join <code>` to the sandbox number; the code
+/// is shown in the Twilio console.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WhatsAppSandbox {
+    join_code: String,
+}
+
+impl WhatsAppSandbox {
+    /// A sandbox joined with `join <join_code>`, e.g. `"orange-tiger"`.
+    pub fn new(join_code: impl Into<String>) -> Self {
+        let join_code = join_code.into();
+        let join_code = join_code
+            .trim()
+            .strip_prefix("join ")
+            .unwrap_or(join_code.trim())
+            .to_string();
+        Self { join_code }
+    }
+
+    pub fn sender(&self) -> Phone {
+        Phone::parse_with_no_country(WHATSAPP_SANDBOX_SENDER)
+            .expect("the sandbox sender is a valid number")
+    }
+
+    /// The message a tester sends to the sandbox number to join it.
+    pub fn join_message(&self) -> String {
+        format!("join {}", self.join_code)
+    }
+
+    /// A `wa.me` link that opens WhatsApp with the join message filled in.
+    pub fn join_link(&self) -> Url {
+        let mut url = Url::parse("https://wa.me/").expect("static url is valid");
+        url.set_path(WHATSAPP_SANDBOX_SENDER.trim_start_matches('+'));
+        url.query_pairs_mut()
+            .append_pair("text", &self.join_message());
+        url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sms::WhatsAppSandbox;
+
+    #[test]
+    fn join_link_carries_the_join_message() {
+        let sandbox = WhatsAppSandbox::new("join orange-tiger");
+
+        assert_eq!(sandbox.join_message(), "join orange-tiger");
+        assert_eq!(
+            sandbox.join_link().as_str(),
+            "https://wa.me/14155238886?text=join+orange-tiger"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! Works out how a body will be encoded and split into segments, which is what
+//! carriers bill for, so apps can warn before sending a long or non-GSM message.
+//!
+//! ```
+//! use twilio_client::sms::segments::{self, Encoding};
+//!
+//! let info = segments::count("Your code is 123456");
+//! assert_eq!(info.encoding, Encoding::Gsm7);
+//! assert_eq!(info.segments, 1);
+//! assert_eq!(info.remaining, 141);
+//! ```
+
+// The GSM 03.38 default alphabet, one septet each
+const GSM7_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+                          ¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+// The extension table, an escape septet plus one each
+const GSM7_EXTENDED: &str = "^{}\\[~]|€\u{0c}";
+
+/// How a body is encoded on the wire.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Encoding {
+    /// 7-bit GSM: 160 characters in one segment, 153 per segment once split.
+    Gsm7,
+    /// UCS-2, used as soon as one character is outside GSM-7: 70 UTF-16 units in one
+    /// segment, 67 per segment once split.
+    Ucs2,
+}
+
+impl Encoding {
+    /// How many units fit in a message that isn't split.
+    pub fn single_segment_capacity(self) -> usize {
+        match self {
+            Encoding::Gsm7 => 160,
+            Encoding::Ucs2 => 70,
+        }
+    }
+
+    /// How many units fit in each part of a split message, after the concatenation
+    /// header.
+    pub fn multi_segment_capacity(self) -> usize {
+        match self {
+            Encoding::Gsm7 => 153,
+            Encoding::Ucs2 => 67,
+        }
+    }
+}
+
+/// The result of [`count`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct SegmentInfo {
+    pub encoding: Encoding,
+    /// Segments the body is sent as; an empty body still takes one.
+    pub segments: usize,
+    /// Encoded length: septets for GSM-7, where extension characters such as `€` take
+    /// two, or UTF-16 units for UCS-2, where most emoji take two.
+    pub units: usize,
+    /// Units left in the last segment before another one is needed.
+    pub remaining: usize,
+}
+
+impl SegmentInfo {
+    pub fn is_multi_segment(&self) -> bool {
+        self.segments > 1
+    }
+}
+
+/// Whether `c` can be sent in GSM-7, and how many septets it takes.
+pub fn gsm7_width(c: char) -> Option<usize> {
+    if GSM7_BASIC.contains(c) {
+        Some(1)
+    } else if GSM7_EXTENDED.contains(c) {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// The encoding `body` needs.
+pub fn encoding(body: &str) -> Encoding {
+    if body.chars().all(|c| gsm7_width(c).is_some()) {
+        Encoding::Gsm7
+    } else {
+        Encoding::Ucs2
+    }
+}
+
+/// Counts the segments `body` is sent as.
+pub fn count(body: &str) -> SegmentInfo {
+    let encoding = encoding(body);
+    let widths = body.chars().map(|c| match encoding {
+        Encoding::Gsm7 => gsm7_width(c).unwrap_or(1),
+        Encoding::Ucs2 => c.len_utf16(),
+    });
+    let units: usize = widths.clone().sum();
+
+    if units <= encoding.single_segment_capacity() {
+        return SegmentInfo {
+            encoding,
+            segments: 1,
+            units,
+            remaining: encoding.single_segment_capacity() - units,
+        };
+    }
+
+    // A character is never split across segments, so a segment can end short of the
+    // capacity when the next character takes two units.
+    let capacity = encoding.multi_segment_capacity();
+    let (mut segments, mut used) = (1, 0);
+    for width in widths {
+        if used + width > capacity {
+            segments += 1;
+            used = 0;
+        }
+        used += width;
+    }
+    SegmentInfo {
+        encoding,
+        segments,
+        units,
+        remaining: capacity - used,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sms::segments::{count, Encoding};
+
+    #[test]
+    fn gsm_bodies_fit_160_characters_in_one_segment() {
+        let info = count(&"a".repeat(160));
+
+        assert_eq!(
+            (info.encoding, info.segments, info.remaining),
+            (Encoding::Gsm7, 1, 0)
+        );
+        assert_eq!(count(&"a".repeat(161)).segments, 2);
+        assert_eq!(count(&"a".repeat(306)).segments, 2);
+        assert_eq!(count(&"a".repeat(307)).segments, 3);
+    }
+
+    #[test]
+    fn extension_characters_take_two_septets_and_are_not_split() {
+        assert_eq!(count("€").units, 2);
+
+        // 152 septets, then a two-septet character that doesn't fit the first part
+        let info = count(&format!("{}€{}", "a".repeat(152), "a".repeat(10)));
+        assert_eq!(info.segments, 2);
+        assert_eq!(info.remaining, 153 - 12);
+    }
+
+    #[test]
+    fn any_other_character_switches_to_ucs2() {
+        let info = count("Habari 👋");
+
+        assert_eq!(info.encoding, Encoding::Ucs2);
+        assert_eq!(info.units, 9);
+        assert_eq!(info.remaining, 61);
+        assert_eq!(count(&"ж".repeat(71)).segments, 2);
+    }
+}
@@ -0,0 +1,35 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+
+use crate::error::ClientError;
+use crate::sms::{Client, SendSmsResponse};
+use crate::Phone;
+
+/// Anything that can send an SMS the way [`Client::send`] does.
+///
+/// Code that depends on this trait rather than on [`Client`] can swap in fakes,
+/// wrappers like [`ShadowSender`](super::ShadowSender), or another account.
+#[async_trait]
+pub trait SmsSender: Debug + Send + Sync {
+    async fn send(
+        &self,
+        to: &Phone,
+        content: &str,
+        send_as_mms: Option<bool>,
+        media_url: Option<Vec<String>>,
+    ) -> Result<SendSmsResponse, ClientError>;
+}
+
+#[async_trait]
+impl SmsSender for Client {
+    async fn send(
+        &self,
+        to: &Phone,
+        content: &str,
+        send_as_mms: Option<bool>,
+        media_url: Option<Vec<String>>,
+    ) -> Result<SendSmsResponse, ClientError> {
+        Client::send(self, to, content, send_as_mms, media_url).await
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::sms::Status;
+use crate::{ErrorCode, Phone};
+
+/// When a [`SenderPool`](crate::sms::SenderPool) takes a sender out of rotation.
+///
+/// A sender's score is the share of its recent final statuses that were delivered.
+/// Failures that say nothing about the sender, such as an unsubscribed or landline
+/// recipient, aren't counted. Carrier filtering is counted, and reported separately in
+/// [`SenderHealth::filtered`].
+///
+/// ```
+/// # use std::time::Duration;
+/// # use twilio_client::sms::SenderHealthPolicy;
+/// // Quarantine for 2 hours once fewer than 70% of the last 100 messages were delivered
+/// let policy = SenderHealthPolicy::new()
+///     .window(100)
+///     .min_score(0.7)
+///     .quarantine_for(Duration::from_secs(2 * 60 * 60));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SenderHealthPolicy {
+    window: usize,
+    min_samples: usize,
+    min_score: f64,
+    quarantine_for: Duration,
+}
+
+impl SenderHealthPolicy {
+    pub fn new() -> Self {
+        Self {
+            window: 50,
+            min_samples: 20,
+            min_score: 0.8,
+            quarantine_for: Duration::hours(1),
+        }
+    }
+
+    /// How many of the latest final statuses the score is computed over. Defaults
+    /// to 50.
+    pub fn window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// How many statuses a sender needs before it can be quarantined. Defaults to 20.
+    pub fn min_samples(mut self, min_samples: usize) -> Self {
+        self.min_samples = min_samples.max(1);
+        self
+    }
+
+    /// The score, from 0.0 to 1.0, below which a sender is quarantined. Defaults
+    /// to 0.8.
+    pub fn min_score(mut self, min_score: f64) -> Self {
+        self.min_score = min_score.clamp(0.0, 1.0);
+        self
+    }
+
+    /// How long a quarantined sender stays out of rotation. It comes back with a
+    /// clean slate. Defaults to 1 hour.
+    pub fn quarantine_for(mut self, period: std::time::Duration) -> Self {
+        self.quarantine_for = Duration::from_std(period).unwrap_or(Duration::MAX);
+        self
+    }
+}
+
+impl Default for SenderHealthPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A sender's standing in a [`SenderPool`](crate::sms::SenderPool).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SenderHealth {
+    pub phone: Phone,
+    /// The share of counted statuses that were delivered, or `None` before any.
+    pub score: Option<f64>,
+    /// How many statuses the score is based on.
+    pub samples: usize,
+    /// How many of them were filtered by the carrier.
+    pub filtered: usize,
+    pub quarantined_until: Option<DateTime<Utc>>,
+}
+
+/// What happened to a sender.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SenderEventKind {
+    /// The sender's score fell below the policy's minimum.
+    Quarantined { score: f64, until: DateTime<Utc> },
+    /// The sender's quarantine ended and it is back in rotation.
+    Restored,
+}
+
+/// An event emitted by a [`SenderPool`](crate::sms::SenderPool).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SenderEvent {
+    pub kind: SenderEventKind,
+    pub phone: Phone,
+}
+
+/// Receives sender quarantine events, e.g. to alert whoever manages the numbers.
+///
+/// `record` is called inline on the send path, so implementations should hand off
+/// any slow work.
+pub trait SenderEventSink: Debug + Send + Sync {
+    fn record(&self, event: SenderEvent);
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Outcome {
+    Delivered,
+    Failed,
+    Filtered,
+}
+
+impl Outcome {
+    // `None` for statuses that aren't final or don't reflect on the sender
+    fn of(status: Status, error: Option<ErrorCode>) -> Option<Self> {
+        match (status, error) {
+            (Status::Delivered | Status::Read, _) => Some(Outcome::Delivered),
+            (Status::Failed | Status::Undelivered, Some(error)) if is_recipient_fault(error) => {
+                None
+            }
+            (
+                Status::Failed | Status::Undelivered,
+                Some(ErrorCode::CarrierViolation | ErrorCode::UnregisteredSender),
+            ) => Some(Outcome::Filtered),
+            (Status::Failed | Status::Undelivered, _) => Some(Outcome::Failed),
+            _ => None,
+        }
+    }
+}
+
+fn is_recipient_fault(error: ErrorCode) -> bool {
+    matches!(
+        error,
+        ErrorCode::InvalidToNumber
+            | ErrorCode::Unsubscribed
+            | ErrorCode::NotMobile
+            | ErrorCode::UnreachableHandset
+            | ErrorCode::UnknownDestination
+            | ErrorCode::LandlineOrUnreachableCarrier
+    )
+}
+
+/// The recent outcomes of one sender and its quarantine.
+#[derive(Debug, Default)]
+pub(crate) struct HealthWindow {
+    outcomes: VecDeque<Outcome>,
+    quarantined_until: Option<DateTime<Utc>>,
+}
+
+impl HealthWindow {
+    /// Records a status, returning the quarantine it triggered, if any.
+    pub(crate) fn record(
+        &mut self,
+        status: Status,
+        error: Option<ErrorCode>,
+        policy: &SenderHealthPolicy,
+        now: DateTime<Utc>,
+    ) -> Option<SenderEventKind> {
+        let outcome = Outcome::of(status, error)?;
+        if self.outcomes.len() == policy.window {
+            self.outcomes.pop_front();
+        }
+        self.outcomes.push_back(outcome);
+
+        let score = self.score()?;
+        if self.quarantined_until.is_some()
+            || self.outcomes.len() < policy.min_samples
+            || score >= policy.min_score
+        {
+            return None;
+        }
+        let until = now + policy.quarantine_for;
+        self.quarantined_until = Some(until);
+        Some(SenderEventKind::Quarantined { score, until })
+    }
+
+    pub(crate) fn is_quarantined(&self) -> bool {
+        self.quarantined_until.is_some()
+    }
+
+    /// Ends a quarantine that is over, returning whether it did.
+    pub(crate) fn restore_if_due(&mut self, now: DateTime<Utc>) -> bool {
+        match self.quarantined_until {
+            Some(until) if until <= now => {
+                self.quarantined_until = None;
+                self.outcomes.clear();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn snapshot(&self, phone: &Phone) -> SenderHealth {
+        SenderHealth {
+            phone: phone.clone(),
+            score: self.score(),
+            samples: self.outcomes.len(),
+            filtered: self
+                .outcomes
+                .iter()
+                .filter(|outcome| **outcome == Outcome::Filtered)
+                .count(),
+            quarantined_until: self.quarantined_until,
+        }
+    }
+
+    fn score(&self) -> Option<f64> {
+        if self.outcomes.is_empty() {
+            return None;
+        }
+        let delivered = self
+            .outcomes
+            .iter()
+            .filter(|outcome| **outcome == Outcome::Delivered)
+            .count();
+        Some(delivered as f64 / self.outcomes.len() as f64)
+    }
+}
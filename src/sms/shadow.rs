@@ -0,0 +1,182 @@
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::error::ClientError;
+use crate::sms::{SendSmsResponse, SmsSender};
+use crate::Phone;
+
+/// How a mirrored send compared between the primary and secondary sender.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShadowComparison {
+    pub primary_ok: bool,
+    pub secondary_ok: bool,
+    /// Both senders succeeded with the same status, or both failed.
+    pub matched: bool,
+}
+
+/// Receives the result of every mirrored send.
+pub trait ShadowObserver: Debug + Send + Sync {
+    fn observe(&self, comparison: ShadowComparison);
+}
+
+/// Sends through `primary` and mirrors a percentage of traffic to `secondary`.
+///
+/// The caller always gets the primary result; the secondary result is only compared
+/// and reported. Use it to de-risk a move to another account or provider.
+#[derive(Debug)]
+pub struct ShadowSender {
+    primary: Arc<dyn SmsSender>,
+    secondary: Arc<dyn SmsSender>,
+    percentage: u8,
+    observer: Option<Arc<dyn ShadowObserver>>,
+    sent: AtomicU64,
+}
+
+impl ShadowSender {
+    /// Mirrors `percentage` (0 to 100) percent of sends to `secondary`.
+    pub fn new(primary: Arc<dyn SmsSender>, secondary: Arc<dyn SmsSender>, percentage: u8) -> Self {
+        Self {
+            primary,
+            secondary,
+            percentage: percentage.min(100),
+            observer: None,
+            sent: AtomicU64::new(0),
+        }
+    }
+
+    pub fn observer(mut self, observer: Arc<dyn ShadowObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    // Spreads mirrored sends evenly instead of sampling randomly, so small percentages
+    // still mirror predictably.
+    fn should_mirror(&self) -> bool {
+        let n = self.sent.fetch_add(1, Ordering::Relaxed);
+        n % 100 < self.percentage as u64
+    }
+}
+
+#[async_trait]
+impl SmsSender for ShadowSender {
+    async fn send(
+        &self,
+        to: &Phone,
+        content: &str,
+        send_as_mms: Option<bool>,
+        media_url: Option<Vec<String>>,
+    ) -> Result<SendSmsResponse, ClientError> {
+        if !self.should_mirror() {
+            return self.primary.send(to, content, send_as_mms, media_url).await;
+        }
+
+        let (primary, secondary) = tokio::join!(
+            self.primary
+                .send(to, content, send_as_mms, media_url.clone()),
+            self.secondary.send(to, content, send_as_mms, media_url),
+        );
+
+        let matched = match (&primary, &secondary) {
+            (Ok(p), Ok(s)) => p.status == s.status,
+            (Err(_), Err(_)) => true,
+            _ => false,
+        };
+        let comparison = ShadowComparison {
+            primary_ok: primary.is_ok(),
+            secondary_ok: secondary.is_ok(),
+            matched,
+        };
+        if !matched {
+            tracing::warn!(?comparison, "Twilio SMS: shadow send outcome mismatch");
+        }
+        if let Some(observer) = &self.observer {
+            observer.observe(comparison);
+        }
+
+        primary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+
+    use crate::error::ClientError;
+    use crate::sms::{
+        SendSmsResponse, ShadowComparison, ShadowObserver, ShadowSender, SmsSender, Status,
+    };
+    use crate::Phone;
+
+    #[derive(Debug, Default)]
+    struct FakeSender {
+        status: Option<Status>,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SmsSender for FakeSender {
+        async fn send(
+            &self,
+            _to: &Phone,
+            _content: &str,
+            _send_as_mms: Option<bool>,
+            _media_url: Option<Vec<String>>,
+        ) -> Result<SendSmsResponse, ClientError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(SendSmsResponse {
+                status: self.status.clone(),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<ShadowComparison>>);
+
+    impl ShadowObserver for Recorder {
+        fn observe(&self, comparison: ShadowComparison) {
+            self.0.lock().unwrap().push(comparison);
+        }
+    }
+
+    fn phone() -> Phone {
+        Phone::parse("0700782326", "KE").unwrap()
+    }
+
+    #[tokio::test]
+    async fn only_the_configured_percentage_is_mirrored() {
+        let secondary = Arc::new(FakeSender::default());
+        let sender = ShadowSender::new(Arc::new(FakeSender::default()), secondary.clone(), 10);
+
+        for _ in 0..100 {
+            sender.send(&phone(), "hi", None, None).await.unwrap();
+        }
+
+        assert_eq!(secondary.calls.load(Ordering::SeqCst), 10);
+    }
+
+    #[tokio::test]
+    async fn mismatched_outcomes_are_reported() {
+        let primary = Arc::new(FakeSender {
+            status: Some(Status::Queued),
+            ..Default::default()
+        });
+        let secondary = Arc::new(FakeSender {
+            status: Some(Status::Failed),
+            ..Default::default()
+        });
+        let recorder = Arc::new(Recorder::default());
+        let sender = ShadowSender::new(primary, secondary, 100).observer(recorder.clone());
+
+        let resp = sender.send(&phone(), "hi", None, None).await.unwrap();
+
+        assert_eq!(resp.status, Some(Status::Queued));
+        assert!(!recorder.0.lock().unwrap()[0].matched);
+    }
+}
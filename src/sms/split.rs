@@ -0,0 +1,253 @@
+use std::time::Duration;
+
+use crate::error::ClientError;
+use crate::sms::segments::{self, Encoding};
+use crate::sms::{Client, MessageBuilder, SendSmsResponse};
+use crate::Raw;
+
+/// The longest body Twilio accepts in one message, in characters.
+pub const MAX_BODY_LENGTH: usize = 1600;
+
+/// How [`Client::send_split_with`] splits a long body: into parts of at most
+/// `max_segments` segments each, sent `delay` apart so they tend to arrive in order.
+///
+/// For destinations whose carriers garble concatenated SMS, one segment per part
+/// sidesteps concatenation altogether.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use twilio_client::sms::SplitPolicy;
+/// let policy = SplitPolicy::new()
+///     .max_segments(2)
+///     .delay(Duration::from_millis(500));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SplitPolicy {
+    max_segments: usize,
+    delay: Duration,
+}
+
+impl SplitPolicy {
+    pub fn new() -> Self {
+        Self {
+            max_segments: 1,
+            delay: Duration::from_secs(1),
+        }
+    }
+
+    /// The most segments in each part. Defaults to 1.
+    pub fn max_segments(mut self, max_segments: usize) -> Self {
+        self.max_segments = max_segments.max(1);
+        self
+    }
+
+    /// How long to wait after each part before sending the next. Defaults to 1 second.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    // How many units of `encoding` fit in a part
+    fn capacity(&self, encoding: Encoding) -> usize {
+        let units = match self.max_segments {
+            1 => encoding.single_segment_capacity(),
+            n => n * encoding.multi_segment_capacity(),
+        };
+        units.min(MAX_BODY_LENGTH)
+    }
+}
+
+impl Default for SplitPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    /// Sends `message` like [`Client::send_message`], but splits a body longer than
+    /// [`MAX_BODY_LENGTH`] into parts sent one after another, each starting with its
+    /// number, e.g. `(2/3) `. Parts break between words where possible and media goes
+    /// with the first part only. Returns one response per part, in order.
+    ///
+    /// Sending stops at the first part that fails; the parts before it have already
+    /// been sent.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Send split message",
+        skip(self, message)
+    )]
+    pub async fn send_split(
+        &self,
+        message: &MessageBuilder,
+    ) -> Result<Vec<SendSmsResponse>, ClientError> {
+        let parts = split_body(&message.body, MAX_BODY_LENGTH);
+        self.send_parts(message, parts, Duration::ZERO).await
+    }
+
+    /// Sends `message` like [`Client::send_split`], but splits a body that takes more
+    /// than `policy`'s segments, pausing between parts. Returns one response per part,
+    /// in order, each with its message SID.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio SMS: Send split message with policy",
+        skip(self, message)
+    )]
+    pub async fn send_split_with(
+        &self,
+        message: &MessageBuilder,
+        policy: &SplitPolicy,
+    ) -> Result<Vec<SendSmsResponse>, ClientError> {
+        let encoding = segments::encoding(&message.body);
+        let parts = split_body_by(
+            &message.body,
+            policy.capacity(encoding),
+            |c| match encoding {
+                Encoding::Gsm7 => segments::gsm7_width(c).unwrap_or(1),
+                Encoding::Ucs2 => c.len_utf16(),
+            },
+        );
+        self.send_parts(message, parts, policy.delay).await
+    }
+
+    async fn send_parts(
+        &self,
+        message: &MessageBuilder,
+        parts: Vec<String>,
+        delay: Duration,
+    ) -> Result<Vec<SendSmsResponse>, ClientError> {
+        if parts.len() == 1 {
+            return Ok(vec![self.send_message(message).await?]);
+        }
+
+        let total = parts.len();
+        let later_options = message.options.without_media();
+        let mut responses = Vec::with_capacity(total);
+        for (index, part) in parts.iter().enumerate() {
+            if index > 0 && !delay.is_zero() {
+                self.transport.clock.sleep(delay).await;
+            }
+            let body = format!("({}/{total}) {part}", index + 1);
+            let options = if index == 0 {
+                &message.options
+            } else {
+                &later_options
+            };
+            let response = self.deliver(&message.to, &body, options).await?;
+            responses.push(Raw::into_inner(response));
+        }
+        Ok(responses)
+    }
+}
+
+/// Splits `body` into parts that fit `max_len` characters once numbered, or returns it
+/// whole if it already fits.
+pub(crate) fn split_body(body: &str, max_len: usize) -> Vec<String> {
+    split_body_by(body, max_len, |_| 1)
+}
+
+/// Splits `body` like [`split_body`], measuring each character as `width` units.
+fn split_body_by(body: &str, max_units: usize, width: impl Fn(char) -> usize) -> Vec<String> {
+    if body.chars().map(&width).sum::<usize>() <= max_units {
+        return vec![body.to_string()];
+    }
+    // The widest prefix, `(n/n) `, depends on how many parts there are, so retry with
+    // a wider one until the count fits. Its characters are one unit wide in every
+    // encoding.
+    let mut digits = 1;
+    loop {
+        let parts = split_at_words(body, max_units - (2 * digits + 4), &width);
+        if parts.len().to_string().len() <= digits {
+            return parts;
+        }
+        digits += 1;
+    }
+}
+
+fn split_at_words(body: &str, capacity: usize, width: impl Fn(char) -> usize) -> Vec<String> {
+    let chars: Vec<char> = body.chars().collect();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let mut end = start;
+        let mut used = 0;
+        while end < chars.len() && used + width(chars[end]) <= capacity {
+            used += width(chars[end]);
+            end += 1;
+        }
+        end = end.max(start + 1);
+        if end < chars.len() && !chars[end].is_whitespace() {
+            // Break after the last whitespace in the part, unless it is one long word
+            if let Some(space) = chars[start..end].iter().rposition(|c| c.is_whitespace()) {
+                end = start + space + 1;
+            }
+        }
+        let part: String = chars[start..end].iter().collect();
+        parts.push(part.trim_end().to_string());
+        start = end;
+        while start < chars.len() && chars[start].is_whitespace() {
+            start += 1;
+        }
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sms::split::{split_body, split_body_by};
+    use crate::sms::{segments, SplitPolicy};
+
+    #[test]
+    fn short_bodies_are_not_split() {
+        assert_eq!(split_body("hello", 10), vec!["hello"]);
+    }
+
+    #[test]
+    fn parts_break_between_words_and_leave_room_for_the_number() {
+        // 20 characters less the `(n/n) ` prefix leaves 14 for each part
+        let parts = split_body("the quick brown fox jumps over the lazy dog", 20);
+
+        assert_eq!(
+            parts,
+            vec!["the quick", "brown fox", "jumps over the", "lazy dog"]
+        );
+        assert!(parts.iter().all(|part| part.chars().count() <= 14));
+    }
+
+    #[test]
+    fn words_longer_than_a_part_are_cut() {
+        let parts = split_body(&"a".repeat(25), 16);
+
+        assert_eq!(parts, vec!["a".repeat(10), "a".repeat(10), "a".repeat(5)]);
+    }
+
+    #[test]
+    fn the_prefix_widens_with_ten_or_more_parts() {
+        // Room for 6 characters each makes 10 parts, whose wider prefix leaves room for 4
+        let parts = split_body(&"a".repeat(60), 12);
+
+        assert_eq!(parts.len(), 15);
+        assert!(parts.iter().all(|part| part.len() == 4));
+    }
+
+    #[test]
+    fn extension_characters_take_two_units() {
+        // 16 units less the prefix leave room for 5 two-unit characters
+        let parts = split_body_by(&"€".repeat(9), 16, |c| segments::gsm7_width(c).unwrap());
+
+        assert_eq!(parts, vec!["€".repeat(5), "€".repeat(4)]);
+    }
+
+    #[test]
+    fn parts_fit_the_segment_budget() {
+        let body = "the quick brown fox jumps over the lazy dog ".repeat(10);
+        let policy = SplitPolicy::new();
+
+        let parts = split_body_by(&body, policy.capacity(segments::encoding(&body)), |_| 1);
+
+        assert_eq!(parts.len(), 3);
+        for (index, part) in parts.iter().enumerate() {
+            let numbered = format!("({}/3) {part}", index + 1);
+            assert_eq!(segments::count(&numbered).segments, 1);
+        }
+    }
+}
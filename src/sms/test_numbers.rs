@@ -0,0 +1,76 @@
+use crate::{ErrorCode, Phone};
+
+/// The magic numbers Twilio's test credentials accept, and the error each one
+/// simulates. Sends with test credentials cost nothing and reach no handset.
+///
+/// Simulated failures come back as the same [`ClientError`](crate::error::ClientError)
+/// real traffic produces, so [`ClientError::error_code`](crate::error::ClientError::error_code)
+/// returns [`MagicNumber::simulated_error`] for them.
+///
+/// ```no_run
+/// # async fn run(client: twilio_client::sms::Client) {
+/// use twilio_client::sms::MagicNumber;
+///
+/// let to = MagicNumber::BlockedRecipient.phone();
+/// let err = client.send(&to, "hello", None, None).await.unwrap_err();
+/// assert_eq!(err.error_code(), MagicNumber::BlockedRecipient.simulated_error());
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum MagicNumber {
+    /// A `From` number that sends successfully.
+    ValidSender,
+    /// A `From` number that isn't a valid phone number.
+    InvalidSender,
+    /// A `From` number that isn't an SMS-capable number on the account.
+    SenderNotSmsCapable,
+    /// A `From` number whose queue is full.
+    SenderQueueFull,
+    /// A `To` number that isn't a valid phone number.
+    InvalidRecipient,
+    /// A `To` number Twilio can't route to.
+    UnroutableRecipient,
+    /// A `To` number in a region the account can't send to.
+    NoInternationalPermission,
+    /// A `To` number that has replied STOP.
+    BlockedRecipient,
+    /// A `To` number that can't receive SMS.
+    RecipientNotSmsCapable,
+}
+
+impl MagicNumber {
+    /// The number in E.164 format.
+    pub fn number(self) -> &'static str {
+        match self {
+            MagicNumber::ValidSender => "+15005550006",
+            MagicNumber::InvalidSender | MagicNumber::InvalidRecipient => "+15005550001",
+            MagicNumber::SenderNotSmsCapable => "+15005550007",
+            MagicNumber::SenderQueueFull => "+15005550008",
+            MagicNumber::UnroutableRecipient => "+15005550002",
+            MagicNumber::NoInternationalPermission => "+15005550003",
+            MagicNumber::BlockedRecipient => "+15005550004",
+            MagicNumber::RecipientNotSmsCapable => "+15005550009",
+        }
+    }
+
+    pub fn phone(self) -> Phone {
+        Phone::parse_with_no_country(self.number()).expect("magic numbers are valid numbers")
+    }
+
+    /// The error Twilio answers with when the number is used in its role, or `None`
+    /// for [`MagicNumber::ValidSender`].
+    pub fn simulated_error(self) -> Option<ErrorCode> {
+        let code = match self {
+            MagicNumber::ValidSender => return None,
+            MagicNumber::InvalidSender => ErrorCode::InvalidFromNumber,
+            MagicNumber::SenderNotSmsCapable => ErrorCode::SenderNotSmsCapable,
+            MagicNumber::SenderQueueFull => ErrorCode::SenderQueueFull,
+            MagicNumber::InvalidRecipient => ErrorCode::InvalidToNumber,
+            MagicNumber::UnroutableRecipient => ErrorCode::CannotRoute,
+            MagicNumber::NoInternationalPermission => ErrorCode::RegionNotEnabled,
+            MagicNumber::BlockedRecipient => ErrorCode::Unsubscribed,
+            MagicNumber::RecipientNotSmsCapable => ErrorCode::NotMobile,
+        };
+        Some(code)
+    }
+}
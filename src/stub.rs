@@ -0,0 +1,191 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::Method;
+use serde_json::Value;
+
+/// Canned responses for clients running without credentials or network access, e.g. in
+/// CI for downstream crates.
+///
+/// A request is answered with the first response whose method matches and whose path
+/// suffix matches the request path, or with the default response (`{}` unless set).
+#[derive(Clone, Debug)]
+pub struct StubResponses {
+    responses: Vec<(Method, String, Value)>,
+    default: Value,
+    latency: Option<LatencyProfile>,
+    // Shared by clones so each request draws the next latency in the sequence
+    requests: Arc<AtomicU64>,
+}
+
+impl StubResponses {
+    pub fn new() -> Self {
+        Self {
+            responses: Vec::new(),
+            default: Value::Object(Default::default()),
+            latency: None,
+            requests: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Answers `method` requests whose path ends with `path_suffix` with `body`, e.g.
+    /// `respond(Method::POST, "/Messages.json", json!({"status": "queued"}))`.
+    pub fn respond(mut self, method: Method, path_suffix: &str, body: Value) -> Self {
+        self.responses.push((method, path_suffix.to_string(), body));
+        self
+    }
+
+    /// The response for requests no other canned response matches.
+    pub fn default_response(mut self, body: Value) -> Self {
+        self.default = body;
+        self
+    }
+
+    /// Delays every response according to `profile`, e.g. to exercise timeouts and
+    /// hedging against realistic Twilio behavior. Responses slower than the client's
+    /// `timeout` fail with [`ClientError::Timeout`](crate::error::ClientError::Timeout)
+    /// once the timeout has passed.
+    ///
+    /// The delays are Tokio sleeps, so tests can run them instantly with
+    /// `#[tokio::test(start_paused = true)]`.
+    pub fn latency(mut self, profile: LatencyProfile) -> Self {
+        self.latency = Some(profile);
+        self
+    }
+
+    /// How long to delay the next response, if at all.
+    pub(crate) fn next_latency(&self) -> Option<Duration> {
+        let profile = self.latency.as_ref()?;
+        Some(profile.sample(self.requests.fetch_add(1, Ordering::Relaxed)))
+    }
+
+    pub(crate) fn response_for(&self, method: &Method, path: &str) -> &Value {
+        self.responses
+            .iter()
+            .find(|(m, suffix, _)| m == method && path.ends_with(suffix.as_str()))
+            .map(|(_, _, body)| body)
+            .unwrap_or(&self.default)
+    }
+}
+
+impl Default for StubResponses {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Simulated response times for [`StubResponses::latency`].
+///
+/// Delays are pseudo-random but deterministic: the same profile and seed always
+/// produce the same sequence, so a test that passes once passes every time.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use twilio_client::stub::LatencyProfile;
+/// // Around 120ms, with one request in fifty taking an extra 3 seconds
+/// let profile = LatencyProfile::normal(Duration::from_millis(120), Duration::from_millis(30))
+///     .spikes(0.02, Duration::from_secs(3));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LatencyProfile {
+    mean: Duration,
+    std_dev: Duration,
+    spike_rate: f64,
+    spike: Duration,
+    seed: u64,
+}
+
+impl LatencyProfile {
+    /// Every response takes `latency`.
+    pub fn fixed(latency: Duration) -> Self {
+        Self::normal(latency, Duration::ZERO)
+    }
+
+    /// Response times normally distributed around `mean`, never below zero.
+    pub fn normal(mean: Duration, std_dev: Duration) -> Self {
+        Self {
+            mean,
+            std_dev,
+            spike_rate: 0.0,
+            spike: Duration::ZERO,
+            seed: 0,
+        }
+    }
+
+    /// Adds `extra` to a `rate` (0.0 to 1.0) of the responses.
+    pub fn spikes(mut self, rate: f64, extra: Duration) -> Self {
+        self.spike_rate = rate.clamp(0.0, 1.0);
+        self.spike = extra;
+        self
+    }
+
+    /// Picks another deterministic sequence. Defaults to 0.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The latency of request number `n`.
+    pub fn sample(&self, n: u64) -> Duration {
+        let mut latency = self.mean.as_secs_f64();
+        if !self.std_dev.is_zero() {
+            // Box-Muller transform; `1 - u` keeps the logarithm's argument above zero
+            let (u1, u2) = (self.uniform(n, 0), self.uniform(n, 1));
+            let z = (-2.0 * (1.0 - u1).ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+            latency += z * self.std_dev.as_secs_f64();
+        }
+        if self.uniform(n, 2) < self.spike_rate {
+            latency += self.spike.as_secs_f64();
+        }
+        Duration::from_secs_f64(latency.max(0.0))
+    }
+
+    // A uniform value in [0, 1) for draw `k` of request `n`
+    fn uniform(&self, n: u64, k: u64) -> f64 {
+        let mixed = splitmix64(self.seed ^ splitmix64(n.wrapping_mul(3).wrapping_add(k)));
+        (mixed >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::stub::LatencyProfile;
+
+    #[test]
+    fn normal_latency_is_deterministic_and_centered_on_the_mean() {
+        let profile = LatencyProfile::normal(Duration::from_millis(100), Duration::from_millis(20));
+
+        let samples: Vec<Duration> = (0..1000).map(|n| profile.sample(n)).collect();
+        let mean = samples.iter().sum::<Duration>() / 1000;
+
+        assert_eq!(
+            samples,
+            (0..1000).map(|n| profile.sample(n)).collect::<Vec<_>>()
+        );
+        assert!(mean > Duration::from_millis(97) && mean < Duration::from_millis(103));
+        assert_ne!(profile.seed(1).sample(0), samples[0]);
+    }
+
+    #[test]
+    fn spikes_hit_roughly_the_configured_rate() {
+        let profile =
+            LatencyProfile::fixed(Duration::from_millis(50)).spikes(0.1, Duration::from_secs(2));
+
+        let spikes = (0..1000)
+            .filter(|n| profile.sample(*n) > Duration::from_secs(1))
+            .count();
+
+        assert!((70..130).contains(&spikes), "{spikes} spikes");
+        assert!((0..1000).all(|n| profile.sample(n) >= Duration::from_millis(50)));
+    }
+}
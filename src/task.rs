@@ -0,0 +1,26 @@
+use std::future::Future;
+
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+
+/// Spawns `future` as a background task called `name`, e.g. `Twilio SMS: Health check`.
+///
+/// tokio-console shows the name with the `tokio-console` feature, when the app is built
+/// with `--cfg tokio_unstable`. Either way the task runs in a root span carrying the name, so its logs aren't
+/// attributed to whichever request happened to spawn it.
+pub(crate) fn spawn_named<F>(name: &'static str, future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let future = future.instrument(tracing::info_span!(parent: None, "Twilio task", name));
+
+    #[cfg(all(tokio_unstable, feature = "tokio-console"))]
+    return tokio::task::Builder::new()
+        .name(name)
+        .spawn(future)
+        .expect("spawning a task only fails outside a Tokio runtime");
+
+    #[cfg(not(all(tokio_unstable, feature = "tokio-console")))]
+    tokio::spawn(future)
+}
@@ -0,0 +1,172 @@
+//! Builds the TwiML documents webhooks answer Twilio with.
+//!
+//! ```
+//! use twilio_client::twiml::{Message, MessagingResponse};
+//!
+//! let reply = MessagingResponse::new()
+//!     .message(Message::new("Your order has shipped & is on its way"))
+//!     .to_string();
+//! assert_eq!(
+//!     reply,
+//!     r#"<?xml version="1.0" encoding="UTF-8"?><Response><Message>Your order has shipped &amp; is on its way</Message></Response>"#
+//! );
+//! ```
+
+use std::fmt;
+
+const DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+/// The reply to an incoming message webhook: the messages to send back, if any.
+///
+/// An empty response acknowledges the message without replying.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MessagingResponse {
+    messages: Vec<Message>,
+}
+
+impl MessagingResponse {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn message(mut self, message: Message) -> Self {
+        self.messages.push(message);
+        self
+    }
+}
+
+impl fmt::Display for MessagingResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{DECLARATION}<Response>")?;
+        for message in &self.messages {
+            write!(f, "{message}")?;
+        }
+        write!(f, "</Response>")
+    }
+}
+
+/// A `<Message>` verb. It goes back to the sender of the incoming message from the
+/// number it was sent to, unless `to` and `from` say otherwise.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Message {
+    body: String,
+    media: Vec<String>,
+    to: Option<String>,
+    from: Option<String>,
+    status_callback: Option<String>,
+}
+
+impl Message {
+    pub fn new(body: impl Into<String>) -> Self {
+        Self {
+            body: body.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Attaches the media at `url`. Twilio takes up to 10 per message.
+    pub fn media(mut self, url: impl Into<String>) -> Self {
+        self.media.push(url.into());
+        self
+    }
+
+    pub fn to(mut self, to: impl Into<String>) -> Self {
+        self.to = Some(to.into());
+        self
+    }
+
+    pub fn from(mut self, from: impl Into<String>) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+
+    /// Where Twilio posts the reply's status updates.
+    pub fn status_callback(mut self, url: impl Into<String>) -> Self {
+        self.status_callback = Some(url.into());
+        self
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<Message")?;
+        let attributes = [
+            ("to", &self.to),
+            ("from", &self.from),
+            ("statusCallback", &self.status_callback),
+        ];
+        for (name, value) in attributes {
+            if let Some(value) = value {
+                write!(f, r#" {name}="{}""#, escape_xml(value))?;
+            }
+        }
+        write!(f, ">")?;
+
+        // A bare body is shorthand for a <Body> noun, which media needs alongside it
+        if self.media.is_empty() {
+            write!(f, "{}", escape_xml(&self.body))?;
+        } else {
+            if !self.body.is_empty() {
+                write!(f, "<Body>{}</Body>", escape_xml(&self.body))?;
+            }
+            for url in &self.media {
+                write!(f, "<Media>{}</Media>", escape_xml(url))?;
+            }
+        }
+        write!(f, "</Message>")
+    }
+}
+
+/// Escapes `text` for use in XML content or attribute values.
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::twiml::{Message, MessagingResponse};
+
+    #[test]
+    fn empty_responses_send_no_reply() {
+        assert_eq!(
+            MessagingResponse::new().to_string(),
+            r#"<?xml version="1.0" encoding="UTF-8"?><Response></Response>"#
+        );
+    }
+
+    #[test]
+    fn media_goes_with_a_body_noun() {
+        let reply = MessagingResponse::new()
+            .message(
+                Message::new("Here's your <receipt>")
+                    .media("https://example.com/r.png?a=1&b=2")
+                    .to("+254700123456")
+                    .status_callback("https://hooks.example.com/status"),
+            )
+            .message(Message::new("").media("https://example.com/logo.png"))
+            .to_string();
+
+        assert_eq!(
+            reply,
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?><Response>"#,
+                r#"<Message to="+254700123456" statusCallback="https://hooks.example.com/status">"#,
+                "<Body>Here&apos;s your &lt;receipt&gt;</Body>",
+                "<Media>https://example.com/r.png?a=1&amp;b=2</Media></Message>",
+                "<Message><Media>https://example.com/logo.png</Media></Message>",
+                "</Response>",
+            )
+        );
+    }
+}
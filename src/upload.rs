@@ -0,0 +1,93 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use futures_util::stream;
+use reqwest::multipart::{Form, Part};
+
+use crate::error::ClientError;
+use crate::Params;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Called with `(bytes_sent, total_bytes)` as an upload's body is streamed.
+pub type UploadProgress = Arc<dyn Fn(u64, u64) + Send + Sync>;
+
+/// A file sent as `multipart/form-data`, e.g. a regulatory supporting document.
+#[derive(Clone)]
+pub struct Upload {
+    field_name: String,
+    file_name: String,
+    content_type: String,
+    bytes: Vec<u8>,
+    fields: Params,
+    progress: Option<UploadProgress>,
+}
+
+impl Upload {
+    /// A file sent in the `field_name` part of the form.
+    pub fn new(
+        field_name: impl Into<String>,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        bytes: Vec<u8>,
+    ) -> Self {
+        Self {
+            field_name: field_name.into(),
+            file_name: file_name.into(),
+            content_type: content_type.into(),
+            bytes,
+            fields: Params::new(),
+            progress: None,
+        }
+    }
+
+    /// Plain text fields sent alongside the file.
+    pub fn fields(mut self, fields: Params) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    pub fn progress(mut self, progress: UploadProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    pub(crate) fn into_form(self) -> Result<Form, ClientError> {
+        let total = self.bytes.len() as u64;
+        let sent = Arc::new(AtomicU64::new(0));
+        let progress = self.progress;
+
+        let chunks: Vec<Vec<u8>> = self.bytes.chunks(CHUNK_SIZE).map(<[u8]>::to_vec).collect();
+        let body = stream::iter(chunks.into_iter().map(move |chunk| {
+            let sent = sent.fetch_add(chunk.len() as u64, Ordering::Relaxed) + chunk.len() as u64;
+            if let Some(progress) = &progress {
+                progress(sent, total);
+            }
+            Ok::<_, std::io::Error>(chunk)
+        }));
+
+        let part = Part::stream_with_length(reqwest::Body::wrap_stream(body), total)
+            .file_name(self.file_name)
+            .mime_str(&self.content_type)
+            .map_err(|e| ClientError::Configuration(format!("invalid content type: {e}")))?;
+
+        let mut form = Form::new();
+        for (key, value) in self.fields.iter() {
+            form = form.text(key.to_string(), value.to_string());
+        }
+        Ok(form.part(self.field_name, part))
+    }
+}
+
+impl fmt::Debug for Upload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Upload")
+            .field("field_name", &self.field_name)
+            .field("file_name", &self.file_name)
+            .field("content_type", &self.content_type)
+            .field("len", &self.bytes.len())
+            .field("fields", &self.fields)
+            .finish()
+    }
+}
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+use crate::verify::Client;
+use crate::{AttemptFilter, Page, Phone, TwilioDateTime};
+
+/// Whether a verification attempt was followed by a successful check.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ConversionStatus {
+    Converted,
+    Unconverted,
+}
+
+/// One code delivery attempt, from the Verify Attempts API.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VerificationAttempt {
+    pub sid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_sid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_sid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_created: Option<TwilioDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_updated: Option<TwilioDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversion_status: Option<ConversionStatus>,
+    /// The delivery channel, e.g. `sms` or `call`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// Carrier details and the delivery status of the message or call.
+    #[serde(default)]
+    pub channel_data: serde_json::Value,
+}
+
+impl Client {
+    /// Lists the first page of delivery attempts matching `filter`, across all services
+    /// on the account unless the filter names one.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: List attempts",
+        skip(self, filter)
+    )]
+    pub async fn list_attempts(
+        &self,
+        filter: &AttemptFilter,
+    ) -> Result<Page<VerificationAttempt>, ClientError> {
+        self.transport
+            .request(Method::GET, "/v2/Attempts", &filter.to_params())
+            .await
+    }
+
+    /// Every attempt to deliver a code to `to` through this client's service since
+    /// `since`, converted or not. Answers "did this user actually receive a code today"
+    /// without the Twilio console.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Verification history",
+        skip(self, to)
+    )]
+    pub async fn verification_history(
+        &self,
+        to: &Phone,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<VerificationAttempt>, ClientError> {
+        let filter = AttemptFilter::new()
+            .to(to)
+            .service_sid(self.service_sid.expose_secret())
+            .created_after(since);
+
+        let mut page = self.list_attempts(&filter).await?;
+        let mut attempts = std::mem::take(&mut page.items);
+        while let Some(uri) = page.next_page_uri.take() {
+            page = self.transport.fetch_page(&uri).await?;
+            attempts.append(&mut page.items);
+        }
+        Ok(attempts)
+    }
+}
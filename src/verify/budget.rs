@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::clock::{Clock, SystemClock};
+use crate::error::VerifyError;
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Counts verifications per tenant and window for a [`VerificationBudget`], e.g. in
+/// Redis so every instance of a service shares one budget.
+#[async_trait]
+pub trait BudgetCounter: Debug + Send + Sync {
+    /// Adds one to the counter `key` and returns its new value. The counter is no
+    /// longer needed after `ttl`, e.g. a Redis `INCR` followed by `EXPIRE`.
+    async fn increment(
+        &self,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<u64, Box<dyn Error + Send + Sync>>;
+}
+
+/// A [`BudgetCounter`] local to the process.
+#[derive(Debug)]
+pub struct InMemoryBudgetCounter {
+    clock: Arc<dyn Clock>,
+    // Each counter with the time it expires
+    counters: Mutex<HashMap<String, (u64, DateTime<Utc>)>>,
+}
+
+impl InMemoryBudgetCounter {
+    pub fn new() -> Self {
+        Self {
+            clock: Arc::new(SystemClock),
+            counters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+impl Default for InMemoryBudgetCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl BudgetCounter for InMemoryBudgetCounter {
+    async fn increment(
+        &self,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<u64, Box<dyn Error + Send + Sync>> {
+        let now = self.clock.now();
+        let mut counters = self.counters.lock().unwrap();
+        counters.retain(|_, (_, expires_at)| *expires_at > now);
+        let (count, _) = counters
+            .entry(key.to_string())
+            .or_insert((0, now + chrono::Duration::from_std(ttl)?));
+        *count += 1;
+        Ok(*count)
+    }
+}
+
+/// Caps how many codes are requested per hour and per day, for the whole account or
+/// per tenant, so an SMS pumping attack is contained before Twilio's Fraud Guard
+/// reacts. Requests over a cap fail with [`VerifyError::BudgetExceeded`] without
+/// reaching Twilio.
+///
+/// Windows are fixed, starting on the hour and at midnight UTC, and requests over a cap
+/// still count against it. The tenant comes from
+/// [`EndUserContext::tenant`](crate::verify::EndUserContext::tenant); requests without
+/// one share a budget. If the counter store fails the request is let through, so an
+/// outage of the store doesn't stop sign-ins.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use twilio_client::verify::{InMemoryBudgetCounter, VerificationBudget};
+/// let budget = VerificationBudget::new(Arc::new(InMemoryBudgetCounter::new()))
+///     .per_hour(500)
+///     .per_day(5_000);
+/// ```
+#[derive(Debug)]
+pub struct VerificationBudget {
+    counter: Arc<dyn BudgetCounter>,
+    per_hour: Option<u64>,
+    per_day: Option<u64>,
+    clock: Arc<dyn Clock>,
+}
+
+impl VerificationBudget {
+    pub fn new(counter: Arc<dyn BudgetCounter>) -> Self {
+        Self {
+            counter,
+            per_hour: None,
+            per_day: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    pub fn per_hour(mut self, max: u64) -> Self {
+        self.per_hour = Some(max);
+        self
+    }
+
+    pub fn per_day(mut self, max: u64) -> Self {
+        self.per_day = Some(max);
+        self
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Counts a request for `tenant`, failing if it goes over a cap.
+    pub(crate) async fn admit(&self, tenant: Option<&str>) -> Result<(), VerifyError> {
+        let windows = [(self.per_hour, HOUR), (self.per_day, DAY)];
+        for (limit, window) in windows {
+            let Some(limit) = limit else {
+                continue;
+            };
+            self.admit_in(tenant, limit, window).await?;
+        }
+        Ok(())
+    }
+
+    async fn admit_in(
+        &self,
+        tenant: Option<&str>,
+        limit: u64,
+        window: Duration,
+    ) -> Result<(), VerifyError> {
+        let now = self.clock.now().timestamp().max(0) as u64;
+        let window_secs = window.as_secs();
+        let index = now / window_secs;
+        let retry_in = Duration::from_secs((index + 1) * window_secs - now);
+        let key = match tenant {
+            Some(tenant) => format!("tenant:{tenant}:{window_secs}:{index}"),
+            None => format!("account:{window_secs}:{index}"),
+        };
+
+        let count = match self.counter.increment(&key, retry_in).await {
+            Ok(count) => count,
+            Err(err) => {
+                tracing::warn!("Twilio Verify: budget counter failed, allowing request: {err}");
+                return Ok(());
+            }
+        };
+        if count > limit {
+            return Err(VerifyError::BudgetExceeded {
+                tenant: tenant.map(str::to_string),
+                limit,
+                window,
+                retry_in,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use chrono::TimeZone;
+    use claim::assert_ok;
+
+    use crate::clock::MockClock;
+    use crate::error::VerifyError;
+    use crate::verify::{InMemoryBudgetCounter, VerificationBudget};
+
+    fn budget(clock: &MockClock) -> VerificationBudget {
+        let clock = Arc::new(clock.clone());
+        let counter = InMemoryBudgetCounter::new().clock(clock.clone());
+        VerificationBudget::new(Arc::new(counter))
+            .per_hour(2)
+            .per_day(3)
+            .clock(clock)
+    }
+
+    #[tokio::test]
+    async fn requests_over_the_hourly_cap_wait_for_the_next_hour() {
+        let clock = MockClock::new(chrono::Utc.with_ymd_and_hms(2025, 3, 1, 9, 45, 0).unwrap());
+        let budget = budget(&clock);
+
+        assert_ok!(budget.admit(None).await);
+        assert_ok!(budget.admit(None).await);
+        assert_eq!(
+            budget.admit(None).await,
+            Err(VerifyError::BudgetExceeded {
+                tenant: None,
+                limit: 2,
+                window: Duration::from_secs(3600),
+                retry_in: Duration::from_secs(15 * 60),
+            })
+        );
+
+        clock.advance(chrono::Duration::minutes(15));
+        assert_ok!(budget.admit(None).await);
+        assert!(matches!(
+            budget.admit(None).await,
+            Err(VerifyError::BudgetExceeded { limit: 3, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn tenants_have_separate_budgets() {
+        let clock = MockClock::default();
+        let budget = budget(&clock);
+
+        assert_ok!(budget.admit(Some("acme")).await);
+        assert_ok!(budget.admit(Some("acme")).await);
+        assert!(budget.admit(Some("acme")).await.is_err());
+
+        assert_ok!(budget.admit(Some("globex")).await);
+        assert_ok!(budget.admit(None).await);
+    }
+}
@@ -0,0 +1,78 @@
+/// Code points of the `0` digit in the decimal scripts users commonly type codes in.
+const DIGIT_ZEROS: [u32; 6] = [
+    0x0660, // Arabic-Indic
+    0x06F0, // Extended Arabic-Indic (Persian, Urdu)
+    0x0966, // Devanagari
+    0x09E6, // Bengali
+    0x0E50, // Thai
+    0xFF10, // Fullwidth
+];
+
+/// Normalizes a user-typed verification code.
+///
+/// Whitespace and dashes are dropped and non-ASCII decimal digits are converted to
+/// their ASCII equivalent, so `"１２３ ４５６"` and `"123-456"` both become `"123456"`.
+/// Any other character is kept as is.
+pub fn normalize_code(input: &str) -> String {
+    input
+        .chars()
+        .filter(|c| !c.is_whitespace() && !is_dash(*c))
+        .map(to_ascii_digit)
+        .collect()
+}
+
+fn is_dash(c: char) -> bool {
+    matches!(c, '-' | '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{FF0D}')
+}
+
+fn to_ascii_digit(c: char) -> char {
+    let code = c as u32;
+    DIGIT_ZEROS
+        .iter()
+        .find(|zero| (**zero..**zero + 10).contains(&code))
+        .and_then(|zero| char::from_digit(code - zero, 10))
+        .unwrap_or(c)
+}
+
+/// Checks that `code` looks like something Twilio could accept for a service sending
+/// `length`-digit codes.
+pub(crate) fn validate_code(code: &str, length: usize) -> Result<(), String> {
+    if !code.chars().all(|c| c.is_ascii_digit()) {
+        return Err("code must only contain digits".to_string());
+    }
+    if code.len() != length {
+        return Err(format!(
+            "code must be {length} digits long, got {}",
+            code.len()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use claim::{assert_err, assert_ok};
+
+    use crate::verify::code::{normalize_code, validate_code};
+
+    #[test]
+    fn whitespace_and_dashes_are_stripped() {
+        assert_eq!(normalize_code(" 123-456\t"), "123456");
+    }
+
+    #[test]
+    fn unicode_digits_are_converted() {
+        assert_eq!(normalize_code("１２３٤٥٦"), "123456");
+    }
+
+    #[test]
+    fn code_with_wrong_length_is_rejected() {
+        assert_err!(validate_code("12345", 6));
+        assert_ok!(validate_code("123456", 6));
+    }
+
+    #[test]
+    fn code_with_letters_is_rejected() {
+        assert_err!(validate_code("12a456", 6));
+    }
+}
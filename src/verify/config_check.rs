@@ -0,0 +1,40 @@
+use reqwest::StatusCode;
+use secrecy::ExposeSecret;
+
+use crate::error::ClientError;
+use crate::health::{credentials_finding, ConfigReport};
+use crate::verify::Client;
+
+impl Client {
+    /// Checks that the credentials are accepted and the service SID exists on the
+    /// account. Meant for startup or a deploy step, so a bad service SID shows up
+    /// before the first user asks for a code.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Validate configuration",
+        skip(self)
+    )]
+    pub async fn validate_configuration(&self) -> ConfigReport {
+        let mut report = ConfigReport::default();
+
+        let service_sid = self.service_sid.expose_secret();
+        let url = format!("/v2/Services/{service_sid}");
+        match self.transport.probe(&url).await {
+            Ok(()) => {}
+            Err(ClientError::ServerResponse { status_code, .. })
+                if status_code == StatusCode::NOT_FOUND =>
+            {
+                report.error(format!(
+                    "Verify service {service_sid} does not exist on this account; check \
+                     service_sid and the region"
+                ));
+            }
+            Err(err) => report.error(credentials_finding(&err)),
+        }
+        if self.transport.dry_run {
+            report.warning("dry_run is on; no codes will be sent");
+        }
+
+        report
+    }
+}
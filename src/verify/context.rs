@@ -0,0 +1,73 @@
+use std::net::IpAddr;
+
+use crate::verify::LocaleFallback;
+use crate::Params;
+
+/// Who is asking for a code, forwarded to Twilio to improve Fraud Guard decisions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EndUserContext {
+    ip: Option<IpAddr>,
+    user_agent: Option<String>,
+    locale: Option<String>,
+    tenant: Option<String>,
+}
+
+impl EndUserContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The IP address of the end user's device, sent as `DeviceIp`.
+    pub fn ip(mut self, ip: IpAddr) -> Self {
+        self.ip = Some(ip);
+        self
+    }
+
+    /// The end user's user agent. Verify has no dedicated parameter for it, so it is
+    /// recorded in the verification's `Tags` metadata.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// The locale the end user wants the code message in, e.g. `sw-KE`. It goes
+    /// through the client's `locale_fallback`, if any, before being sent as `Locale`.
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// The tenant the end user belongs to, whose
+    /// [`VerificationBudget`](crate::verify::VerificationBudget) the request counts
+    /// against. Not sent to Twilio.
+    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    pub(crate) fn tenant_id(&self) -> Option<&str> {
+        self.tenant.as_deref()
+    }
+
+    pub(crate) fn apply(&self, body: &mut Params, fallback: Option<&LocaleFallback>) {
+        if let Some(ip) = self.ip {
+            body.insert("DeviceIp", ip.to_string());
+        }
+        if let Some(user_agent) = &self.user_agent {
+            let tags = serde_json::json!({ "user_agent": user_agent });
+            body.insert("Tags", tags.to_string());
+        }
+        if let Some(requested) = &self.locale {
+            let locale = match fallback {
+                Some(fallback) => fallback.resolve(requested),
+                None => Some(requested.clone()),
+            };
+            if locale.as_deref() != Some(requested.as_str()) {
+                tracing::debug!("Twilio Verify: locale {requested} degraded to {locale:?}");
+            }
+            if let Some(locale) = locale {
+                body.insert("Locale", locale);
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use reqwest::{Method, StatusCode};
+use secrecy::ExposeSecret;
+
+use crate::error::ClientError;
+use crate::verify::Client;
+use crate::Params;
+
+impl Client {
+    /// Deletes the Verify entity `identity` along with its factors (TOTP, push).
+    /// Returns `false` if the service has no such entity.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Delete entity",
+        skip(self, identity)
+    )]
+    pub async fn delete_entity(&self, identity: &str) -> Result<bool, ClientError> {
+        let service_sid = self.service_sid.expose_secret();
+        let url = format!("/v2/Services/{service_sid}/Entities/{identity}");
+        let resp: Result<(), ClientError> = self
+            .transport
+            .request(Method::DELETE, &url, &Params::new())
+            .await;
+        match resp {
+            Ok(()) => Ok(true),
+            Err(ClientError::ServerResponse { status_code, .. })
+                if status_code == StatusCode::NOT_FOUND =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
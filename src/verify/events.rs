@@ -0,0 +1,35 @@
+use std::fmt::Debug;
+
+/// A step in the OTP funnel.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum VerifyEventKind {
+    /// A verification code was requested for a phone.
+    RequestIssued,
+    /// A code was submitted for checking, regardless of the outcome.
+    CodeChecked,
+    /// The submitted code was correct.
+    Approved,
+    /// The check did not approve the verification, or the request failed.
+    Failed,
+    /// The verification expired or no longer exists.
+    Expired,
+}
+
+/// An analytics event emitted by [`Client`](super::Client).
+///
+/// Phones are identified by their keyed pseudonym when the client has a
+/// `pseudonymizer`, and left out otherwise: a plain hash of a phone number can be
+/// reversed by hashing every number, so sinks never see anything that identifies it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifyEvent {
+    pub kind: VerifyEventKind,
+    pub phone_hash: Option<String>,
+}
+
+/// Receives verification funnel events, e.g. to forward them to product analytics.
+///
+/// `record` is called inline on the request path, so implementations should hand off
+/// any slow work.
+pub trait VerifyEventSink: Debug + Send + Sync {
+    fn record(&self, event: VerifyEvent);
+}
@@ -0,0 +1,92 @@
+use std::future::Future;
+use std::time::Duration;
+
+use secrecy::SecretString;
+
+use crate::error::ClientError;
+use crate::verify::{Client, VerifyOutcome};
+use crate::Phone;
+
+/// Pacing for [`Client::request_and_wait_for_check`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OtpFlowOptions {
+    /// How long the whole flow may take before it gives up as expired.
+    pub deadline: Duration,
+    /// How long to wait for a code before sending another one.
+    pub resend_interval: Duration,
+    /// The most codes re-sent after the first.
+    pub max_resends: u32,
+}
+
+impl Default for OtpFlowOptions {
+    fn default() -> Self {
+        Self {
+            deadline: Duration::from_secs(10 * 60),
+            resend_interval: Duration::from_secs(60),
+            max_resends: 3,
+        }
+    }
+}
+
+impl Client {
+    /// Runs a whole OTP flow: sends a code, then checks each code `next_code` yields
+    /// until one is approved, re-sending on the configured interval and when a
+    /// verification expires.
+    ///
+    /// `next_code` returning `None` means the user gave up, reported as
+    /// [`VerifyOutcome::Canceled`]. Running out of time is reported as
+    /// [`VerifyOutcome::Expired`]. A client [`ResendPolicy`](crate::verify::ResendPolicy)
+    /// still applies, so keep `resend_interval` at or above its intervals.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Request and wait for check",
+        skip(self, to, next_code)
+    )]
+    pub async fn request_and_wait_for_check<F, Fut>(
+        &self,
+        to: &Phone,
+        mut next_code: F,
+        options: OtpFlowOptions,
+    ) -> Result<VerifyOutcome, ClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Option<SecretString>>,
+    {
+        let deadline = tokio::time::Instant::now() + options.deadline;
+        let mut resends = 0;
+
+        self.request(to).await?;
+        let mut resend_at = tokio::time::Instant::now() + options.resend_interval;
+        // Kept across loop iterations so a resend doesn't drop a code being typed.
+        let mut pending = Box::pin(next_code());
+
+        loop {
+            let can_resend = resends < options.max_resends;
+            tokio::select! {
+                code = &mut pending => {
+                    let Some(code) = code else {
+                        return Ok(VerifyOutcome::Canceled);
+                    };
+                    match self.check(to, code).await? {
+                        VerifyOutcome::Incorrect => {}
+                        VerifyOutcome::Expired if can_resend => {
+                            resends += 1;
+                            self.request(to).await?;
+                            resend_at = tokio::time::Instant::now() + options.resend_interval;
+                        }
+                        outcome => return Ok(outcome),
+                    }
+                    pending = Box::pin(next_code());
+                }
+                _ = tokio::time::sleep_until(resend_at), if can_resend => {
+                    resends += 1;
+                    self.request(to).await?;
+                    resend_at = tokio::time::Instant::now() + options.resend_interval;
+                }
+                _ = tokio::time::sleep_until(deadline) => {
+                    return Ok(VerifyOutcome::Expired);
+                }
+            }
+        }
+    }
+}
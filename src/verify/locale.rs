@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+// The locales Verify has SMS templates for. Twilio sends anything else in English.
+const SUPPORTED_LOCALES: [&str; 40] = [
+    "af", "ar", "ca", "cs", "da", "de", "el", "en", "en-GB", "es", "es-419", "et", "fi", "fr",
+    "fr-CA", "he", "hi", "hr", "hu", "id", "it", "ja", "ko", "lt", "ms", "nb", "nl", "pl", "pt",
+    "pt-BR", "ro", "ru", "sv", "th", "tl", "tr", "uk", "vi", "zh-CN", "zh-HK",
+];
+
+/// Maps the locale an end user asked for onto one Verify supports, so unsupported
+/// locales degrade predictably instead of Twilio silently falling back to English.
+///
+/// A locale without a registered chain falls back to its language (`fr-BE` → `fr`) and
+/// then to the default locale.
+///
+/// ```
+/// # use twilio_client::verify::LocaleFallback;
+/// let fallback = LocaleFallback::new()
+///     .supported(["sw", "en"])
+///     .chain("sw-KE", ["sw", "en"]);
+/// assert_eq!(fallback.resolve("sw-KE").as_deref(), Some("sw"));
+/// assert_eq!(fallback.resolve("de-AT").as_deref(), Some("en"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LocaleFallback {
+    supported: Vec<String>,
+    chains: HashMap<String, Vec<String>>,
+    default: Option<String>,
+}
+
+impl Default for LocaleFallback {
+    fn default() -> Self {
+        Self {
+            supported: SUPPORTED_LOCALES.iter().map(|l| l.to_string()).collect(),
+            chains: HashMap::new(),
+            default: Some("en".to_string()),
+        }
+    }
+}
+
+impl LocaleFallback {
+    /// A fallback over the locales Verify supports, defaulting to `en`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the supported locales, e.g. with the ones the service's custom
+    /// templates are translated to.
+    pub fn supported<I, S>(mut self, locales: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.supported = locales.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The locales to try, in order, when `locale` isn't supported.
+    pub fn chain<I, S>(mut self, locale: impl Into<String>, fallbacks: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let fallbacks = fallbacks.into_iter().map(Into::into).collect();
+        self.chains.insert(locale.into().to_lowercase(), fallbacks);
+        self
+    }
+
+    /// The locale used when nothing else matches. `None` leaves the choice to Twilio.
+    pub fn default_locale(mut self, locale: Option<String>) -> Self {
+        self.default = locale;
+        self
+    }
+
+    /// The first supported locale in the fallback chain of `locale`, in the casing it
+    /// was registered as supported.
+    pub fn resolve(&self, locale: &str) -> Option<String> {
+        let mut candidates = vec![locale.to_string()];
+        match self.chains.get(&locale.to_lowercase()) {
+            Some(chain) => candidates.extend(chain.iter().cloned()),
+            None => {
+                if let Some((language, _)) = locale.split_once(['-', '_']) {
+                    candidates.push(language.to_string());
+                }
+            }
+        }
+        candidates.extend(self.default.clone());
+
+        candidates
+            .iter()
+            .find_map(|candidate| self.find_supported(candidate))
+            .map(str::to_string)
+    }
+
+    fn find_supported(&self, locale: &str) -> Option<&str> {
+        let locale = locale.replace('_', "-");
+        self.supported
+            .iter()
+            .find(|supported| supported.eq_ignore_ascii_case(&locale))
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::verify::LocaleFallback;
+
+    #[test]
+    fn registered_chains_are_followed_in_order() {
+        let fallback = LocaleFallback::new()
+            .supported(["sw", "en"])
+            .chain("sw-KE", ["sw-TZ", "sw", "en"]);
+
+        assert_eq!(fallback.resolve("sw-ke").as_deref(), Some("sw"));
+    }
+
+    #[test]
+    fn unregistered_locales_fall_back_to_their_language() {
+        let fallback = LocaleFallback::new();
+
+        assert_eq!(fallback.resolve("pt_BR").as_deref(), Some("pt-BR"));
+        assert_eq!(fallback.resolve("fr-BE").as_deref(), Some("fr"));
+        assert_eq!(fallback.resolve("sw-KE").as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn without_a_default_unsupported_locales_resolve_to_none() {
+        let fallback = LocaleFallback::new().default_locale(None);
+
+        assert_eq!(fallback.resolve("sw-KE"), None);
+    }
+}
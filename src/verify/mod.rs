@@ -1,65 +1,214 @@
+mod attempts;
+mod budget;
+mod code;
+mod config_check;
+mod context;
+mod entities;
+mod events;
+mod flow;
+mod locale;
+mod outcome;
+mod region;
+mod resend;
+mod session;
+pub use attempts::*;
+pub use budget::*;
+pub use code::normalize_code;
+pub use context::*;
+pub use events::*;
+pub use flow::*;
+pub use locale::*;
+pub use outcome::*;
+pub use region::Region;
+pub use resend::*;
+pub use session::*;
+
+use std::net::IpAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::error::ClientError;
-use crate::make_request::make_request;
-use crate::models::Phone;
-use crate::sms::DEFAULT_TIMEOUT;
-use reqwest::Url;
+use crate::cache::ResponseCache;
+use crate::clock::{Clock, SystemClock};
+use crate::error::{ClientError, VerifyError};
+use crate::health::HealthCheck;
+use crate::limiter::{ConcurrencyLimiter, Priority};
+use crate::make_request::{Transport, DEFAULT_MAX_RESPONSE_SIZE, DEFAULT_TIMEOUT, STUB_BASE_URL};
+use crate::models::{Params, Phone, Pseudonymizer, Raw, TwilioDateTime};
+use crate::retry::{Idempotency, RetryPolicy};
+use crate::sampling::BodySampler;
+use crate::stub::StubResponses;
+use region::Failover;
+use reqwest::{Method, Url};
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TwilioRequestResponse {
     pub status: Option<Status>,
     pub send_code_attempts: Option<Vec<SendCodeAttempt>>,
     pub to: Option<String>,
     pub valid: Option<bool>,
-    pub date_created: Option<String>,
-    pub date_updated: Option<String>,
+    pub date_created: Option<TwilioDateTime>,
+    pub date_updated: Option<TwilioDateTime>,
+    /// Set on responses synthesized by a [`ClientBuilder::dry_run`] client.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    /// The name of the [`Region`] that answered.
+    #[serde(skip)]
+    pub served_by: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SendCodeAttempt {
     pub attempt_sid: String,
     pub channel: Channel,
-    pub time: String,
+    pub time: TwilioDateTime,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TwilioVerifyResponse {
     pub status: Status,
     pub payee: Option<String>,
-    pub date_updated: String,
+    pub date_updated: TwilioDateTime,
     pub account_sid: String,
     pub to: String,
     pub amount: Option<f32>,
     pub valid: bool,
     pub sid: String,
-    pub date_created: String,
+    pub date_created: TwilioDateTime,
     pub service_sid: String,
     pub channel: Channel,
+    /// Set on responses synthesized by a [`ClientBuilder::dry_run`] client.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    /// The name of the [`Region`] that answered.
+    #[serde(skip)]
+    pub served_by: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Channel {
     Sms,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+/// The status of a verification
+///
+/// Statuses Twilio adds later parse as [`Status::Unknown`], which keeps the status as
+/// sent, rather than failing the response.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
 pub enum Status {
     Pending,
     Approved,
+    Canceled,
+    MaxAttemptsReached,
+    Deleted,
+    Failed,
+    Expired,
+    /// A status this version of the crate doesn't know, as Twilio sent it.
+    Unknown(String),
+}
+
+impl Status {
+    /// The status as Twilio spells it, e.g. `"max_attempts_reached"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Status::Pending => "pending",
+            Status::Approved => "approved",
+            Status::Canceled => "canceled",
+            Status::MaxAttemptsReached => "max_attempts_reached",
+            Status::Deleted => "deleted",
+            Status::Failed => "failed",
+            Status::Expired => "expired",
+            Status::Unknown(status) => status,
+        }
+    }
+}
+
+impl From<String> for Status {
+    fn from(status: String) -> Self {
+        match status.as_str() {
+            "pending" => Status::Pending,
+            "approved" => Status::Approved,
+            "canceled" => Status::Canceled,
+            "max_attempts_reached" => Status::MaxAttemptsReached,
+            "deleted" => Status::Deleted,
+            "failed" => Status::Failed,
+            "expired" => Status::Expired,
+            _ => Status::Unknown(status),
+        }
+    }
+}
+
+impl From<Status> for String {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Unknown(status) => status,
+            status => status.as_str().to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Status {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Status".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "anyOf": [
+                {
+                    "enum": [
+                        "pending", "approved", "canceled", "max_attempts_reached", "deleted",
+                        "failed", "expired"
+                    ]
+                },
+                {
+                    "type": "string",
+                    "description": "A status added by Twilio after this version of the crate"
+                }
+            ]
+        })
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct ClientBuilder {
     base_url: Option<Url>,
+    region: Option<Region>,
+    failover_region: Option<Region>,
     service_sid: Option<SecretString>,
     account_sid: Option<SecretString>,
     auth_token: Option<SecretString>,
     timeout: Option<Duration>,
+    max_response_size: Option<usize>,
+    stub: Option<StubResponses>,
+    local_address: Option<IpAddr>,
+    cache: Option<Arc<ResponseCache>>,
+    sampler: Option<Arc<BodySampler>>,
+    hedge_after: Option<Duration>,
+    limiter: Option<ConcurrencyLimiter>,
+    retry: Option<RetryPolicy>,
+    priority: Priority,
+    dry_run: bool,
+    dry_run_code: Option<SecretString>,
+    read_only: bool,
+    event_sink: Option<Arc<dyn VerifyEventSink>>,
+    normalize_codes: bool,
+    code_length: Option<usize>,
+    locale_fallback: Option<LocaleFallback>,
+    pseudonymizer: Option<Pseudonymizer>,
+    resend_policy: Option<Arc<ResendPolicy>>,
+    budget: Option<Arc<VerificationBudget>>,
+    sessions: Option<Arc<SessionPolicy>>,
+    clock: Option<Arc<dyn Clock>>,
 }
 
 impl ClientBuilder {
@@ -72,6 +221,22 @@ impl ClientBuilder {
         self
     }
 
+    /// Send requests to `region` instead of `base_url`.
+    pub fn region(mut self, region: Region) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Resend requests to `region` when the primary region can't be reached or
+    /// answers with an outage page. Code requests and checks, which the primary may
+    /// have carried out before timing out, fail over only when the primary couldn't
+    /// be connected to. A code must be checked in the region that sent it, which
+    /// [`TwilioRequestResponse::served_by`] names.
+    pub fn failover_region(mut self, region: Region) -> Self {
+        self.failover_region = Some(region);
+        self
+    }
+
     pub fn account_sid(mut self, account_sid: SecretString) -> Self {
         self.account_sid = Some(account_sid);
         self
@@ -92,10 +257,174 @@ impl ClientBuilder {
         self
     }
 
-    pub fn build(self) -> Result<Client, ClientError> {
-        let base_url = self.base_url.ok_or_else(|| {
-            ClientError::Configuration("Twilio verify base_url is required".to_string())
-        })?;
+    /// Emit [`VerifyEvent`]s for every request and check to `sink`.
+    pub fn event_sink(mut self, sink: Arc<dyn VerifyEventSink>) -> Self {
+        self.event_sink = Some(sink);
+        self
+    }
+
+    /// Identify phones in [`VerifyEvent`]s by their keyed pseudonym. Without one,
+    /// events leave the phone out.
+    pub fn pseudonymizer(mut self, pseudonymizer: Pseudonymizer) -> Self {
+        self.pseudonymizer = Some(pseudonymizer);
+        self
+    }
+
+    /// Pace the codes sent to each phone according to `policy`.
+    pub fn resend_policy(mut self, policy: ResendPolicy) -> Self {
+        self.resend_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Cap the codes requested per hour and per day according to `budget`.
+    pub fn budget(mut self, budget: VerificationBudget) -> Self {
+        self.budget = Some(Arc::new(budget));
+        self
+    }
+
+    /// Where [`Session`]s are kept and what they allow. Defaults to
+    /// [`SessionPolicy::default`], which keeps them in memory.
+    pub fn sessions(mut self, policy: SessionPolicy) -> Self {
+        self.sessions = Some(Arc::new(policy));
+        self
+    }
+
+    /// The largest response body, in bytes, the client will buffer before giving up.
+    pub fn max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
+    /// Run submitted codes through [`normalize_code`] before checking them.
+    pub fn normalize_codes(mut self, normalize: bool) -> Self {
+        self.normalize_codes = normalize;
+        self
+    }
+
+    /// The code length configured on the Verify service. Codes of any other length are
+    /// rejected locally instead of using up a check attempt.
+    pub fn code_length(mut self, length: usize) -> Self {
+        self.code_length = Some(length);
+        self
+    }
+
+    /// Resolve the locale of every [`EndUserContext`] through `fallback` so locales
+    /// Verify doesn't support degrade to a chosen one.
+    pub fn locale_fallback(mut self, fallback: LocaleFallback) -> Self {
+        self.locale_fallback = Some(fallback);
+        self
+    }
+
+    /// Bind outgoing connections to `address`, e.g. to leave through the egress IP that
+    /// is allowed by the account's IP access control list.
+    pub fn local_address(mut self, address: IpAddr) -> Self {
+        self.local_address = Some(address);
+        self
+    }
+
+    /// Cache `GET` responses (message, service, template fetches) in `cache`.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Record a sample of redacted request/response pairs with `sampler`.
+    pub fn sampler(mut self, sampler: BodySampler) -> Self {
+        self.sampler = Some(Arc::new(sampler));
+        self
+    }
+
+    /// Send a second copy of a read, e.g. a `GET` through [`Client::raw_request`], that
+    /// has no response after `delay`, and use whichever copy succeeds first, to cut
+    /// tail latency when Twilio slows down. Code requests and checks are never hedged:
+    /// each copy of a check would count against its attempts.
+    pub fn hedge_after(mut self, delay: Duration) -> Self {
+        self.hedge_after = Some(delay);
+        self
+    }
+
+    /// Share `limiter` with other clients to cap in-flight requests across all of them.
+    pub fn concurrency_limiter(mut self, limiter: ConcurrencyLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// Allow only reads, e.g. fetches and lists, and fail every other call with
+    /// [`ClientError::ReadOnlyMode`] before it reaches Twilio. Lets disaster recovery
+    /// drills and game days run against production credentials without sending codes
+    /// to real users.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Validate, rate limit and log requests and checks as usual but skip the call to
+    /// Twilio, returning a synthesized response with `dry_run` set. No code is
+    /// approved in dry-run mode unless a [`ClientBuilder::dry_run_code`] is set.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// The one code a `dry_run` client accepts, so rehearsals can get through a sign-in
+    /// flow. Checks of it come back as [`VerifyOutcome::DryRunApproved`], never
+    /// [`VerifyOutcome::Approved`]; every other code is incorrect.
+    pub fn dry_run_code(mut self, code: SecretString) -> Self {
+        self.dry_run_code = Some(code);
+        self
+    }
+
+    /// The priority of this client's requests when a shared `concurrency_limiter` is
+    /// saturated. Defaults to [`Priority::Normal`].
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// The clock retries and hedged reads wait on, and dry-run checks are dated by.
+    /// Defaults to the system clock.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Retry transient failures according to `policy`. Off by default.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Build a client that never touches the network and answers every call from
+    /// `responses`. Credentials and the base url become optional.
+    pub fn stub(mut self, responses: StubResponses) -> Self {
+        self.stub = Some(responses);
+        self
+    }
+
+    pub fn build(mut self) -> Result<Client, ClientError> {
+        if self.stub.is_some() {
+            // Stub clients never send anything, so placeholders are enough
+            self.base_url = self.base_url.or_else(|| Url::parse(STUB_BASE_URL).ok());
+            self.account_sid = self
+                .account_sid
+                .or_else(|| Some(SecretString::from("stub")));
+            self.auth_token = self.auth_token.or_else(|| Some(SecretString::from("stub")));
+            self.service_sid = self
+                .service_sid
+                .or_else(|| Some(SecretString::from("stub")));
+        }
+        let base_url = self
+            .region
+            .as_ref()
+            .map(|region| region.base_url.clone())
+            .or(self.base_url)
+            .ok_or_else(|| {
+                ClientError::Configuration("Twilio verify base_url is required".to_string())
+            })?;
+        let region = match self.region {
+            Some(region) => region.name,
+            None => base_url.host_str().unwrap_or_default().to_string(),
+        };
         let account_sid = self.account_sid.ok_or_else(|| {
             ClientError::Configuration("Twilio verify account_sid is required".to_string())
         })?;
@@ -107,31 +436,76 @@ impl ClientBuilder {
         })?;
 
         let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        let max_response_size = self.max_response_size.unwrap_or(DEFAULT_MAX_RESPONSE_SIZE);
 
         let http_client = reqwest::Client::builder()
             .timeout(timeout)
+            .local_address(self.local_address)
             .build()
             .map_err(ClientError::Reqwest)?;
 
-        Ok(Client {
+        let transport = Transport {
             http_client,
             base_url,
-            service_sid,
             account_sid,
             auth_token,
             timeout,
+            max_response_size,
+            stub: self.stub.map(Arc::new),
+            local_address: self.local_address,
+            cache: self.cache,
+            limiter: self.limiter,
+            dry_run: self.dry_run,
+            span: None,
+            retry: self.retry,
+            priority: self.priority,
+            sampler: self.sampler,
+            hedge_after: self.hedge_after,
+            read_only: self.read_only,
+            service_name: "Twilio Verify",
+            clock: self.clock.unwrap_or_else(|| Arc::new(SystemClock)),
+        };
+        let failover = self.failover_region.map(|region| Failover {
+            transport: Transport {
+                base_url: region.base_url.clone(),
+                ..transport.clone()
+            },
+            region,
+        });
+
+        Ok(Client {
+            transport,
+            region,
+            failover,
+            service_sid,
+            event_sink: self.event_sink,
+            normalize_codes: self.normalize_codes,
+            code_length: self.code_length,
+            locale_fallback: self.locale_fallback,
+            pseudonymizer: self.pseudonymizer,
+            resend_policy: self.resend_policy,
+            budget: self.budget,
+            sessions: self.sessions.unwrap_or_default(),
+            dry_run_code: self.dry_run_code,
         })
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Client {
-    http_client: reqwest::Client,
-    base_url: Url,
-    account_sid: SecretString,
-    auth_token: SecretString,
+    transport: Transport,
+    region: String,
+    failover: Option<Failover>,
     service_sid: SecretString,
-    timeout: Duration,
+    event_sink: Option<Arc<dyn VerifyEventSink>>,
+    normalize_codes: bool,
+    code_length: Option<usize>,
+    locale_fallback: Option<LocaleFallback>,
+    pseudonymizer: Option<Pseudonymizer>,
+    resend_policy: Option<Arc<ResendPolicy>>,
+    budget: Option<Arc<VerificationBudget>>,
+    sessions: Arc<SessionPolicy>,
+    dry_run_code: Option<SecretString>,
 }
 
 impl Client {
@@ -139,49 +513,242 @@ impl Client {
         ClientBuilder::new()
     }
 
-    #[tracing::instrument(name = "Twilio Verify: Request OTP to phone", skip(self, to))]
+    /// A copy of the client whose calls are traced as children of `span`, e.g. one
+    /// carrying `order_id` or `tenant_id` fields, instead of the current span.
+    pub fn with_span(&self, span: tracing::Span) -> Self {
+        let mut client = self.clone();
+        if let Some(failover) = &mut client.failover {
+            failover.transport.span = Some(span.clone());
+        }
+        client.transport.span = Some(span);
+        client
+    }
+
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Request OTP to phone",
+        skip(self, to)
+    )]
     pub async fn request(&self, to: &Phone) -> Result<TwilioRequestResponse, ClientError> {
+        self.request_with_context(to, &EndUserContext::default())
+            .await
+    }
+
+    /// Requests a code like [`Client::request`], forwarding the end user's IP and user
+    /// agent as fraud signals.
+    pub async fn request_with_context(
+        &self,
+        to: &Phone,
+        context: &EndUserContext,
+    ) -> Result<TwilioRequestResponse, ClientError> {
+        self.request_with_context_raw(to, context)
+            .await
+            .map(Raw::into_inner)
+    }
+
+    /// Requests a code like [`Client::request_with_context`], also returning the
+    /// response JSON as received.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Request OTP to phone with context",
+        skip(self, to, context)
+    )]
+    pub async fn request_with_context_raw(
+        &self,
+        to: &Phone,
+        context: &EndUserContext,
+    ) -> Result<Raw<TwilioRequestResponse>, ClientError> {
+        // Held until the code is sent, so a failed request doesn't count
+        let reservation = match &self.resend_policy {
+            Some(policy) => Some(policy.admit(&to.hash())?),
+            None => None,
+        };
+        if let Some(budget) = &self.budget {
+            budget.admit(context.tenant_id()).await?;
+        }
+
         let service_sid = self.service_sid.expose_secret();
         let url = format!("/v2/Services/{service_sid}/Verifications");
 
-        let mut body = std::collections::HashMap::new();
+        let mut body = Params::new();
         body.insert("To", to.e164_number());
         body.insert("Channel", "sms".to_string());
+        context.apply(&mut body, self.locale_fallback.as_ref());
 
-        make_request(
-            &self.http_client,
-            (&self.base_url, &url),
-            &self.account_sid,
-            &self.auth_token,
-            self.timeout,
-            &body,
-            "Twilio SMS",
-        )
-        .await
+        let resp = if self.transport.dry_run {
+            let response = serde_json::json!({
+                "status": "pending",
+                "to": to.e164_number(),
+                "valid": false,
+                "dry_run": true,
+            });
+            self.transport.simulate(Method::POST, &url, response).await
+        } else {
+            self.request_regional(Method::POST, &url, &body, Idempotency::Unsafe)
+                .await
+                .map(|(mut resp, region): (Raw<TwilioRequestResponse>, _)| {
+                    resp.value.served_by = Some(region);
+                    resp
+                })
+        };
+        if resp.is_ok() {
+            if let Some(reservation) = reservation {
+                reservation.commit();
+            }
+            self.emit(VerifyEventKind::RequestIssued, to);
+        }
+        resp
     }
 
-    #[tracing::instrument(name = "Twilio Verify: Verify OTP", skip(self, to, code))]
     pub async fn verify(
         &self,
         to: &Phone,
         code: SecretString,
     ) -> Result<TwilioVerifyResponse, ClientError> {
+        self.verify_raw(to, code).await.map(Raw::into_inner)
+    }
+
+    /// Checks a code like [`Client::verify`], also returning the response JSON as
+    /// received.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Verify OTP",
+        skip(self, to, code)
+    )]
+    pub async fn verify_raw(
+        &self,
+        to: &Phone,
+        code: SecretString,
+    ) -> Result<Raw<TwilioVerifyResponse>, ClientError> {
         let service_sid = self.service_sid.expose_secret();
         let url = format!("/v2/Services/{service_sid}/VerificationCheck");
 
-        let mut body = std::collections::HashMap::new();
+        let code = if self.normalize_codes {
+            normalize_code(code.expose_secret())
+        } else {
+            code.expose_secret().to_string()
+        };
+        if let Some(length) = self.code_length {
+            code::validate_code(&code, length)
+                .map_err(|reason| ClientError::Verify(VerifyError::InvalidCode(reason)))?;
+        }
+
+        let mut body = Params::new();
         body.insert("To", to.e164_number());
-        body.insert("Code", code.expose_secret().to_string());
-
-        make_request(
-            &self.http_client,
-            (&self.base_url, &url),
-            &self.account_sid,
-            &self.auth_token,
-            self.timeout,
-            &body,
-            "Twilio Verify",
-        )
-        .await
+        body.insert("Code", code.clone());
+
+        let resp: Result<Raw<TwilioVerifyResponse>, ClientError> = if self.transport.dry_run {
+            self.transport
+                .simulate(Method::POST, &url, self.dry_run_check(to, &code))
+                .await
+        } else {
+            self.request_regional(Method::POST, &url, &body, Idempotency::UntilSuccess)
+                .await
+                .map(|(mut resp, region): (Raw<TwilioVerifyResponse>, _)| {
+                    resp.value.served_by = Some(region);
+                    resp
+                })
+        };
+
+        self.emit(VerifyEventKind::CodeChecked, to);
+        let outcome = match &resp {
+            Ok(r) if r.status == Status::Approved => {
+                if let Some(policy) = &self.resend_policy {
+                    policy.reset(&to.hash());
+                }
+                VerifyEventKind::Approved
+            }
+            Ok(r) if r.status == Status::Expired => VerifyEventKind::Expired,
+            // Twilio answers a check against an expired verification with a 404
+            Err(ClientError::ServerResponse { status_code, .. })
+                if *status_code == reqwest::StatusCode::NOT_FOUND =>
+            {
+                VerifyEventKind::Expired
+            }
+            _ => VerifyEventKind::Failed,
+        };
+        self.emit(outcome, to);
+
+        resp
+    }
+
+    /// Spawns a task that fetches the Verify service every `interval` to check the
+    /// credentials and connectivity. Must be called within a Tokio runtime.
+    pub fn spawn_healthcheck(&self, interval: Duration) -> HealthCheck {
+        let client = self.clone();
+        HealthCheck::spawn("Twilio Verify: Health check", interval, move || {
+            let client = client.clone();
+            async move {
+                let service_sid = client.service_sid.expose_secret();
+                let url = format!("/v2/Services/{service_sid}");
+                client.transport.probe(&url).await
+            }
+        })
+    }
+
+    /// The local address outgoing connections are bound to, if any.
+    pub fn local_address(&self) -> Option<IpAddr> {
+        self.transport.local_address
+    }
+
+    /// Establishes a connection to Twilio ahead of the first request, e.g. during
+    /// service startup, so the first send doesn't pay for a cold connection.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Warm up connection",
+        skip(self)
+    )]
+    pub async fn warm_up(&self) -> Result<(), ClientError> {
+        self.transport.warm_up().await
+    }
+
+    /// Sends an authenticated request to an arbitrary Twilio endpoint.
+    ///
+    /// `path` is resolved against the configured base url. This is an escape hatch for
+    /// endpoints the crate doesn't model yet.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Raw request",
+        skip(self, params)
+    )]
+    pub async fn raw_request(
+        &self,
+        method: Method,
+        path: &str,
+        params: &Params,
+    ) -> Result<serde_json::Value, ClientError> {
+        self.transport.request(method, path, params).await
+    }
+
+    /// A dry-run check answer. Only the client's `dry_run_code` is approved, so a dry-run
+    /// client in production can't be used to sign in with any code.
+    fn dry_run_check(&self, to: &Phone, code: &str) -> serde_json::Value {
+        let now = self.transport.clock.now().to_rfc3339();
+        let approved = self
+            .dry_run_code
+            .as_ref()
+            .is_some_and(|expected| expected.expose_secret() == code);
+        serde_json::json!({
+            "status": if approved { "approved" } else { "pending" },
+            "date_updated": now,
+            "account_sid": self.transport.account_sid.expose_secret(),
+            "to": to.e164_number(),
+            "valid": approved,
+            "sid": "dry-run",
+            "date_created": now,
+            "service_sid": self.service_sid.expose_secret(),
+            "channel": "sms",
+            "dry_run": true,
+        })
+    }
+
+    fn emit(&self, kind: VerifyEventKind, to: &Phone) {
+        if let Some(sink) = &self.event_sink {
+            let phone_hash = self
+                .pseudonymizer
+                .as_ref()
+                .map(|pseudonymizer| pseudonymizer.pseudonym(to));
+            sink.record(VerifyEvent { kind, phone_hash });
+        }
     }
 }
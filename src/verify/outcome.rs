@@ -0,0 +1,66 @@
+use reqwest::StatusCode;
+use secrecy::SecretString;
+
+use crate::error::{ClientError, VerifyError};
+use crate::verify::{Client, Status, TwilioVerifyResponse};
+use crate::Phone;
+
+/// What checking a code amounted to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum VerifyOutcome {
+    /// The code was correct.
+    Approved,
+    /// The code was wrong; the verification is still pending.
+    Incorrect,
+    /// The verification expired or no longer exists.
+    Expired,
+    /// The verification was canceled.
+    Canceled,
+    /// Too many wrong codes were submitted for this verification.
+    MaxAttemptsReached,
+    /// A `dry_run` client's configured code was checked. Nothing was verified, so
+    /// treat it as approved only in rehearsals.
+    DryRunApproved,
+}
+
+impl TwilioVerifyResponse {
+    pub fn outcome(&self) -> VerifyOutcome {
+        match self.status {
+            Status::Approved if self.valid && self.dry_run => VerifyOutcome::DryRunApproved,
+            Status::Approved if self.valid => VerifyOutcome::Approved,
+            Status::Expired | Status::Deleted => VerifyOutcome::Expired,
+            Status::Canceled => VerifyOutcome::Canceled,
+            Status::MaxAttemptsReached => VerifyOutcome::MaxAttemptsReached,
+            _ => VerifyOutcome::Incorrect,
+        }
+    }
+}
+
+impl Client {
+    /// Checks `code` like [`Client::verify`], but folds the non-approved answers Twilio
+    /// gives (wrong code, 404 for expired verifications, error 60202) into a
+    /// [`VerifyOutcome`]. Only genuine failures are returned as errors.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Check OTP",
+        skip(self, to, code)
+    )]
+    pub async fn check(
+        &self,
+        to: &Phone,
+        code: SecretString,
+    ) -> Result<VerifyOutcome, ClientError> {
+        match self.verify(to, code).await {
+            Ok(resp) => Ok(resp.outcome()),
+            Err(ClientError::ServerResponse { status_code, .. })
+                if status_code == StatusCode::NOT_FOUND =>
+            {
+                Ok(VerifyOutcome::Expired)
+            }
+            Err(ClientError::Verify(VerifyError::MaxCheckAttempts { .. })) => {
+                Ok(VerifyOutcome::MaxAttemptsReached)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use reqwest::{Method, Url};
+use serde::de::DeserializeOwned;
+
+use crate::error::ClientError;
+use crate::make_request::Transport;
+use crate::retry::{never_sent, Idempotency};
+use crate::verify::Client;
+use crate::Params;
+
+/// A Twilio region to send Verify requests to, e.g. `ie1` at
+/// `https://verify.dublin.ie1.twilio.com`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub name: String,
+    pub base_url: Url,
+}
+
+impl Region {
+    pub fn new(name: impl Into<String>, base_url: Url) -> Self {
+        Self {
+            name: name.into(),
+            base_url,
+        }
+    }
+}
+
+/// The secondary region of a client with
+/// [`ClientBuilder::failover_region`](super::ClientBuilder::failover_region).
+#[derive(Clone, Debug)]
+pub(crate) struct Failover {
+    pub(crate) region: Region,
+    pub(crate) transport: Transport,
+}
+
+// Errors that mean the region couldn't be reached or is down, rather than that the
+// request itself was refused
+fn is_connectivity_error(err: &ClientError) -> bool {
+    match err {
+        ClientError::Timeout(_) | ClientError::UpstreamUnavailable { .. } => true,
+        ClientError::Reqwest(err) => err.is_connect() || err.is_timeout(),
+        _ => false,
+    }
+}
+
+// A request that timed out or hit an outage page may still have been carried out, e.g.
+// a code sent, so only one that never went out is safe to repeat in another region
+fn should_fail_over(err: &ClientError, idempotency: Idempotency) -> bool {
+    match idempotency {
+        Idempotency::Safe => is_connectivity_error(err),
+        Idempotency::UntilSuccess | Idempotency::Unsafe => never_sent(err),
+    }
+}
+
+impl Client {
+    /// Sends the request to the primary region, then to the failover region if the
+    /// primary can't be reached. Returns the name of the region that answered.
+    ///
+    /// Verifications live in the region that created them, so a check sent to the
+    /// other region finds nothing and comes back expired. Checks fail over only when
+    /// the primary can't be connected to at all, e.g. because it was also down when
+    /// the code was requested.
+    pub(crate) async fn request_regional<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        params: &Params,
+        idempotency: Idempotency,
+    ) -> Result<(T, String), ClientError> {
+        let resp = self
+            .transport
+            .request_idempotent(method.clone(), path, params, idempotency)
+            .await;
+        match (resp, &self.failover) {
+            (Err(err), Some(failover)) if should_fail_over(&err, idempotency) => {
+                tracing::warn!(
+                    "Twilio Verify: region {} unreachable, failing over to {}: {}",
+                    self.region,
+                    failover.region.name,
+                    err
+                );
+                let resp = failover
+                    .transport
+                    .request_idempotent(method, path, params, idempotency)
+                    .await?;
+                Ok((resp, failover.region.name.clone()))
+            }
+            (resp, _) => resp.map(|resp| (resp, self.region.clone())),
+        }
+    }
+}
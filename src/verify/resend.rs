@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::clock::{Clock, SystemClock};
+use crate::error::VerifyError;
+
+/// Paces the codes sent to a phone: each resend has to wait `min_interval`, growing by
+/// `growth_factor` after every resend, and at most `max_resends` follow the first code.
+///
+/// Requests that come too early fail with [`VerifyError::ResendTooSoon`], whose
+/// `retry_in` can drive a frontend cooldown timer. A phone starts over once a code is
+/// approved or `reset_after` passes without a send.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use twilio_client::verify::ResendPolicy;
+/// // Resends after 30s, 60s, 120s, then no more
+/// let policy = ResendPolicy::new(Duration::from_secs(30))
+///     .growth_factor(2.0)
+///     .max_resends(3);
+/// ```
+#[derive(Debug)]
+pub struct ResendPolicy {
+    min_interval: Duration,
+    growth_factor: f64,
+    max_resends: u32,
+    reset_after: Duration,
+    clock: Arc<dyn Clock>,
+    // Keyed by phone hash so raw numbers aren't kept around
+    sends: Mutex<HashMap<String, SendHistory>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SendHistory {
+    resends: u32,
+    last_sent_at: DateTime<Utc>,
+}
+
+impl ResendPolicy {
+    pub fn new(min_interval: std::time::Duration) -> Self {
+        Self {
+            min_interval: Duration::from_std(min_interval).unwrap_or(Duration::MAX),
+            growth_factor: 1.0,
+            max_resends: 3,
+            // Verify's default code lifetime
+            reset_after: Duration::minutes(10),
+            clock: Arc::new(SystemClock),
+            sends: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// How much longer each resend waits than the one before. Defaults to 1, a fixed
+    /// interval.
+    pub fn growth_factor(mut self, growth_factor: f64) -> Self {
+        self.growth_factor = growth_factor.max(1.0);
+        self
+    }
+
+    /// The most codes sent after the first. Defaults to 3.
+    pub fn max_resends(mut self, max_resends: u32) -> Self {
+        self.max_resends = max_resends;
+        self
+    }
+
+    /// How long after its last code a phone starts over. Defaults to 10 minutes.
+    pub fn reset_after(mut self, reset_after: std::time::Duration) -> Self {
+        self.reset_after = Duration::from_std(reset_after).unwrap_or(Duration::MAX);
+        self
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Fails if a code can't be sent to the phone hashed as `key` yet, and otherwise
+    /// counts the send under the same lock, so concurrent requests for one phone
+    /// can't both get through. The send is taken back when the reservation is dropped
+    /// without [`ResendReservation::commit`], e.g. because Twilio refused it.
+    pub(crate) fn admit(&self, key: &str) -> Result<ResendReservation<'_>, VerifyError> {
+        let now = self.clock.now();
+        let mut sends = self.sends.lock().unwrap();
+        sends.retain(|_, history| now - history.last_sent_at < self.reset_after);
+
+        let previous = sends.get(key).copied();
+        let sent = match previous {
+            None => SendHistory {
+                resends: 0,
+                last_sent_at: now,
+            },
+            Some(history) => {
+                if history.resends >= self.max_resends {
+                    return Err(VerifyError::ResendLimitReached);
+                }
+                let next_at = history.last_sent_at + self.interval(history.resends);
+                if now < next_at {
+                    let retry_in = (next_at - now).to_std().unwrap_or_default();
+                    return Err(VerifyError::ResendTooSoon { retry_in });
+                }
+                SendHistory {
+                    resends: history.resends + 1,
+                    last_sent_at: now,
+                }
+            }
+        };
+        sends.insert(key.to_string(), sent);
+        Ok(ResendReservation {
+            policy: self,
+            key: key.to_string(),
+            previous,
+            sent,
+            committed: false,
+        })
+    }
+
+    /// Forgets the phone hashed as `key`, e.g. once its code is approved.
+    pub(crate) fn reset(&self, key: &str) {
+        self.sends.lock().unwrap().remove(key);
+    }
+
+    fn interval(&self, resends: u32) -> Duration {
+        let factor = self.growth_factor.powi(resends as i32);
+        let millis = self.min_interval.num_milliseconds() as f64 * factor;
+        Duration::milliseconds(millis.min(i64::MAX as f64) as i64)
+    }
+}
+
+/// A code counted against a phone by [`ResendPolicy::admit`], taken back on drop
+/// unless committed.
+#[derive(Debug)]
+pub(crate) struct ResendReservation<'a> {
+    policy: &'a ResendPolicy,
+    key: String,
+    previous: Option<SendHistory>,
+    sent: SendHistory,
+    committed: bool,
+}
+
+impl ResendReservation<'_> {
+    /// Keeps the send counted, once the code went out.
+    pub(crate) fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for ResendReservation<'_> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        let mut sends = self.policy.sends.lock().unwrap();
+        // Unless the phone was reset or moved on since
+        if sends.get(&self.key) == Some(&self.sent) {
+            match self.previous {
+                Some(previous) => sends.insert(self.key.clone(), previous),
+                None => sends.remove(&self.key),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use claim::{assert_err, assert_ok};
+
+    use crate::clock::MockClock;
+    use crate::error::VerifyError;
+    use crate::verify::ResendPolicy;
+
+    fn policy(clock: &MockClock) -> ResendPolicy {
+        ResendPolicy::new(Duration::from_secs(30))
+            .growth_factor(2.0)
+            .max_resends(2)
+            .clock(Arc::new(clock.clone()))
+    }
+
+    #[test]
+    fn resend_intervals_grow_until_the_limit() {
+        let clock = MockClock::default();
+        let policy = policy(&clock);
+
+        policy.admit("phone").unwrap().commit();
+        clock.advance(chrono::Duration::seconds(10));
+        assert_eq!(
+            policy.admit("phone").unwrap_err(),
+            VerifyError::ResendTooSoon {
+                retry_in: Duration::from_secs(20)
+            }
+        );
+
+        clock.advance(chrono::Duration::seconds(20));
+        policy.admit("phone").unwrap().commit();
+
+        clock.advance(chrono::Duration::seconds(30));
+        assert_eq!(
+            policy.admit("phone").unwrap_err(),
+            VerifyError::ResendTooSoon {
+                retry_in: Duration::from_secs(30)
+            }
+        );
+
+        clock.advance(chrono::Duration::seconds(30));
+        policy.admit("phone").unwrap().commit();
+        assert_eq!(
+            policy.admit("phone").unwrap_err(),
+            VerifyError::ResendLimitReached
+        );
+    }
+
+    #[test]
+    fn phones_start_over_after_a_reset() {
+        let clock = MockClock::default();
+        let policy = policy(&clock);
+
+        policy.admit("phone").unwrap().commit();
+        policy.reset("phone");
+
+        assert_ok!(policy.admit("phone"));
+    }
+
+    #[test]
+    fn phones_start_over_after_reset_after_passes() {
+        let clock = MockClock::default();
+        let policy = policy(&clock);
+
+        policy.admit("phone").unwrap().commit();
+        clock.advance(chrono::Duration::minutes(10));
+
+        assert_ok!(policy.admit("phone"));
+    }
+
+    #[test]
+    fn only_one_of_two_simultaneous_requests_is_admitted() {
+        let clock = MockClock::default();
+        let policy = policy(&clock);
+
+        let first = assert_ok!(policy.admit("phone"));
+        assert_err!(policy.admit("phone"));
+        first.commit();
+        assert_err!(policy.admit("phone"));
+    }
+
+    #[test]
+    fn dropped_reservations_are_taken_back() {
+        let clock = MockClock::default();
+        let policy = policy(&clock);
+
+        policy.admit("phone").unwrap().commit();
+        clock.advance(chrono::Duration::seconds(30));
+        drop(policy.admit("phone").unwrap());
+
+        // Still the first resend, due after 30s rather than 60s
+        policy.admit("phone").unwrap().commit();
+    }
+}
@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{Clock, SystemClock};
+use crate::error::{ClientError, VerifyError};
+use crate::verify::{Client, VerifyOutcome};
+use crate::{Phone, TwilioDateTime};
+
+/// The state of an OTP flow started with [`Client::start_session`], kept in a
+/// [`SessionStore`] so any instance of a service can continue it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub id: String,
+    pub phone: Phone,
+    /// How many codes were sent, the first included.
+    pub sends: u32,
+    /// How many codes were checked.
+    pub attempts: u32,
+    pub expires_at: TwilioDateTime,
+}
+
+/// A counter of a [`Session`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SessionCounter {
+    Sends,
+    Attempts,
+}
+
+/// Where [`Session`]s are kept between requests.
+///
+/// Share one store across instances behind a load balancer, e.g. Redis with a hash per
+/// session:
+///
+/// ```ignore
+/// #[async_trait]
+/// impl SessionStore for RedisSessionStore {
+///     async fn put(&self, session: &Session, ttl: Duration) -> Result<(), BoxError> {
+///         let key = format!("otp:{}", session.id);
+///         redis::pipe()
+///             .atomic()
+///             .hset(&key, "session", serde_json::to_string(session)?)
+///             .hset(&key, "sends", session.sends)
+///             .hset(&key, "attempts", session.attempts)
+///             .expire(&key, ttl.as_secs() as i64)
+///             .query_async(&mut self.connection()).await?;
+///         Ok(())
+///     }
+///
+///     // `get` reads all three fields back into the session, and `remove` is a `DEL`
+///
+///     async fn increment(&self, id: &str, counter: SessionCounter) -> Result<Option<u32>, BoxError> {
+///         let field = match counter {
+///             SessionCounter::Sends => "sends",
+///             SessionCounter::Attempts => "attempts",
+///         };
+///         // HINCRBY on a missing key would create it without a TTL
+///         let script = redis::Script::new(
+///             "if redis.call('EXISTS', KEYS[1]) == 1 then \
+///                 return redis.call('HINCRBY', KEYS[1], ARGV[1], 1) end",
+///         );
+///         Ok(script.key(format!("otp:{id}")).arg(field).invoke_async(&mut self.connection()).await?)
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait SessionStore: Debug + Send + Sync {
+    /// Stores `session`, replacing any with the same id, until `ttl` passes.
+    async fn put(
+        &self,
+        session: &Session,
+        ttl: Duration,
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+
+    /// The session `id`, or `None` once it expired or was removed.
+    async fn get(&self, id: &str) -> Result<Option<Session>, Box<dyn Error + Send + Sync>>;
+
+    /// Atomically adds one to a counter of the session `id` and returns the new value,
+    /// or `None` if there is no such session. The session's TTL is left as it is.
+    async fn increment(
+        &self,
+        id: &str,
+        counter: SessionCounter,
+    ) -> Result<Option<u32>, Box<dyn Error + Send + Sync>>;
+
+    async fn remove(&self, id: &str) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// A [`SessionStore`] local to the process, the default.
+#[derive(Debug)]
+pub struct InMemorySessionStore {
+    clock: Arc<dyn Clock>,
+    // Each session with the time it expires
+    sessions: Mutex<HashMap<String, (Session, DateTime<Utc>)>>,
+}
+
+impl InMemorySessionStore {
+    pub fn new() -> Self {
+        Self {
+            clock: Arc::new(SystemClock),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn live(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Session, DateTime<Utc>)>> {
+        let now = self.clock.now();
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|_, (_, expires_at)| *expires_at > now);
+        sessions
+    }
+}
+
+impl Default for InMemorySessionStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SessionStore for InMemorySessionStore {
+    async fn put(
+        &self,
+        session: &Session,
+        ttl: Duration,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let expires_at = self.clock.now() + chrono::Duration::from_std(ttl)?;
+        self.live()
+            .insert(session.id.clone(), (session.clone(), expires_at));
+        Ok(())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<Session>, Box<dyn Error + Send + Sync>> {
+        Ok(self.live().get(id).map(|(session, _)| session.clone()))
+    }
+
+    async fn increment(
+        &self,
+        id: &str,
+        counter: SessionCounter,
+    ) -> Result<Option<u32>, Box<dyn Error + Send + Sync>> {
+        Ok(self.live().get_mut(id).map(|(session, _)| {
+            let count = match counter {
+                SessionCounter::Sends => &mut session.sends,
+                SessionCounter::Attempts => &mut session.attempts,
+            };
+            *count += 1;
+            *count
+        }))
+    }
+
+    async fn remove(&self, id: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.live().remove(id);
+        Ok(())
+    }
+}
+
+/// How long [`Session`]s last and how many codes they allow, and where they are kept.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use std::time::Duration;
+/// # use twilio_client::verify::{InMemorySessionStore, SessionPolicy};
+/// let policy = SessionPolicy::new(Arc::new(InMemorySessionStore::new()))
+///     .ttl(Duration::from_secs(5 * 60))
+///     .max_attempts(3);
+/// ```
+#[derive(Debug)]
+pub struct SessionPolicy {
+    store: Arc<dyn SessionStore>,
+    ttl: Duration,
+    max_sends: u32,
+    max_attempts: u32,
+    clock: Arc<dyn Clock>,
+}
+
+impl SessionPolicy {
+    pub fn new(store: Arc<dyn SessionStore>) -> Self {
+        Self {
+            store,
+            // Verify's default code lifetime
+            ttl: Duration::from_secs(10 * 60),
+            max_sends: 4,
+            max_attempts: 5,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// How long a session lasts from its first code. Defaults to 10 minutes.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// The most codes sent in a session, the first included. Defaults to 4.
+    pub fn max_sends(mut self, max_sends: u32) -> Self {
+        self.max_sends = max_sends.max(1);
+        self
+    }
+
+    /// The most codes checked in a session. Defaults to 5, Verify's own limit.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+impl Default for SessionPolicy {
+    fn default() -> Self {
+        Self::new(Arc::new(InMemorySessionStore::new()))
+    }
+}
+
+fn store_error(err: Box<dyn Error + Send + Sync>) -> ClientError {
+    ClientError::Verify(VerifyError::SessionStore(err.to_string()))
+}
+
+impl Client {
+    /// Sends a code to `to` and starts the session `id` for it. `id` names the flow,
+    /// e.g. a sign-in attempt, and must not be guessable: anyone with it can use up
+    /// the session's attempts. Starting an existing session again starts it over.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Start session",
+        skip(self, id, to)
+    )]
+    pub async fn start_session(&self, id: &str, to: &Phone) -> Result<Session, ClientError> {
+        let policy = &self.sessions;
+        self.request(to).await?;
+
+        let session = Session {
+            id: id.to_string(),
+            phone: to.clone(),
+            sends: 1,
+            attempts: 0,
+            expires_at: TwilioDateTime::from(
+                policy.clock.now()
+                    + chrono::Duration::from_std(policy.ttl).unwrap_or(chrono::Duration::MAX),
+            ),
+        };
+        policy
+            .store
+            .put(&session, policy.ttl)
+            .await
+            .map_err(store_error)?;
+        Ok(session)
+    }
+
+    /// Sends another code in the session `id`. Fails with
+    /// [`VerifyError::SessionNotFound`] once it expired and with
+    /// [`VerifyError::ResendLimitReached`] after its last code. The send is counted
+    /// before the code is requested, so one the client's `ResendPolicy` holds back
+    /// still counts.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Resend session code",
+        skip(self, id)
+    )]
+    pub async fn resend_session(&self, id: &str) -> Result<Session, ClientError> {
+        let policy = &self.sessions;
+        let mut session = self
+            .session(id)
+            .await?
+            .ok_or(VerifyError::SessionNotFound)?;
+        let sends = policy
+            .store
+            .increment(id, SessionCounter::Sends)
+            .await
+            .map_err(store_error)?
+            .ok_or(VerifyError::SessionNotFound)?;
+        if sends > policy.max_sends {
+            return Err(VerifyError::ResendLimitReached.into());
+        }
+
+        self.request(&session.phone).await?;
+        session.sends = sends;
+        Ok(session)
+    }
+
+    /// Checks `code` in the session `id`, which ends once the code is approved or can
+    /// no longer be. An expired session is [`VerifyOutcome::Expired`], and one out of
+    /// attempts is [`VerifyOutcome::MaxAttemptsReached`] without asking Twilio.
+    #[tracing::instrument(
+        parent = self.transport.parent_span(),
+        name = "Twilio Verify: Check session code",
+        skip(self, id, code)
+    )]
+    pub async fn check_session(
+        &self,
+        id: &str,
+        code: SecretString,
+    ) -> Result<VerifyOutcome, ClientError> {
+        let policy = &self.sessions;
+        let Some(session) = self.session(id).await? else {
+            return Ok(VerifyOutcome::Expired);
+        };
+        let attempts = policy
+            .store
+            .increment(id, SessionCounter::Attempts)
+            .await
+            .map_err(store_error)?;
+        let outcome = match attempts {
+            None => VerifyOutcome::Expired,
+            Some(attempts) if attempts > policy.max_attempts => VerifyOutcome::MaxAttemptsReached,
+            Some(_) => self.check(&session.phone, code).await?,
+        };
+
+        if outcome != VerifyOutcome::Incorrect {
+            policy.store.remove(id).await.map_err(store_error)?;
+        }
+        Ok(outcome)
+    }
+
+    /// The session `id`, or `None` once it expired or ended.
+    pub async fn session(&self, id: &str) -> Result<Option<Session>, ClientError> {
+        self.sessions.store.get(id).await.map_err(store_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::clock::MockClock;
+    use crate::verify::{InMemorySessionStore, Session, SessionCounter, SessionStore};
+    use crate::Phone;
+
+    fn session() -> Session {
+        Session {
+            id: "signin-1".to_string(),
+            phone: Phone::parse("0700123456", "KE").unwrap(),
+            sends: 1,
+            attempts: 0,
+            expires_at: chrono::Utc::now().into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn sessions_expire_after_their_ttl() {
+        let clock = MockClock::default();
+        let store = InMemorySessionStore::new().clock(Arc::new(clock.clone()));
+
+        store
+            .put(&session(), Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .increment("signin-1", SessionCounter::Attempts)
+                .await
+                .unwrap(),
+            Some(1)
+        );
+        assert_eq!(store.get("signin-1").await.unwrap().unwrap().attempts, 1);
+
+        clock.advance(chrono::Duration::seconds(60));
+        assert_eq!(store.get("signin-1").await.unwrap(), None);
+        assert_eq!(
+            store
+                .increment("signin-1", SessionCounter::Sends)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
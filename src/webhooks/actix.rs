@@ -0,0 +1,118 @@
+use actix_web::dev::Payload;
+use actix_web::http::StatusCode;
+use actix_web::{web, FromRequest, HttpRequest, ResponseError};
+use futures_util::future::LocalBoxFuture;
+use serde::de::DeserializeOwned;
+
+use crate::webhooks::{
+    parse_form, RequestValidator, TwilioWebhook, WebhookRejection, SIGNATURE_HEADER,
+};
+
+/// Takes the [`RequestValidator`] from the app's data, registered with
+/// `App::new().app_data(web::Data::new(validator))`. The scheme and host come from
+/// the connection info, which honours `Forwarded` and `X-Forwarded-*` headers. The
+/// body is limited by actix's `PayloadConfig`.
+impl<T> FromRequest for TwilioWebhook<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    type Error = WebhookRejection;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let validator = req
+            .app_data::<web::Data<RequestValidator>>()
+            .map(|validator| validator.get_ref().clone());
+        let url = validator.as_ref().map(|validator| {
+            let info = req.connection_info();
+            let path_and_query = req
+                .uri()
+                .path_and_query()
+                .map(|path_and_query| path_and_query.as_str())
+                .unwrap_or("/");
+            validator.request_url(info.scheme(), info.host(), path_and_query)
+        });
+        let signature = req
+            .headers()
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = web::Bytes::from_request(req, payload);
+
+        Box::pin(async move {
+            let (Some(validator), Some(url)) = (validator, url) else {
+                return Err(WebhookRejection::MissingValidator);
+            };
+            let body = body
+                .await
+                .map_err(|err| WebhookRejection::InvalidPayload(err.to_string()))?;
+            parse_form(&validator, &url, signature.as_deref(), &body).map(TwilioWebhook)
+        })
+    }
+}
+
+impl ResponseError for WebhookRejection {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            WebhookRejection::MissingSignature | WebhookRejection::InvalidSignature => {
+                StatusCode::FORBIDDEN
+            }
+            WebhookRejection::InvalidPayload(_) => StatusCode::BAD_REQUEST,
+            WebhookRejection::MissingValidator => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+    use actix_web::{web, FromRequest};
+    use secrecy::SecretString;
+
+    use crate::webhooks::{IncomingMessage, RequestValidator, TwilioWebhook, WebhookRejection};
+
+    const BODY: &str = "MessageSid=SM123&AccountSid=AC123&From=%2B254700123456\
+        &To=%2B254700782326&Body=STOP";
+
+    fn validator() -> RequestValidator {
+        RequestValidator::new(SecretString::from("token"))
+            .base_url("https://hooks.example.com".parse().unwrap())
+    }
+
+    async fn extract(
+        signature: &str,
+        validator: Option<RequestValidator>,
+    ) -> Result<TwilioWebhook<IncomingMessage>, WebhookRejection> {
+        let mut request = TestRequest::post()
+            .uri("/sms?tenant=acme")
+            .insert_header(("X-Twilio-Signature", signature))
+            .set_payload(BODY);
+        if let Some(validator) = validator {
+            request = request.app_data(web::Data::new(validator));
+        }
+        let (request, mut payload) = request.to_http_parts();
+        TwilioWebhook::from_request(&request, &mut payload).await
+    }
+
+    #[tokio::test]
+    async fn signed_requests_are_parsed() {
+        let params: Vec<(String, String)> = serde_urlencoded::from_str(BODY).unwrap();
+        let signature = validator().sign("https://hooks.example.com/sms?tenant=acme", params);
+
+        let TwilioWebhook(message) = extract(&signature, Some(validator())).await.unwrap();
+
+        assert_eq!(message.body, "STOP");
+    }
+
+    #[tokio::test]
+    async fn forged_or_unvalidated_requests_are_rejected() {
+        assert_eq!(
+            extract("Zm9yZ2Vk", Some(validator())).await,
+            Err(WebhookRejection::InvalidSignature)
+        );
+        assert_eq!(
+            extract("Zm9yZ2Vk", None).await,
+            Err(WebhookRejection::MissingValidator)
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use axum::body::Bytes;
+use axum::extract::{FromRef, FromRequest, Request};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde::de::DeserializeOwned;
+
+use crate::webhooks::{
+    parse_form, RequestValidator, TwilioWebhook, WebhookRejection, SIGNATURE_HEADER,
+};
+
+/// Takes the [`RequestValidator`] from the router's state, e.g.
+/// `Router::new().route("/status", post(handler)).with_state(validator)`, or from a
+/// field of it with a `FromRef` impl. The body is limited by axum's
+/// `DefaultBodyLimit`.
+impl<S, T> FromRequest<S> for TwilioWebhook<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+    RequestValidator: FromRef<S>,
+{
+    type Rejection = WebhookRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let validator = RequestValidator::from_ref(state);
+        let headers = req.headers();
+        let header_value = |name| headers.get(name).and_then(|value| value.to_str().ok());
+
+        let scheme = header_value("x-forwarded-proto")
+            .or(req.uri().scheme_str())
+            .unwrap_or("https");
+        let host = header_value(header::HOST.as_str())
+            .or(req.uri().authority().map(|authority| authority.as_str()))
+            .unwrap_or_default();
+        let path_and_query = req
+            .uri()
+            .path_and_query()
+            .map(|path_and_query| path_and_query.as_str())
+            .unwrap_or("/");
+        let url = validator.request_url(scheme, host, path_and_query);
+        let signature = header_value(SIGNATURE_HEADER).map(str::to_string);
+
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|rejection| WebhookRejection::InvalidPayload(rejection.body_text()))?;
+        parse_form(&validator, &url, signature.as_deref(), &body).map(TwilioWebhook)
+    }
+}
+
+impl IntoResponse for WebhookRejection {
+    fn into_response(self) -> Response {
+        let status = match self {
+            WebhookRejection::MissingSignature | WebhookRejection::InvalidSignature => {
+                StatusCode::FORBIDDEN
+            }
+            WebhookRejection::InvalidPayload(_) => StatusCode::BAD_REQUEST,
+            WebhookRejection::MissingValidator => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, self.to_string()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::extract::{FromRequest, Request};
+    use secrecy::SecretString;
+
+    use crate::webhooks::{IncomingMessage, RequestValidator, TwilioWebhook, WebhookRejection};
+
+    const BODY: &str = "MessageSid=SM123&AccountSid=AC123&From=%2B254700123456\
+        &To=%2B254700782326&Body=STOP";
+
+    fn request(signature: &str) -> Request {
+        Request::post("/sms?tenant=acme")
+            .header("host", "hooks.example.com")
+            .header("x-twilio-signature", signature)
+            .body(Body::from(BODY))
+            .unwrap()
+    }
+
+    fn signature() -> String {
+        let params: Vec<(String, String)> = serde_urlencoded::from_str(BODY).unwrap();
+        RequestValidator::new(SecretString::from("token"))
+            .sign("https://hooks.example.com/sms?tenant=acme", params)
+    }
+
+    #[tokio::test]
+    async fn signed_requests_are_parsed() {
+        let validator = RequestValidator::new(SecretString::from("token"));
+
+        let TwilioWebhook(message) =
+            TwilioWebhook::<IncomingMessage>::from_request(request(&signature()), &validator)
+                .await
+                .unwrap();
+
+        assert_eq!(message.body, "STOP");
+    }
+
+    #[tokio::test]
+    async fn forged_requests_are_rejected() {
+        let validator = RequestValidator::new(SecretString::from("token"));
+
+        let rejection =
+            TwilioWebhook::<IncomingMessage>::from_request(request("Zm9yZ2Vk"), &validator)
+                .await
+                .unwrap_err();
+
+        assert_eq!(rejection, WebhookRejection::InvalidSignature);
+    }
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::ParseError;
+use crate::models::Phone;
+use crate::webhooks::Location;
+
+/// A call Twilio forwards to a number's incoming call webhook, deserialized from the
+/// form it POSTs.
+///
+/// `From` and `To` are kept as received: callers can be `anonymous`, a SIP URI or a
+/// Twilio client, so [`IncomingCall::caller`] parses `From` only when it is needed.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(from = "IncomingCallForm")]
+pub struct IncomingCall {
+    pub call_sid: String,
+    pub account_sid: String,
+    pub from: String,
+    pub to: String,
+    pub status: CallStatus,
+    /// `inbound`, `outbound-api` or `outbound-dial`.
+    pub direction: Option<String>,
+    /// The caller ID name, if the number has caller name lookup enabled.
+    pub caller_name: Option<String>,
+    /// The number that forwarded the call, if the carrier says so.
+    pub forwarded_from: Option<String>,
+    /// Where the caller's number is registered, as far as Twilio knows.
+    pub from_location: Location,
+    pub to_location: Location,
+}
+
+impl IncomingCall {
+    /// The caller's phone, or an error for callers without a valid number, e.g.
+    /// withheld caller IDs.
+    pub fn caller(&self) -> Result<Phone, ParseError> {
+        Phone::parse_with_no_country(&self.from)
+    }
+}
+
+/// The status of a call
+///
+/// Statuses Twilio adds later parse as [`CallStatus::Unknown`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum CallStatus {
+    Queued,
+    Ringing,
+    InProgress,
+    Completed,
+    Busy,
+    Failed,
+    NoAnswer,
+    Canceled,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct IncomingCallForm {
+    call_sid: String,
+    account_sid: String,
+    from: String,
+    to: String,
+    call_status: CallStatus,
+    direction: Option<String>,
+    caller_name: Option<String>,
+    forwarded_from: Option<String>,
+    from_city: Option<String>,
+    from_state: Option<String>,
+    from_zip: Option<String>,
+    from_country: Option<String>,
+    to_city: Option<String>,
+    to_state: Option<String>,
+    to_zip: Option<String>,
+    to_country: Option<String>,
+}
+
+impl From<IncomingCallForm> for IncomingCall {
+    fn from(form: IncomingCallForm) -> Self {
+        IncomingCall {
+            call_sid: form.call_sid,
+            account_sid: form.account_sid,
+            from: form.from,
+            to: form.to,
+            status: form.call_status,
+            direction: form.direction,
+            caller_name: form.caller_name,
+            forwarded_from: form.forwarded_from,
+            from_location: Location {
+                city: form.from_city,
+                state: form.from_state,
+                zip: form.from_zip,
+                country: form.from_country,
+            },
+            to_location: Location {
+                city: form.to_city,
+                state: form.to_state,
+                zip: form.to_zip,
+                country: form.to_country,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claim::assert_err;
+
+    use crate::webhooks::{CallStatus, IncomingCall};
+
+    #[test]
+    fn calls_parse_from_the_form() {
+        let form = "CallSid=CA123&AccountSid=AC123&From=%2B254700123456&To=%2B254700782326\
+            &CallStatus=in-progress&Direction=inbound&FromCountry=KE&FromCity=NAIROBI\
+            &CallerCountry=KE&ApiVersion=2010-04-01";
+
+        let call: IncomingCall = serde_urlencoded::from_str(form).unwrap();
+
+        assert_eq!(call.status, CallStatus::InProgress);
+        assert_eq!(call.direction.as_deref(), Some("inbound"));
+        assert_eq!(call.from_location.city.as_deref(), Some("NAIROBI"));
+        assert_eq!(call.caller().unwrap().e164_number(), "+254700123456");
+    }
+
+    #[test]
+    fn withheld_callers_have_no_phone() {
+        let form = "CallSid=CA123&AccountSid=AC123&From=anonymous&To=%2B254700782326\
+            &CallStatus=ringing";
+
+        let call: IncomingCall = serde_urlencoded::from_str(form).unwrap();
+
+        assert_err!(call.caller());
+    }
+}
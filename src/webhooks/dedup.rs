@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::clock::{Clock, SystemClock};
+
+/// Where [`DuplicateDetector`] remembers the deliveries it has seen.
+///
+/// Share one store (e.g. Redis) across instances to catch duplicates that land on a
+/// different instance than the first delivery.
+#[async_trait]
+pub trait IdempotencyStore: Debug + Send + Sync {
+    /// Records `key` and returns `true` if it had not been recorded before.
+    async fn insert_if_absent(&self, key: &str) -> Result<bool, Box<dyn Error + Send + Sync>>;
+
+    /// Forgets `key`, so it can be recorded again.
+    async fn remove(&self, key: &str) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// A process-local store that forgets keys after `ttl`.
+#[derive(Debug)]
+pub struct InMemoryIdempotencyStore {
+    ttl: Duration,
+    clock: Arc<dyn Clock>,
+    seen: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl InMemoryIdempotencyStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            clock: Arc::new(SystemClock),
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn insert_if_absent(&self, key: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        let now = self.clock.now();
+        let mut seen = self.seen.lock().unwrap();
+        seen.retain(|_, at| now - *at < self.ttl);
+
+        if seen.contains_key(key) {
+            return Ok(false);
+        }
+        seen.insert(key.to_string(), now);
+        Ok(true)
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.seen.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// Detects webhook deliveries Twilio has retried after a non-2xx response.
+///
+/// A status callback is identified by its `MessageSid` and `MessageStatus`, so each
+/// genuine status transition still gets through once.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use twilio_client::webhooks::{DuplicateDetector, InMemoryIdempotencyStore};
+/// # async fn handle() -> Result<(), std::io::Error> { Ok(()) }
+/// # async fn example() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let detector = DuplicateDetector::new(Arc::new(InMemoryIdempotencyStore::new(
+///     chrono::Duration::hours(1),
+/// )));
+/// if !detector.is_duplicate("SM123", "delivered").await? {
+///     if let Err(err) = handle().await {
+///         // Answering with a 5xx makes Twilio retry, which must not be skipped
+///         detector.release("SM123", "delivered").await?;
+///         return Err(err.into());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DuplicateDetector {
+    store: Arc<dyn IdempotencyStore>,
+}
+
+impl DuplicateDetector {
+    pub fn new(store: Arc<dyn IdempotencyStore>) -> Self {
+        Self { store }
+    }
+
+    /// Returns `true` if this delivery was already seen and should be skipped.
+    /// Otherwise the delivery is recorded as seen; [`DuplicateDetector::release`] it if
+    /// handling it fails.
+    pub async fn is_duplicate(
+        &self,
+        message_sid: &str,
+        message_status: &str,
+    ) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        let key = Self::key(message_sid, message_status);
+        Ok(!self.store.insert_if_absent(&key).await?)
+    }
+
+    /// Forgets a delivery whose handling failed, so Twilio's retry of it is processed
+    /// instead of being skipped as a duplicate.
+    pub async fn release(
+        &self,
+        message_sid: &str,
+        message_status: &str,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.store
+            .remove(&Self::key(message_sid, message_status))
+            .await
+    }
+
+    fn key(message_sid: &str, message_status: &str) -> String {
+        format!("{message_sid}:{message_status}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::Duration;
+
+    use crate::clock::MockClock;
+    use crate::webhooks::{DuplicateDetector, InMemoryIdempotencyStore};
+
+    #[tokio::test]
+    async fn repeated_deliveries_are_duplicates() {
+        let store = InMemoryIdempotencyStore::new(Duration::hours(1));
+        let detector = DuplicateDetector::new(Arc::new(store));
+
+        assert!(!detector.is_duplicate("SM1", "delivered").await.unwrap());
+        assert!(detector.is_duplicate("SM1", "delivered").await.unwrap());
+        assert!(!detector.is_duplicate("SM1", "read").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn retries_of_failed_deliveries_are_processed() {
+        let store = InMemoryIdempotencyStore::new(Duration::hours(1));
+        let detector = DuplicateDetector::new(Arc::new(store));
+
+        assert!(!detector.is_duplicate("SM1", "delivered").await.unwrap());
+        // The handler failed, so Twilio will deliver the callback again
+        detector.release("SM1", "delivered").await.unwrap();
+
+        assert!(!detector.is_duplicate("SM1", "delivered").await.unwrap());
+        assert!(detector.is_duplicate("SM1", "delivered").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn keys_expire_after_the_ttl() {
+        let clock = MockClock::default();
+        let store =
+            InMemoryIdempotencyStore::new(Duration::minutes(5)).clock(Arc::new(clock.clone()));
+        let detector = DuplicateDetector::new(Arc::new(store));
+
+        assert!(!detector.is_duplicate("SM1", "sent").await.unwrap());
+        clock.advance(Duration::minutes(6));
+        assert!(!detector.is_duplicate("SM1", "sent").await.unwrap());
+    }
+}
@@ -0,0 +1,58 @@
+use serde::de::DeserializeOwned;
+
+use crate::webhooks::RequestValidator;
+
+/// The payload of a webhook request whose signature checked out, extracted by the
+/// axum and actix integrations.
+///
+/// `T` is the form Twilio POSTs, e.g.
+/// [`MessageStatusCallback`](crate::webhooks::MessageStatusCallback) or
+/// [`IncomingMessage`](crate::webhooks::IncomingMessage). The extractor needs the
+/// [`RequestValidator`] from the app's state (axum) or data (actix).
+#[derive(Clone, Debug, PartialEq)]
+pub struct TwilioWebhook<T>(pub T);
+
+impl<T> TwilioWebhook<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Why a webhook request was rejected.
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum WebhookRejection {
+    /// The request has no `X-Twilio-Signature` header. Responds with 403.
+    #[error("missing X-Twilio-Signature header")]
+    MissingSignature,
+    /// The signature doesn't match the request. Responds with 403.
+    #[error("invalid X-Twilio-Signature")]
+    InvalidSignature,
+    /// The body couldn't be read or parsed. Responds with 400.
+    #[error("invalid webhook payload: {0}")]
+    InvalidPayload(String),
+    /// No `RequestValidator` was registered with the app. Responds with 500.
+    #[error("no RequestValidator was registered")]
+    MissingValidator,
+}
+
+/// Checks a form body against `signature`, then parses it.
+pub(crate) fn parse_form<T: DeserializeOwned>(
+    validator: &RequestValidator,
+    url: &str,
+    signature: Option<&str>,
+    body: &[u8],
+) -> Result<T, WebhookRejection> {
+    let signature = signature.ok_or(WebhookRejection::MissingSignature)?;
+    let params: Vec<(String, String)> = serde_urlencoded::from_bytes(body)
+        .map_err(|err| WebhookRejection::InvalidPayload(err.to_string()))?;
+    if !validator.validate(
+        url,
+        params.iter().map(|(key, value)| (key, value)),
+        signature,
+    ) {
+        tracing::warn!("Twilio webhooks: rejecting request to {url} with an invalid signature");
+        return Err(WebhookRejection::InvalidSignature);
+    }
+    serde_urlencoded::from_bytes(body)
+        .map_err(|err| WebhookRejection::InvalidPayload(err.to_string()))
+}
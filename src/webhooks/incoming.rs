@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use url::Url;
+
+use crate::error::ParseError;
+use crate::models::Address;
+
+/// A message Twilio forwards to a number's incoming message webhook, deserialized from
+/// the form it POSTs, e.g. with axum's `Form` extractor.
+///
+/// ```
+/// # use twilio_client::webhooks::IncomingMessage;
+/// let form = "MessageSid=SM123&AccountSid=AC123&From=%2B254700123456&To=%2B254700782326\
+///     &Body=STOP&NumMedia=0&NumSegments=1";
+/// let message: IncomingMessage = serde_urlencoded::from_str(form).unwrap();
+/// assert_eq!(message.body, "STOP");
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "IncomingForm")]
+pub struct IncomingMessage {
+    pub message_sid: String,
+    pub account_sid: String,
+    /// Set when the receiving number belongs to a messaging service.
+    pub messaging_service_sid: Option<String>,
+    pub from: Address,
+    pub to: Address,
+    pub body: String,
+    pub num_segments: Option<u32>,
+    /// The attachments, in the order Twilio numbered them.
+    pub media: Vec<IncomingMedia>,
+    /// Where the sender's number is registered, as far as Twilio knows.
+    pub from_location: Location,
+    pub to_location: Location,
+}
+
+/// An attachment of an [`IncomingMessage`]. The URL needs the account's credentials to
+/// fetch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IncomingMedia {
+    pub url: Url,
+    /// The attachment's MIME type, e.g. `image/jpeg`.
+    pub content_type: Option<String>,
+}
+
+/// The geographic data Twilio attaches to the numbers of an [`IncomingMessage`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Location {
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip: Option<String>,
+    /// The ISO 3166-1 alpha-2 country code, e.g. `KE`.
+    pub country: Option<String>,
+}
+
+// Every field of the form arrives as a string, and the `MediaUrlN` and
+// `MediaContentTypeN` pairs are numbered, so they are collected from the rest
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct IncomingForm {
+    message_sid: String,
+    account_sid: String,
+    messaging_service_sid: Option<String>,
+    from: Address,
+    to: Address,
+    #[serde(default)]
+    body: String,
+    num_media: Option<String>,
+    num_segments: Option<String>,
+    from_city: Option<String>,
+    from_state: Option<String>,
+    from_zip: Option<String>,
+    from_country: Option<String>,
+    to_city: Option<String>,
+    to_state: Option<String>,
+    to_zip: Option<String>,
+    to_country: Option<String>,
+    #[serde(flatten)]
+    rest: HashMap<String, String>,
+}
+
+impl TryFrom<IncomingForm> for IncomingMessage {
+    type Error = ParseError;
+
+    fn try_from(mut form: IncomingForm) -> Result<Self, Self::Error> {
+        let num_media = parse_count("NumMedia", form.num_media.as_deref())?.unwrap_or(0);
+        let media = (0..num_media)
+            .map(|index| {
+                let url = form
+                    .rest
+                    .remove(&format!("MediaUrl{index}"))
+                    .ok_or_else(|| ParseError(format!("MediaUrl{index} is missing")))?;
+                Ok(IncomingMedia {
+                    url: Url::parse(&url)
+                        .map_err(|err| ParseError(format!("MediaUrl{index}: {err}")))?,
+                    content_type: form.rest.remove(&format!("MediaContentType{index}")),
+                })
+            })
+            .collect::<Result<_, ParseError>>()?;
+
+        Ok(IncomingMessage {
+            message_sid: form.message_sid,
+            account_sid: form.account_sid,
+            messaging_service_sid: form.messaging_service_sid,
+            from: form.from,
+            to: form.to,
+            body: form.body,
+            num_segments: parse_count("NumSegments", form.num_segments.as_deref())?,
+            media,
+            from_location: Location {
+                city: form.from_city,
+                state: form.from_state,
+                zip: form.from_zip,
+                country: form.from_country,
+            },
+            to_location: Location {
+                city: form.to_city,
+                state: form.to_state,
+                zip: form.to_zip,
+                country: form.to_country,
+            },
+        })
+    }
+}
+
+fn parse_count(field: &str, value: Option<&str>) -> Result<Option<u32>, ParseError> {
+    value
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| ParseError(format!("{field} is not a count: {value}")))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use claim::assert_err;
+
+    use crate::models::Address;
+    use crate::webhooks::IncomingMessage;
+
+    #[test]
+    fn media_is_collected_in_order() {
+        let form = "MessageSid=MM123&AccountSid=AC123&MessagingServiceSid=MG123\
+            &From=whatsapp%3A%2B254700123456&To=%2B254700782326&Body=Look&NumSegments=1\
+            &NumMedia=2&MediaUrl1=https%3A%2F%2Fapi.twilio.com%2Fb&MediaContentType1=image%2Fpng\
+            &MediaUrl0=https%3A%2F%2Fapi.twilio.com%2Fa&MediaContentType0=image%2Fjpeg\
+            &FromCountry=KE&SmsStatus=received&ApiVersion=2010-04-01";
+
+        let message: IncomingMessage = serde_urlencoded::from_str(form).unwrap();
+
+        assert!(matches!(message.from, Address::WhatsApp(_)));
+        assert_eq!(message.messaging_service_sid.as_deref(), Some("MG123"));
+        assert_eq!(message.num_segments, Some(1));
+        assert_eq!(message.from_location.country.as_deref(), Some("KE"));
+        assert_eq!(message.to_location.country, None);
+        let media: Vec<_> = message
+            .media
+            .iter()
+            .map(|media| (media.url.path(), media.content_type.as_deref()))
+            .collect();
+        assert_eq!(
+            media,
+            vec![("/a", Some("image/jpeg")), ("/b", Some("image/png"))]
+        );
+    }
+
+    #[test]
+    fn missing_media_urls_are_rejected() {
+        let form = "MessageSid=MM123&AccountSid=AC123&From=%2B254700123456\
+            &To=%2B254700782326&NumMedia=1";
+
+        assert_err!(serde_urlencoded::from_str::<IncomingMessage>(form));
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "axum")]
+mod axum;
+mod call;
+mod dedup;
+#[cfg(any(feature = "axum", feature = "actix"))]
+mod extract;
+mod incoming;
+mod screening;
+mod signature;
+#[cfg(feature = "sms")]
+mod status;
+pub use call::*;
+pub use dedup::*;
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub use extract::*;
+pub use incoming::*;
+pub use screening::*;
+pub use signature::*;
+#[cfg(feature = "sms")]
+pub use status::*;
@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+
+use crate::models::Phone;
+use crate::twiml::escape_xml;
+use crate::webhooks::IncomingCall;
+
+/// Turns away incoming calls from denied numbers or countries, answering Twilio with
+/// the TwiML to do so.
+///
+/// Denied calls are rejected before they are answered, so they aren't billed, unless
+/// [`CallScreen::say`] is set.
+///
+/// ```
+/// # use twilio_client::Phone;
+/// # use twilio_client::webhooks::CallScreen;
+/// let screen = CallScreen::new()
+///     .deny(&Phone::parse("0700123456", "KE").unwrap())
+///     .deny_country("NG");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CallScreen {
+    // E.164 numbers
+    numbers: HashSet<String>,
+    countries: HashSet<String>,
+    deny_unidentified: bool,
+    message: Option<String>,
+}
+
+/// What [`CallScreen::screen`] decided.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Screening {
+    /// The call may go on; the app answers it with its own TwiML. The caller is `None`
+    /// when `From` isn't a phone number.
+    Allow(Option<Phone>),
+    /// The call is turned away with `twiml`, which is the whole webhook response.
+    Deny { reason: DenyReason, twiml: String },
+}
+
+/// Why [`CallScreen`] turned a call away.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DenyReason {
+    /// The caller's number is denied.
+    Number,
+    /// The caller's number is from a denied country.
+    Country,
+    /// `From` isn't a phone number, e.g. a withheld caller ID.
+    Unidentified,
+}
+
+impl CallScreen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn deny(mut self, phone: &Phone) -> Self {
+        self.numbers.insert(phone.e164_number());
+        self
+    }
+
+    /// Denies every number of a country, by its ISO 3166-1 alpha-2 code, e.g. `NG`.
+    pub fn deny_country(mut self, country_iso: &str) -> Self {
+        self.countries.insert(country_iso.to_uppercase());
+        self
+    }
+
+    /// Denies callers whose `From` isn't a phone number. They are let through by
+    /// default.
+    pub fn deny_unidentified(mut self) -> Self {
+        self.deny_unidentified = true;
+        self
+    }
+
+    /// Answers denied calls to say `message` and hang up, instead of rejecting them.
+    pub fn say(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn screen(&self, call: &IncomingCall) -> Screening {
+        let caller = call.caller().ok();
+        let reason = match &caller {
+            None if self.deny_unidentified => DenyReason::Unidentified,
+            None => return Screening::Allow(None),
+            Some(phone) if self.numbers.contains(&phone.e164_number()) => DenyReason::Number,
+            Some(phone) if self.denies_country_of(phone) => DenyReason::Country,
+            Some(_) => return Screening::Allow(caller),
+        };
+        tracing::info!("Twilio Voice: denying call {} ({reason:?})", call.call_sid);
+        Screening::Deny {
+            reason,
+            twiml: self.twiml(),
+        }
+    }
+
+    // Numbers without a country, e.g. +800 freephone ones, match no country rule
+    fn denies_country_of(&self, phone: &Phone) -> bool {
+        !self.countries.is_empty()
+            && phone
+                .country_id()
+                .is_some_and(|country| self.countries.contains(&country))
+    }
+
+    fn twiml(&self) -> String {
+        let verbs = match &self.message {
+            Some(message) => format!("<Say>{}</Say><Hangup/>", escape_xml(message)),
+            None => r#"<Reject reason="rejected"/>"#.to_string(),
+        };
+        format!(r#"<?xml version="1.0" encoding="UTF-8"?><Response>{verbs}</Response>"#)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::Phone;
+    use crate::webhooks::{CallScreen, DenyReason, IncomingCall, Screening};
+
+    fn call(from: &str) -> IncomingCall {
+        let form = serde_urlencoded::to_string([
+            ("CallSid", "CA123"),
+            ("AccountSid", "AC123"),
+            ("From", from),
+            ("To", "+254700782326"),
+            ("CallStatus", "ringing"),
+        ])
+        .unwrap();
+        serde_urlencoded::from_str(&form).unwrap()
+    }
+
+    #[test]
+    fn denied_numbers_are_rejected() {
+        let screen = CallScreen::new().deny(&Phone::parse("0700123456", "KE").unwrap());
+
+        assert_eq!(
+            screen.screen(&call("+254700123456")),
+            Screening::Deny {
+                reason: DenyReason::Number,
+                twiml: r#"<?xml version="1.0" encoding="UTF-8"?><Response><Reject reason="rejected"/></Response>"#
+                    .to_string(),
+            }
+        );
+        assert!(matches!(
+            screen.screen(&call("+254700782326")),
+            Screening::Allow(Some(_))
+        ));
+    }
+
+    #[test]
+    fn numbers_without_a_country_match_no_country_rule() {
+        let screen = CallScreen::new().deny_country("KE");
+
+        assert!(matches!(
+            screen.screen(&call("+80012345678")),
+            Screening::Allow(Some(_))
+        ));
+    }
+
+    #[test]
+    fn denied_countries_and_unidentified_callers_hear_the_message() {
+        let screen = CallScreen::new()
+            .deny_country("ke")
+            .deny_unidentified()
+            .say("Calls from <this> number aren't accepted");
+
+        let Screening::Deny { reason, twiml } = screen.screen(&call("+254700123456")) else {
+            panic!("the call should be denied");
+        };
+        assert_eq!(reason, DenyReason::Country);
+        assert!(twiml
+            .contains("<Say>Calls from &lt;this&gt; number aren&apos;t accepted</Say><Hangup/>"));
+        assert!(matches!(
+            screen.screen(&call("anonymous")),
+            Screening::Deny {
+                reason: DenyReason::Unidentified,
+                ..
+            }
+        ));
+        assert_eq!(
+            CallScreen::new().screen(&call("anonymous")),
+            Screening::Allow(None)
+        );
+    }
+}
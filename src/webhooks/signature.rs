@@ -0,0 +1,204 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, SecretString};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use url::Url;
+
+/// The header Twilio signs its webhook requests in.
+pub const SIGNATURE_HEADER: &str = "X-Twilio-Signature";
+
+/// Checks the [`SIGNATURE_HEADER`] of webhook requests, so forged callbacks can be
+/// rejected.
+///
+/// Twilio signs with the auth token of the account the webhook belongs to. `url` must be
+/// the full URL Twilio requested, including the scheme, any port and the query string,
+/// as configured on the Twilio side: behind a proxy that rewrites it, rebuild it from
+/// the forwarded headers.
+///
+/// ```
+/// # use secrecy::SecretString;
+/// # use twilio_client::webhooks::RequestValidator;
+/// let validator = RequestValidator::new(SecretString::from("12345"));
+/// let params = [
+///     ("CallSid", "CA1234567890ABCDE"),
+///     ("Caller", "+12349013030"),
+///     ("Digits", "1234"),
+///     ("From", "+12349013030"),
+///     ("To", "+18005551212"),
+/// ];
+/// assert!(validator.validate(
+///     "https://mycompany.com/myapp.php?foo=1&bar=2",
+///     params,
+///     "0/KCTR6DLpKmkAf8muzZqo1nDgQ=",
+/// ));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RequestValidator {
+    auth_token: SecretString,
+    base_url: Option<Url>,
+}
+
+impl RequestValidator {
+    pub fn new(auth_token: SecretString) -> Self {
+        Self {
+            auth_token,
+            base_url: None,
+        }
+    }
+
+    /// The scheme and host Twilio reaches this service at, e.g.
+    /// `https://hooks.example.com`. The axum and actix extractors join the path and
+    /// query of a request onto it to get the URL Twilio signed; without it they go by
+    /// the request's headers.
+    pub fn base_url(mut self, url: Url) -> Self {
+        self.base_url = Some(url);
+        self
+    }
+
+    /// The URL Twilio signed for a request to `path_and_query`, given the scheme and
+    /// host the request itself says it was made to.
+    #[cfg(any(feature = "axum", feature = "actix"))]
+    pub(crate) fn request_url(&self, scheme: &str, host: &str, path_and_query: &str) -> String {
+        match &self.base_url {
+            Some(base) => format!("{}{path_and_query}", base.as_str().trim_end_matches('/')),
+            None => format!("{scheme}://{host}{path_and_query}"),
+        }
+    }
+
+    /// Checks a form-encoded request, e.g. a status callback, against its `signature`.
+    /// `params` are the POST parameters; pass none for a GET.
+    pub fn validate<K, V>(
+        &self,
+        url: &str,
+        params: impl IntoIterator<Item = (K, V)>,
+        signature: &str,
+    ) -> bool
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let Ok(signature) = STANDARD.decode(signature.trim()) else {
+            return false;
+        };
+        self.mac(&signed_payload(url, params))
+            .verify_slice(&signature)
+            .is_ok()
+    }
+
+    /// The signature Twilio sends for a form-encoded request, e.g. to test webhook
+    /// handlers with.
+    pub fn sign<K, V>(&self, url: &str, params: impl IntoIterator<Item = (K, V)>) -> String
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        STANDARD.encode(
+            self.mac(&signed_payload(url, params))
+                .finalize()
+                .into_bytes(),
+        )
+    }
+
+    /// Checks a request with a JSON body, whose URL carries the body's hash in a
+    /// `bodySHA256` query parameter.
+    pub fn validate_body(&self, url: &str, body: &[u8], signature: &str) -> bool {
+        let Ok(parsed) = Url::parse(url) else {
+            return false;
+        };
+        let Some((_, expected)) = parsed.query_pairs().find(|(key, _)| key == "bodySHA256") else {
+            return false;
+        };
+        let hash: String = Sha256::digest(body)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        hash.eq_ignore_ascii_case(&expected)
+            && self.validate(url, std::iter::empty::<(&str, &str)>(), signature)
+    }
+
+    fn mac(&self, payload: &str) -> Hmac<Sha1> {
+        let mut mac = Hmac::<Sha1>::new_from_slice(self.auth_token.expose_secret().as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+// The URL followed by every parameter's name and value, sorted by name
+fn signed_payload<K, V>(url: &str, params: impl IntoIterator<Item = (K, V)>) -> String
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut params: Vec<(K, V)> = params.into_iter().collect();
+    params.sort_by(|(a_key, a_value), (b_key, b_value)| {
+        (a_key.as_ref(), a_value.as_ref()).cmp(&(b_key.as_ref(), b_value.as_ref()))
+    });
+
+    let mut payload = url.to_string();
+    for (key, value) in &params {
+        payload.push_str(key.as_ref());
+        payload.push_str(value.as_ref());
+    }
+    payload
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use hmac::{Hmac, Mac};
+    use secrecy::SecretString;
+    use sha1::Sha1;
+
+    use crate::webhooks::RequestValidator;
+
+    const URL: &str = "https://mycompany.com/myapp.php?foo=1&bar=2";
+
+    fn sign(payload: &str) -> String {
+        let mut mac = Hmac::<Sha1>::new_from_slice(b"12345").unwrap();
+        mac.update(payload.as_bytes());
+        STANDARD.encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn params_are_signed_sorted_by_key() {
+        let validator = RequestValidator::new(SecretString::from("12345"));
+        let signature = sign(&format!("{URL}BodyhiFrom+254700123456"));
+
+        assert!(validator.validate(URL, [("From", "+254700123456"), ("Body", "hi")], &signature));
+        assert_eq!(
+            validator.sign(URL, [("From", "+254700123456"), ("Body", "hi")]),
+            signature
+        );
+        assert!(!validator.validate(
+            URL,
+            [("From", "+254700123456"), ("Body", "hello")],
+            &signature
+        ));
+        assert!(!validator.validate(URL, [("Body", "hi")], &signature));
+        assert!(!validator.validate(URL, [("Body", "hi")], "not base64!"));
+    }
+
+    #[test]
+    fn forged_tokens_fail() {
+        let validator = RequestValidator::new(SecretString::from("other"));
+
+        assert!(!validator.validate(URL, [("Body", "hi")], &sign(&format!("{URL}Bodyhi"))));
+    }
+
+    #[test]
+    fn json_bodies_are_checked_against_their_hash() {
+        let validator = RequestValidator::new(SecretString::from("12345"));
+        let body = br#"{"property":"value"}"#;
+        let url = "https://mycompany.com/myapp?bodySHA256=\
+            d7a04cbabf75c2d00df128c13c2b716a69597217351f54e3f3d8b715a28a9395";
+        let signature = sign(url);
+
+        assert!(validator.validate_body(url, body, &signature));
+        assert!(!validator.validate_body(url, br#"{"property":"forged"}"#, &signature));
+        assert!(!validator.validate_body(URL, body, &sign(URL)));
+    }
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::Address;
+use crate::sms::Status;
+use crate::ErrorCode;
+
+/// A message status update Twilio POSTs to a send's `StatusCallback`, deserialized
+/// from the form, e.g. with axum's `Form` extractor.
+///
+/// ```
+/// # use twilio_client::sms::Status;
+/// # use twilio_client::webhooks::MessageStatusCallback;
+/// let form = "MessageSid=SM123&AccountSid=AC123&From=%2B254700782326\
+///     &To=%2B254700123456&MessageStatus=undelivered&ErrorCode=30003";
+/// let callback: MessageStatusCallback = serde_urlencoded::from_str(form).unwrap();
+/// assert_eq!(callback.status, Status::Undelivered);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "PascalCase")]
+pub struct MessageStatusCallback {
+    pub message_sid: String,
+    pub account_sid: String,
+    pub messaging_service_sid: Option<String>,
+    /// Absent for some channels until the message has been sent.
+    pub from: Option<Address>,
+    pub to: Address,
+    #[serde(rename = "MessageStatus")]
+    pub status: Status,
+    /// Set when the status is `failed` or `undelivered`.
+    pub error_code: Option<ErrorCode>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::Address;
+    use crate::sms::Status;
+    use crate::webhooks::MessageStatusCallback;
+    use crate::ErrorCode;
+
+    #[test]
+    fn callbacks_parse_from_the_form() {
+        let form = "SmsSid=SM123&SmsStatus=failed&MessageStatus=failed&ErrorCode=30007\
+            &To=%2B254700123456&MessagingServiceSid=MG123&MessageSid=SM123\
+            &AccountSid=AC123&From=MyBrand&ApiVersion=2010-04-01";
+
+        let callback: MessageStatusCallback = serde_urlencoded::from_str(form).unwrap();
+
+        assert_eq!(callback.status, Status::Failed);
+        assert_eq!(callback.error_code, Some(ErrorCode::CarrierViolation));
+        assert_eq!(
+            callback.from,
+            Some(Address::Alphanumeric("MyBrand".to_string()))
+        );
+        assert_eq!(callback.messaging_service_sid.as_deref(), Some("MG123"));
+    }
+
+    #[test]
+    fn new_statuses_do_not_fail_the_callback() {
+        let form = "MessageSid=SM123&AccountSid=AC123&To=%2B254700123456\
+            &MessageStatus=canceled_by_carrier";
+
+        let callback: MessageStatusCallback = serde_urlencoded::from_str(form).unwrap();
+
+        assert_eq!(
+            callback.status,
+            Status::Unknown("canceled_by_carrier".to_string())
+        );
+        assert_eq!((callback.from, callback.error_code), (None, None));
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod tests {
+    use claim::assert_ok;
+    use fake::{Fake, Faker};
+    use reqwest::Url;
+    use secrecy::SecretString;
+    use std::sync::{Arc, Mutex};
+    use twilio_client::content::{
+        ApprovalRequest, ApprovalState, Client, NewContentTemplate, TemplateCategory,
+    };
+    use twilio_client::error::{ClientError, ContentVariablesError};
+    use twilio_client::upload::Upload;
+    use twilio_client::Params;
+    use wiremock::matchers::{body_json, body_string_contains, header, header_regex, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn content_client(base_url: &str) -> Client {
+        let base_url = Url::parse(base_url).expect("Failed to parse base uri");
+
+        Client::builder()
+            .base_url(base_url)
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .timeout(std::time::Duration::from_secs(1))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn variables_are_validated_against_the_cached_template() {
+        let mock_server = MockServer::start().await;
+        let client = content_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/v1/Content/HX123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sid": "HX123",
+                "types": {"twilio/text": {"body": "Your order {{1}} has shipped"}}
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert_ok!(
+            client
+                .validate_variables("HX123", &serde_json::json!({"1": "A-100"}))
+                .await
+        );
+        let mismatch = client
+            .validate_variables("HX123", &serde_json::json!({"order": "A-100"}))
+            .await;
+
+        assert!(matches!(
+            mismatch,
+            Err(ClientError::ContentVariables(
+                ContentVariablesError::Mismatch { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn create_template_sends_json_body() {
+        let mock_server = MockServer::start().await;
+        let client = content_client(&mock_server.uri());
+        let template = NewContentTemplate {
+            friendly_name: "otp".to_string(),
+            language: "en".to_string(),
+            types: serde_json::json!({"twilio/text": {"body": "Your code is {{1}}"}}),
+            ..Default::default()
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/v1/Content"))
+            .and(header("Content-Type", "application/json"))
+            .and(body_json(serde_json::json!({
+                "friendly_name": "otp",
+                "language": "en",
+                "types": {"twilio/text": {"body": "Your code is {{1}}"}}
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "sid": "HX123",
+                "friendly_name": "otp"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.create_template(&template).await;
+
+        assert_eq!(outcome.unwrap().sid, "HX123");
+    }
+
+    #[tokio::test]
+    async fn submit_for_approval_posts_to_whatsapp_approval_requests() {
+        let mock_server = MockServer::start().await;
+        let client = content_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .and(path("/v1/Content/HX123/ApprovalRequests/whatsapp"))
+            .and(body_json(serde_json::json!({
+                "name": "otp_code",
+                "category": "AUTHENTICATION"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "name": "otp_code",
+                "category": "AUTHENTICATION",
+                "status": "received"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let request = ApprovalRequest {
+            name: "otp_code".to_string(),
+            category: TemplateCategory::Authentication,
+        };
+        let outcome = client.submit_for_approval("HX123", &request).await;
+
+        assert_ok!(outcome);
+    }
+
+    #[tokio::test]
+    async fn approval_status_parses_rejections() {
+        let mock_server = MockServer::start().await;
+        let client = content_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/v1/Content/HX123/ApprovalRequests"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sid": "HX123",
+                "whatsapp": {
+                    "name": "otp_code",
+                    "status": "rejected",
+                    "rejection_reason": "Invalid format"
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let whatsapp = client
+            .approval_status("HX123")
+            .await
+            .unwrap()
+            .whatsapp
+            .unwrap();
+
+        assert_eq!(whatsapp.status, ApprovalState::Rejected);
+        assert_eq!(whatsapp.rejection_reason.as_deref(), Some("Invalid format"));
+    }
+
+    #[tokio::test]
+    async fn raw_upload_sends_multipart_and_reports_progress() {
+        let mock_server = MockServer::start().await;
+        let client = content_client(&mock_server.uri());
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let recorded = progress.clone();
+
+        Mock::given(method("POST"))
+            .and(path("/v2/RegulatoryCompliance/SupportingDocuments"))
+            .and(header_regex("Content-Type", "^multipart/form-data"))
+            .and(body_string_contains("FriendlyName"))
+            .respond_with(
+                ResponseTemplate::new(201).set_body_json(serde_json::json!({"sid": "RD1"})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut fields = Params::new();
+        fields.insert("FriendlyName", "passport");
+        let upload = Upload::new("File", "passport.pdf", "application/pdf", vec![7; 100_000])
+            .fields(fields)
+            .progress(Arc::new(move |sent, total| {
+                recorded.lock().unwrap().push((sent, total))
+            }));
+        let outcome = client
+            .raw_upload(
+                reqwest::Method::POST,
+                "/v2/RegulatoryCompliance/SupportingDocuments",
+                upload,
+            )
+            .await;
+
+        assert_eq!(outcome.unwrap()["sid"], "RD1");
+        assert_eq!(progress.lock().unwrap().last(), Some(&(100_000, 100_000)));
+    }
+}
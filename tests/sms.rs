@@ -1,13 +1,29 @@
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use chrono::TimeZone;
     use claim::{assert_err, assert_ok};
     use fake::faker::lorem::en::Sentence;
     use fake::{Fake, Faker};
     use reqwest::Url;
     use secrecy::{ExposeSecret, SecretString};
-    use twilio_client::sms::{Client, SendSmsResponse};
-    use twilio_client::Phone;
-    use wiremock::matchers::{any, header, method, path};
+    use twilio_client::clock::{Clock, MockClock};
+    use twilio_client::error::ClientError;
+    use twilio_client::limiter::{ConcurrencyLimiter, OverloadPolicy, Priority};
+    use twilio_client::retry::RetryPolicy;
+    use twilio_client::sampling::{BodySampler, SampleSink, SampledExchange};
+    use twilio_client::sms::{
+        Client, Direction, FallbackStep, FeedbackOutcome, InMemorySuppressionList, LineType,
+        MagicNumber, MessageBuilder, PooledSender, PreflightPolicy, PreflightReason, PriceSign,
+        Receipt, ReceiptStore, RotationStrategy, SendOptions, SendSmsResponse, SenderHealthPolicy,
+        SenderPool, SplitPolicy, Status, Verdict, WhatsAppDirectory, WhatsAppFallback,
+        WhatsAppSandbox,
+    };
+    use twilio_client::{ErrorCode, MessageFilter, Params, Phone};
+    use wiremock::matchers::{
+        any, body_string, body_string_contains, header, method, path, query_param,
+    };
     use wiremock::{Mock, MockServer, Request, ResponseTemplate};
 
     fn generate_phone() -> (String, String) {
@@ -123,19 +139,1991 @@ mod tests {
         assert_err!(outcome);
     }
 
-    struct SendSmsBodyMatcher;
+    #[tokio::test]
+    async fn raw_request_sends_params_as_query_for_get() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, _) = sms_client(&mock_server.uri());
 
-    impl wiremock::Match for SendSmsBodyMatcher {
-        fn matches(&self, request: &Request) -> bool {
-            // Try to parse the body as a JSON value
-            let result: Result<serde_json::Value, _> = serde_urlencoded::from_bytes(&request.body);
-            if let Ok(body) = result {
-                // Check that all the mandatory fields are populated
-                body.get("From").is_some() && body.get("To").is_some() && body.get("Body").is_some()
-            } else {
-                // If parsing failed, do not match the request
-                false
-            }
+        Mock::given(method("GET"))
+            .and(path("/v1/Custom/Resource.json"))
+            .and(query_param("PageSize", "20"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"sid": "X"})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut params = Params::new();
+        params.insert("PageSize", "20");
+        let outcome = sms_client
+            .raw_request(reqwest::Method::GET, "/v1/Custom/Resource.json", &params)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome["sid"], "X");
+    }
+
+    #[tokio::test]
+    async fn send_sms_fails_if_the_response_is_too_large() {
+        let mock_server = MockServer::start().await;
+        let sms_client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .max_response_size(16)
+            .build()
+            .unwrap();
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_string("x".repeat(64)))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = sms_client
+            .send(&phone(), content().as_ref(), None, None)
+            .await;
+
+        assert!(matches!(outcome, Err(ClientError::ResponseTooLarge(16))));
+    }
+
+    #[tokio::test]
+    async fn fetch_returns_the_message_status() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}/Messages/SM123.json",
+                account_sid.expose_secret()
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sid": "SM123",
+                "status": "delivered",
+                "date_sent": "Wed, 18 Aug 2010 20:01:40 +0000",
+                "direction": "outbound-api",
+                "price": "-0.00750",
+                "price_unit": "USD",
+                "num_segments": "2",
+                "num_media": "0",
+                "messaging_service_sid": "MG123",
+                "uri": "/2010-04-01/Accounts/AC123/Messages/SM123.json",
+                "subresource_uris": {
+                    "media": "/2010-04-01/Accounts/AC123/Messages/SM123/Media.json"
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let message = sms_client.fetch("SM123").await.unwrap();
+
+        assert_eq!(message.status, Some(Status::Delivered));
+        assert!(message.date_sent.is_some());
+        assert_eq!(message.direction, Some(Direction::OutboundApi));
+        let cost = message.cost().unwrap();
+        assert_eq!(
+            (cost.amount.as_str(), cost.sign),
+            ("0.0075", PriceSign::Charge)
+        );
+        assert_eq!(
+            (message.num_segments, message.num_media),
+            (Some(2), Some(0))
+        );
+        assert_eq!(message.messaging_service_sid.as_deref(), Some("MG123"));
+        assert!(message.uri.unwrap().ends_with("/Messages/SM123.json"));
+        assert!(message.subresource_uris.unwrap().contains_key("media"));
+    }
+
+    #[tokio::test]
+    async fn read_only_clients_fetch_but_never_send_or_delete() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .read_only(true)
+            .build()
+            .unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/2010-04-01/Accounts/AC123/Messages/SM123.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"sid": "SM123", "status": "delivered"})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        for mutating in ["POST", "DELETE"] {
+            Mock::given(method(mutating))
+                .respond_with(ResponseTemplate::new(201))
+                .expect(0)
+                .mount(&mock_server)
+                .await;
+        }
+
+        assert_ok!(client.fetch("SM123").await);
+        assert!(matches!(
+            client.send(&phone(), "hello", None, None).await,
+            Err(ClientError::ReadOnlyMode)
+        ));
+        assert!(matches!(
+            client.delete("SM123").await,
+            Err(ClientError::ReadOnlyMode)
+        ));
+    }
+
+    #[test]
+    fn statuses_added_by_twilio_later_still_parse() {
+        let message: SendSmsResponse =
+            serde_json::from_value(serde_json::json!({ "sid": "SM123", "status": "in_transit" }))
+                .unwrap();
+
+        let status = message.status.unwrap();
+        assert_eq!(status, Status::Unknown("in_transit".to_string()));
+        assert_eq!(status.as_str(), "in_transit");
+        assert!(!status.is_final());
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            serde_json::json!("in_transit")
+        );
+        assert_eq!(
+            serde_json::to_value(Status::PartiallyDelivered).unwrap(),
+            serde_json::json!("partially_delivered")
+        );
+    }
+
+    #[tokio::test]
+    async fn fetched_statuses_count_towards_sender_health() {
+        let mock_server = MockServer::start().await;
+        let pool = SenderPool::new(
+            vec![PooledSender::new(phone())],
+            RotationStrategy::WeightedRoundRobin,
+        )
+        .health_policy(SenderHealthPolicy::new().min_samples(1));
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender_pool(pool)
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .build()
+            .unwrap();
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sid": "SM123",
+                "from": phone().e164_number(),
+                "status": "undelivered",
+                "error_code": 30007
+            })))
+            .mount(&mock_server)
+            .await;
+
+        assert_ok!(client.fetch("SM123").await);
+
+        let health = &client.sender_pool().unwrap().health()[0];
+        assert_eq!((health.score, health.filtered), (Some(0.0), 1));
+        assert!(health.quarantined_until.is_some());
+    }
+
+    #[tokio::test]
+    async fn a_message_counts_towards_sender_health_once() {
+        let mock_server = MockServer::start().await;
+        let pool = SenderPool::new(
+            vec![PooledSender::new(phone())],
+            RotationStrategy::WeightedRoundRobin,
+        )
+        .health_policy(SenderHealthPolicy::new());
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender_pool(pool)
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .build()
+            .unwrap();
+        let fetched = |status| {
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sid": "SM123",
+                "from": phone().e164_number(),
+                "status": status,
+            }))
+        };
+
+        Mock::given(method("GET"))
+            .respond_with(fetched("delivered"))
+            .up_to_n_times(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(fetched("read"))
+            .mount(&mock_server)
+            .await;
+
+        for _ in 0..3 {
+            assert_ok!(client.fetch("SM123").await);
+        }
+
+        let health = &client.sender_pool().unwrap().health()[0];
+        assert_eq!((health.score, health.samples), (Some(1.0), 1));
+    }
+
+    #[tokio::test]
+    async fn only_accepted_sends_count_against_a_senders_daily_cap() {
+        let mock_server = MockServer::start().await;
+        let client = |dry_run| {
+            let pool = SenderPool::new(
+                vec![PooledSender::new(phone()).daily_cap(1)],
+                RotationStrategy::WeightedRoundRobin,
+            );
+            Client::builder()
+                .base_url(Url::parse(&mock_server.uri()).unwrap())
+                .sender_pool(pool)
+                .account_sid(SecretString::from(Faker.fake::<String>()))
+                .auth_token(SecretString::from(Faker.fake::<String>()))
+                .dry_run(dry_run)
+                .build()
+                .unwrap()
+        };
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "code": 21211,
+                "message": "Invalid 'To' Phone Number",
+                "status": 400
+            })))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let dry_run = client(true);
+        assert_ok!(dry_run.send(&phone(), "hello", None, None).await);
+        assert_ok!(dry_run.send(&phone(), "hello", None, None).await);
+
+        let client = client(false);
+        assert_err!(client.send(&phone(), "hello", None, None).await);
+        assert_ok!(client.send(&phone(), "hello", None, None).await);
+        assert!(matches!(
+            client.send(&phone(), "hello", None, None).await,
+            Err(ClientError::NoSenderAvailable)
+        ));
+    }
+
+    #[tokio::test]
+    async fn concurrent_sends_hold_a_senders_daily_cap() {
+        let mock_server = MockServer::start().await;
+        let pool = SenderPool::new(
+            vec![PooledSender::new(phone()).daily_cap(2)],
+            RotationStrategy::WeightedRoundRobin,
+        );
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender_pool(pool)
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .build()
+            .unwrap();
+
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(SendSmsResponse::default())
+                    .set_delay(std::time::Duration::from_millis(200)),
+            )
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let to = phone();
+        let outcomes =
+            futures_util::future::join_all((0..5).map(|_| client.send(&to, "hello", None, None)))
+                .await;
+
+        assert_eq!(outcomes.iter().filter(|outcome| outcome.is_ok()).count(), 2);
+        assert!(outcomes
+            .iter()
+            .filter(|outcome| outcome.is_err())
+            .all(|outcome| matches!(outcome, Err(ClientError::NoSenderAvailable))));
+    }
+
+    #[tokio::test]
+    async fn fetch_decodes_the_failure_reason() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}/Messages/SM123.json",
+                account_sid.expose_secret()
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sid": "SM123",
+                "status": "undelivered",
+                "error_code": 30006,
+                "error_message": "Landline or unreachable carrier"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let message = sms_client.fetch("SM123").await.unwrap();
+
+        assert_eq!(
+            message.error(),
+            Some(ErrorCode::LandlineOrUnreachableCarrier)
+        );
+        assert_eq!(
+            message.failure_reason().as_deref(),
+            Some("landline or unreachable carrier")
+        );
+    }
+
+    #[tokio::test]
+    async fn slow_fetches_are_hedged_with_a_second_request() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .timeout(std::time::Duration::from_secs(5))
+            .hedge_after(std::time::Duration::from_millis(100))
+            .build()
+            .unwrap();
+        let fetched = |status| {
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"sid": "SM123", "status": status}))
+        };
+
+        Mock::given(method("GET"))
+            .respond_with(fetched("sent").set_delay(std::time::Duration::from_secs(3)))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(fetched("delivered"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let started = std::time::Instant::now();
+        let message = client.fetch("SM123").await.unwrap();
+
+        assert_eq!(message.status, Some(Status::Delivered));
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn wait_for_final_status_polls_until_delivered() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, _) = sms_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"status": "sent"})),
+            )
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"status": "delivered"})),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let lifecycle = sms_client
+            .wait_for_final_status(
+                "SM123",
+                std::time::Duration::from_millis(10),
+                std::time::Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(lifecycle.history(), [Status::Sent, Status::Delivered]);
+    }
+
+    #[tokio::test]
+    async fn wait_for_final_status_waits_on_the_client_clock() {
+        let mock_server = MockServer::start().await;
+        let start = chrono::Utc.with_ymd_and_hms(2024, 5, 6, 7, 0, 0).unwrap();
+        let clock = MockClock::new(start);
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .clock(Arc::new(clock.clone()))
+            .build()
+            .unwrap();
+
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"status": "sent"})),
+            )
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let lifecycle = client
+            .wait_for_final_status(
+                "SM123",
+                std::time::Duration::from_secs(60),
+                std::time::Duration::from_secs(150),
+            )
+            .await
+            .unwrap();
+
+        assert!(!lifecycle.is_final());
+        assert_eq!(clock.now(), start + chrono::Duration::seconds(120));
+    }
+
+    #[tokio::test]
+    async fn list_media_returns_typed_media() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+        let media_uri = format!(
+            "/2010-04-01/Accounts/{}/Messages/MM123/Media/ME456.json",
+            account_sid.expose_secret()
+        );
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}/Messages/MM123/Media.json",
+                account_sid.expose_secret()
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "media_list": [{
+                    "sid": "ME456",
+                    "content_type": "image/jpeg",
+                    "parent_sid": "MM123",
+                    "uri": media_uri,
+                }],
+                "page": 0,
+                "page_size": 50,
+                "next_page_uri": null,
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let page = assert_ok!(sms_client.list_media("MM123").await);
+
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].content_type, "image/jpeg");
+        assert_eq!(
+            page.items[0].content_uri(),
+            media_uri.strip_suffix(".json").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_media_targets_the_media_resource() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+
+        Mock::given(method("DELETE"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}/Messages/MM123/Media/ME456.json",
+                account_sid.expose_secret()
+            )))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert_ok!(sms_client.delete_media("MM123", "ME456").await);
+    }
+
+    #[tokio::test]
+    async fn confirm_feedback_posts_a_confirmed_outcome() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}/Messages/SM123/Feedback.json",
+                account_sid.expose_secret()
+            )))
+            .and(body_string("Outcome=confirmed"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "message_sid": "SM123",
+                "outcome": "confirmed",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let feedback = assert_ok!(sms_client.confirm_feedback("SM123").await);
+        assert_eq!(feedback.outcome, FeedbackOutcome::Confirmed);
+    }
+
+    #[tokio::test]
+    async fn caller_id_validation_returns_the_code_to_enter() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}/OutgoingCallerIds.json",
+                account_sid.expose_secret()
+            )))
+            .and(body_string_contains("PhoneNumber=%2B254700782326"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "phone_number": "+254700782326",
+                "validation_code": "111111",
+                "call_sid": "CA123",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let validation = assert_ok!(
+            sms_client
+                .request_caller_id_validation(&phone(), None)
+                .await
+        );
+        assert_eq!(validation.validation_code, "111111");
+    }
+
+    #[tokio::test]
+    async fn archive_walks_each_day_page_by_page() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+        let messages_path = format!(
+            "/2010-04-01/Accounts/{}/Messages.json",
+            account_sid.expose_secret()
+        );
+        let list = |sids: &[&str], next: Option<String>| {
+            let messages: Vec<_> = sids
+                .iter()
+                .map(|sid| serde_json::json!({"sid": sid}))
+                .collect();
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "messages": messages,
+                "next_page_uri": next,
+            }))
+        };
+
+        Mock::given(query_param("Page", "1"))
+            .respond_with(list(&["SM2"], None))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(query_param("DateSent", "2024-01-01"))
+            .respond_with(list(&["SM1"], Some(format!("{messages_path}?Page=1"))))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(query_param("DateSent", "2024-01-02"))
+            .respond_with(list(&["SM3"], None))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let start = chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let mut archive = sms_client.archive(start, end);
+        let mut sids = Vec::new();
+        while let Some(batch) = archive.next_batch().await.unwrap() {
+            sids.extend(batch.messages.into_iter().filter_map(|m| m.sid));
         }
+
+        assert_eq!(sids, ["SM1", "SM2", "SM3"]);
+        assert_eq!(archive.checkpoint().date, end.succ_opt().unwrap());
+    }
+
+    #[tokio::test]
+    async fn send_message_sends_the_builder_fields() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, _) = sms_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("Body=Shipped"))
+            .and(body_string_contains(
+                "MediaUrl=https%3A%2F%2Fexample.com%2Fa.jpg",
+            ))
+            .and(body_string_contains("ProvideFeedback=true"))
+            .and(body_string_contains("SmartEncoded=true"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let message = MessageBuilder::new(&phone(), "Shipped")
+            .media_url("https://example.com/a.jpg")
+            .provide_feedback(true)
+            .smart_encoded(true);
+
+        assert_ok!(sms_client.send_message(&message).await);
+    }
+
+    #[tokio::test]
+    async fn send_split_numbers_each_part_and_attaches_media_once() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, _) = sms_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(SendSmsResponse::default()))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let message = MessageBuilder::new(&phone(), "word ".repeat(400))
+            .media_url("https://example.com/a.jpg");
+        let responses = sms_client.send_split(&message).await.unwrap();
+
+        assert_eq!(responses.len(), 2);
+        let bodies: Vec<std::collections::HashMap<String, String>> = mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| serde_urlencoded::from_bytes(&request.body).unwrap())
+            .collect();
+        assert!(bodies[0]["Body"].starts_with("(1/2) word"));
+        assert!(bodies[1]["Body"].starts_with("(2/2) word"));
+        assert!(bodies
+            .iter()
+            .all(|body| body["Body"].chars().count() <= 1600));
+        assert!(bodies[0].contains_key("MediaUrl"));
+        assert!(!bodies[1].contains_key("MediaUrl"));
+    }
+
+    #[tokio::test]
+    async fn send_split_with_keeps_each_part_to_one_segment() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, _) = sms_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(SendSmsResponse::default()))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let message = MessageBuilder::new(&phone(), "Habari yako, rafiki? ".repeat(20));
+        let policy = SplitPolicy::new().delay(std::time::Duration::from_millis(20));
+        let responses = sms_client.send_split_with(&message, &policy).await.unwrap();
+
+        assert_eq!(responses.len(), 3);
+        let bodies: Vec<String> = mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| {
+                serde_urlencoded::from_bytes::<std::collections::HashMap<String, String>>(
+                    &request.body,
+                )
+                .unwrap()["Body"]
+                    .clone()
+            })
+            .collect();
+        assert!(bodies[2].starts_with("(3/3) "));
+        assert!(bodies
+            .iter()
+            .all(|body| twilio_client::sms::segments::count(body).segments == 1));
+    }
+
+    #[tokio::test]
+    async fn template_messages_send_content_sid_and_variables() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, _) = sms_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("ContentSid=HX123"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let message = MessageBuilder::template(&phone(), "HX123", [("1", "A-100")]);
+        assert_ok!(sms_client.send_message(&message).await);
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let body: Params = serde_urlencoded::from_bytes::<Vec<(String, String)>>(&requests[0].body)
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(body.get("ContentVariables"), Some(r#"{"1":"A-100"}"#));
+        assert!(!body.contains_key("Body"));
+    }
+
+    #[tokio::test]
+    async fn cancel_confirms_the_cancellation() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, _) = sms_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .and(body_string("Status=canceled"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"sid": "SM123", "status": "canceled"})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let resp = assert_ok!(sms_client.cancel("SM123").await);
+        assert!(resp.is_canceled());
+    }
+
+    #[tokio::test]
+    async fn redact_posts_an_empty_body() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}/Messages/SM123.json",
+                account_sid.expose_secret()
+            )))
+            .and(body_string("Body="))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"sid": "SM123", "body": ""})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let resp = assert_ok!(sms_client.redact("SM123").await);
+        assert_eq!(resp.body.as_deref(), Some(""));
+    }
+
+    #[tokio::test]
+    async fn delete_accepts_no_content() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+
+        Mock::given(method("DELETE"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}/Messages/SM123.json",
+                account_sid.expose_secret()
+            )))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert_ok!(sms_client.delete("SM123").await);
+    }
+
+    #[tokio::test]
+    async fn purge_user_deletes_messages_both_ways_and_reports_failures() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+        let account = account_sid.expose_secret().to_string();
+        let user = phone();
+
+        for (key, sids) in [("To", ["SM1", "SM2"]), ("From", ["SM2", "SM3"])] {
+            Mock::given(method("GET"))
+                .and(query_param(key, user.e164_number()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "messages": [{"sid": sids[0]}, {"sid": sids[1]}],
+                    "next_page_uri": null
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+        for sid in ["SM1", "SM2"] {
+            Mock::given(method("DELETE"))
+                .and(path(format!(
+                    "/2010-04-01/Accounts/{account}/Messages/{sid}.json"
+                )))
+                .respond_with(ResponseTemplate::new(204))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+        Mock::given(method("DELETE"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{account}/Messages/SM3.json"
+            )))
+            .respond_with(ResponseTemplate::new(409).set_body_json(serde_json::json!({
+                "code": 20009,
+                "message": "Cannot delete message in progress"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let report = sms_client.purge_user(&user).await.unwrap();
+
+        assert_eq!(report.messages_deleted, ["SM1", "SM2"]);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].resource, "SM3");
+        assert!(!report.is_complete());
+    }
+
+    #[tokio::test]
+    async fn list_sends_the_filter_as_query_params() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}/Messages.json",
+                account_sid.expose_secret()
+            )))
+            .and(query_param("To", "+254700782326"))
+            .and(query_param("DateSent>", "2024-01-01"))
+            .and(query_param("PageSize", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "messages": [{"sid": "SM1"}, {"sid": "SM2"}],
+                "page": 0,
+                "page_size": 2,
+                "next_page_uri": null
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let filter = MessageFilter::new()
+            .to(&phone())
+            .sent_after(chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+            .page_size(2);
+        let page = sms_client.list(&filter).await.unwrap();
+
+        assert_eq!(page.items.len(), 2);
+        assert!(!page.has_next());
+    }
+
+    #[tokio::test]
+    async fn list_stream_follows_every_page() {
+        use futures_util::TryStreamExt;
+
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+        let messages_path = format!(
+            "/2010-04-01/Accounts/{}/Messages.json",
+            account_sid.expose_secret()
+        );
+
+        Mock::given(path(messages_path.as_str()))
+            .and(query_param("Page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "messages": [{"sid": "SM3"}],
+                "page": 1,
+                "page_size": 2,
+                "next_page_uri": null
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(path(messages_path.as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "messages": [{"sid": "SM1"}, {"sid": "SM2"}],
+                "page": 0,
+                "page_size": 2,
+                "next_page_uri": format!("{messages_path}?Page=1")
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let filter = MessageFilter::new();
+        let messages: Vec<_> = sms_client.list_stream(&filter).try_collect().await.unwrap();
+
+        let sids: Vec<_> = messages.iter().filter_map(|m| m.sid.as_deref()).collect();
+        assert_eq!(sids, ["SM1", "SM2", "SM3"]);
+    }
+
+    #[tokio::test]
+    async fn validate_configuration_flags_a_sender_without_sms() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+        let sender = phone().e164_number();
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}.json",
+                account_sid.expose_secret()
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("PhoneNumber", sender.as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "incoming_phone_numbers": [{
+                    "sid": "PN1",
+                    "phone_number": sender,
+                    "capabilities": {"voice": true, "sms": false, "mms": false}
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let report = sms_client.validate_configuration().await;
+
+        assert!(!report.is_ok());
+        assert_eq!(report.findings().len(), 1);
+        assert!(report.findings()[0].message.contains("can't send SMS"));
+    }
+
+    #[tokio::test]
+    async fn healthcheck_reports_rejected_credentials() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}.json",
+                account_sid.expose_secret()
+            )))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "code": 20003,
+                "message": "Authenticate"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let healthcheck = sms_client.spawn_healthcheck(std::time::Duration::from_secs(60));
+        let mut health = healthcheck.subscribe();
+        health.changed().await.unwrap();
+
+        assert!(!health.borrow().is_healthy());
+        assert_eq!(healthcheck.latest(), *health.borrow());
+        assert_eq!(healthcheck.name(), "Twilio SMS: Health check");
+        assert!(!healthcheck.is_finished());
+        healthcheck.stop().await;
+    }
+
+    #[tokio::test]
+    async fn fetch_incoming_number_parses_capabilities() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/2010-04-01/Accounts/{}/IncomingPhoneNumbers/PN123.json",
+                account_sid.expose_secret()
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sid": "PN123",
+                "phone_number": "+254700782326",
+                "capabilities": {"voice": true, "SMS": true, "MMS": false, "fax": false}
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let number = sms_client.fetch_incoming_number("PN123").await.unwrap();
+
+        assert!(number.supports_sms());
+        assert!(!number.supports_mms());
+    }
+
+    #[tokio::test]
+    async fn next_page_follows_next_page_uri() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, account_sid) = sms_client(&mock_server.uri());
+        let numbers_path = format!(
+            "/2010-04-01/Accounts/{}/IncomingPhoneNumbers.json",
+            account_sid.expose_secret()
+        );
+
+        Mock::given(method("GET"))
+            .and(path(numbers_path.clone()))
+            .and(query_param("Page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "incoming_phone_numbers": [{"sid": "PN2", "phone_number": "+254700123456"}],
+                "page": 1,
+                "page_size": 1,
+                "next_page_uri": null
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(numbers_path.clone()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "incoming_phone_numbers": [{"sid": "PN1", "phone_number": "+254700782326"}],
+                "page": 0,
+                "page_size": 1,
+                "next_page_uri": format!("{numbers_path}?Page=1&PageSize=1")
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let first = sms_client.list_incoming_numbers().await.unwrap();
+        let second = sms_client.next_page(&first).await.unwrap().unwrap();
+
+        assert_eq!(second.items[0].sid, "PN2");
+        assert!(sms_client.next_page(&second).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn send_sms_binds_to_the_configured_local_address() {
+        let mock_server = MockServer::start().await;
+        let local_address = "127.0.0.1".parse().unwrap();
+        let sms_client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .local_address(local_address)
+            .build()
+            .unwrap();
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = sms_client
+            .send(&phone(), content().as_ref(), None, None)
+            .await;
+
+        assert_ok!(outcome);
+        assert_eq!(sms_client.local_address(), Some(local_address));
+    }
+
+    #[tokio::test]
+    async fn send_sms_repeats_media_url_for_each_attachment() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, _) = sms_client(&mock_server.uri());
+
+        Mock::given(body_string_contains(
+            "MediaUrl=https%3A%2F%2Fa.example%2F1.png&MediaUrl=https%3A%2F%2Fa.example%2F2.png",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(SendSmsResponse::default()))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+        let media = vec![
+            "https://a.example/1.png".to_string(),
+            "https://a.example/2.png".to_string(),
+        ];
+        let outcome = sms_client
+            .send(&phone(), content().as_ref(), None, Some(media))
+            .await;
+
+        assert_ok!(outcome);
+    }
+
+    #[tokio::test]
+    async fn messaging_service_replaces_the_from_number() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .messaging_service_sid(SecretString::from("MG123"))
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .build()
+            .unwrap();
+
+        Mock::given(body_string_contains("MessagingServiceSid=MG123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert_ok!(client.send(&phone(), "hello", None, None).await);
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let body: Params = serde_urlencoded::from_bytes::<Vec<(String, String)>>(&requests[0].body)
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert!(!body.contains_key("From"));
+    }
+
+    #[tokio::test]
+    async fn shorten_urls_requires_a_messaging_service() {
+        let mock_server = MockServer::start().await;
+        let (sms_client, _) = sms_client(&mock_server.uri());
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(SendSmsResponse::default()))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let message =
+            MessageBuilder::new(&phone(), "Track it at https://example.com/o/1").shorten_urls(true);
+        let outcome = sms_client.send_message(&message).await;
+
+        assert!(matches!(outcome, Err(ClientError::Configuration(_))));
+    }
+
+    #[tokio::test]
+    async fn short_codes_are_sent_as_the_from_address() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .short_code("894546")
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .build()
+            .unwrap();
+
+        Mock::given(body_string_contains("From=894546"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert_ok!(client.send(&phone(), "hello", None, None).await);
+    }
+
+    #[test]
+    fn short_codes_must_be_digits() {
+        let client = Client::builder()
+            .base_url(Url::parse("http://localhost").unwrap())
+            .short_code("+1894546")
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .build();
+
+        assert!(matches!(client, Err(ClientError::Configuration(_))));
+    }
+
+    #[derive(Debug, Default)]
+    struct SampleRecorder(std::sync::Mutex<Vec<SampledExchange>>);
+
+    impl SampleSink for SampleRecorder {
+        fn record(&self, exchange: SampledExchange) {
+            self.0.lock().unwrap().push(exchange);
+        }
+    }
+
+    #[tokio::test]
+    async fn sampled_exchanges_reach_the_sink_redacted() {
+        let mock_server = MockServer::start().await;
+        let recorder = Arc::new(SampleRecorder::default());
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .sampler(BodySampler::new(recorder.clone(), 1.0))
+            .build()
+            .unwrap();
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "sid": "SM1",
+                "to": "+254700782326",
+                "status": "queued"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        assert_ok!(client.send(&phone(), "Your code is 1234", None, None).await);
+
+        let exchanges = recorder.0.lock().unwrap();
+        assert_eq!(exchanges.len(), 1);
+        assert_eq!(exchanges[0].status, 201);
+        let request_body = exchanges[0].request_body.as_deref().unwrap();
+        assert!(!request_body.contains("1234") && !request_body.contains("254700782326"));
+        assert!(!exchanges[0].response_body.contains("254700782326"));
+        assert!(exchanges[0].response_body.contains("SM1"));
+    }
+
+    #[tokio::test]
+    async fn simulated_test_credential_errors_decode_like_real_ones() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(MagicNumber::ValidSender.phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .build()
+            .unwrap();
+
+        Mock::given(body_string_contains("To=%2B15005550004"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "code": 21610,
+                "message": "Attempt to send to unsubscribed recipient",
+                "more_info": "https://www.twilio.com/docs/errors/21610",
+                "status": 400
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let to = MagicNumber::BlockedRecipient.phone();
+        let err = client.send(&to, "hello", None, None).await.unwrap_err();
+
+        assert_eq!(err.error_code(), Some(ErrorCode::Unsubscribed));
+        assert_eq!(
+            err.error_code(),
+            MagicNumber::BlockedRecipient.simulated_error()
+        );
+    }
+
+    #[tokio::test]
+    async fn otp_preset_expires_messages_unless_the_send_overrides_it() {
+        let mock_server = MockServer::start().await;
+        let client = Client::for_otp()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .build()
+            .unwrap();
+
+        Mock::given(body_string_contains("ValidityPeriod=600"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(body_string_contains("ValidityPeriod=60"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert_ok!(client.send(&phone(), "Your code is 1234", None, None).await);
+        let message = MessageBuilder::new(&phone(), "Your code is 1234")
+            .validity_period(std::time::Duration::from_secs(60));
+        assert_ok!(client.send_message(&message).await);
+    }
+
+    #[tokio::test]
+    async fn presets_send_a_timed_out_message_only_once() {
+        for preset in [
+            Client::for_otp(),
+            Client::for_bulk(ConcurrencyLimiter::new(1)),
+        ] {
+            let mock_server = MockServer::start().await;
+            let client = preset
+                .base_url(Url::parse(&mock_server.uri()).unwrap())
+                .sender(phone())
+                .account_sid(SecretString::from(Faker.fake::<String>()))
+                .auth_token(SecretString::from(Faker.fake::<String>()))
+                .timeout(std::time::Duration::from_millis(200))
+                .build()
+                .unwrap();
+
+            Mock::given(method("POST"))
+                .respond_with(
+                    ResponseTemplate::new(201)
+                        .set_body_json(SendSmsResponse::default())
+                        .set_delay(std::time::Duration::from_secs(1)),
+                )
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+
+            let outcome = client.send(&phone(), "Your code is 1234", None, None).await;
+
+            assert!(matches!(outcome, Err(ClientError::Timeout(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn send_with_options_schedules_the_message() {
+        let mock_server = MockServer::start().await;
+        let clock = MockClock::new(chrono::Utc.with_ymd_and_hms(2024, 5, 6, 7, 0, 0).unwrap());
+        let sms_client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .messaging_service_sid(SecretString::from("MG123"))
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .clock(Arc::new(clock.clone()))
+            .build()
+            .unwrap();
+
+        Mock::given(body_string_contains("ScheduleType=fixed"))
+            .and(body_string_contains("SendAt=2024-05-06T08%3A00%3A00Z"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let options = SendOptions::new().send_at(clock.now() + chrono::Duration::hours(1));
+        let outcome = sms_client
+            .send_with_options(&phone(), content().as_ref(), &options)
+            .await;
+
+        assert_ok!(outcome);
+    }
+
+    #[tokio::test]
+    async fn invalid_schedules_are_rejected_before_sending() {
+        let mock_server = MockServer::start().await;
+        let (sender_client, _) = sms_client(&mock_server.uri());
+        let clock = MockClock::default();
+        let service_client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .messaging_service_sid(SecretString::from("MG123"))
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .clock(Arc::new(clock.clone()))
+            .build()
+            .unwrap();
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(SendSmsResponse::default()))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let in_an_hour = SendOptions::new().send_at(clock.now() + chrono::Duration::hours(1));
+        let too_soon = SendOptions::new().send_at(clock.now() + chrono::Duration::minutes(5));
+        let too_late = SendOptions::new().send_at(clock.now() + chrono::Duration::days(36));
+        for (client, options) in [
+            (&sender_client, &in_an_hour),
+            (&service_client, &too_soon),
+            (&service_client, &too_late),
+        ] {
+            let outcome = client.send_with_options(&phone(), "hello", options).await;
+            assert!(matches!(outcome, Err(ClientError::Configuration(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn per_message_status_callback_overrides_the_client_default() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .status_callback(Url::parse("https://example.com/default").unwrap())
+            .build()
+            .unwrap();
+
+        Mock::given(body_string_contains(
+            "StatusCallback=https%3A%2F%2Fexample.com%2Fdefault",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(SendSmsResponse::default()))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+        Mock::given(body_string_contains(
+            "StatusCallback=https%3A%2F%2Fexample.com%2Fotp",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(SendSmsResponse::default()))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+        let options =
+            SendOptions::new().status_callback(Url::parse("https://example.com/otp").unwrap());
+        assert_ok!(client.send(&phone(), "hello", None, None).await);
+        assert_ok!(client.send_with_options(&phone(), "code", &options).await);
+    }
+
+    #[tokio::test]
+    async fn concurrency_limiter_serializes_requests_across_clients() {
+        let mock_server = MockServer::start().await;
+        let limiter = ConcurrencyLimiter::new(1);
+        let client = |limiter: ConcurrencyLimiter| {
+            Client::builder()
+                .base_url(Url::parse(&mock_server.uri()).unwrap())
+                .sender(phone())
+                .account_sid(SecretString::from(Faker.fake::<String>()))
+                .auth_token(SecretString::from(Faker.fake::<String>()))
+                .concurrency_limiter(limiter)
+                .build()
+                .unwrap()
+        };
+        let (first, second) = (client(limiter.clone()), client(limiter.clone()));
+        let response = ResponseTemplate::new(200)
+            .set_body_json(SendSmsResponse::default())
+            .set_delay(std::time::Duration::from_millis(200));
+
+        Mock::given(any())
+            .respond_with(response)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let to = phone();
+        let started = std::time::Instant::now();
+        let (a, b) = tokio::join!(
+            first.send(&to, "one", None, None),
+            second.send(&to, "two", None, None)
+        );
+
+        assert_ok!(a);
+        assert_ok!(b);
+        assert!(started.elapsed() >= std::time::Duration::from_millis(400));
+        assert_eq!(limiter.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn high_priority_messages_use_reserved_slots() {
+        let mock_server = MockServer::start().await;
+        let limiter = ConcurrencyLimiter::new(2)
+            .reserve_for_high(1)
+            .on_overload(Priority::Normal, OverloadPolicy::Reject);
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .concurrency_limiter(limiter.clone())
+            .build()
+            .unwrap();
+        let response = ResponseTemplate::new(200)
+            .set_body_json(SendSmsResponse::default())
+            .set_delay(std::time::Duration::from_millis(300));
+
+        Mock::given(any())
+            .respond_with(response)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let to = phone();
+        let bulk = client.send(&to, "newsletter", None, None);
+        let otp = async {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            let shed = client.send(&to, "newsletter", None, None).await;
+            let code = MessageBuilder::new(&to, "Your code is 1234").priority(Priority::High);
+            (shed, client.send_message(&code).await)
+        };
+        let (bulk, (shed, otp)) = tokio::join!(bulk, otp);
+
+        assert_ok!(bulk);
+        assert!(matches!(shed, Err(ClientError::Overloaded)));
+        assert_ok!(otp);
+    }
+
+    #[tokio::test]
+    async fn dry_run_skips_the_network_and_flags_the_response() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .dry_run(true)
+            .build()
+            .unwrap();
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let response = client.send(&phone(), "hello", None, None).await.unwrap();
+
+        assert!(response.dry_run);
+        assert_eq!(response.status, Some(Status::Queued));
+        assert_eq!(response.to, Some(phone().e164_number()));
+    }
+
+    #[tokio::test]
+    async fn sends_that_may_have_reached_twilio_are_not_retried() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .retry_policy(RetryPolicy::new(3).backoff(
+                std::time::Duration::from_millis(1),
+                std::time::Duration::from_millis(1),
+            ))
+            .build()
+            .unwrap();
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(SendSmsResponse::default()))
+            .mount(&mock_server)
+            .await;
+
+        // Twilio may have sent the message before failing, and wouldn't drop a resend
+        assert_err!(client.send(&phone(), "hello", None, None).await);
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(!requests[0]
+            .headers
+            .contains_key("I-Twilio-Idempotency-Token"));
+    }
+
+    #[tokio::test]
+    async fn sends_that_never_went_out_are_retried() {
+        // Nothing listens on a port once its listener is dropped, so every attempt fails
+        // to connect
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let clock = MockClock::default();
+        let client = Client::builder()
+            .base_url(Url::parse(&format!("http://127.0.0.1:{port}")).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .retry_policy(RetryPolicy::new(3).backoff(
+                std::time::Duration::from_secs(60),
+                std::time::Duration::from_secs(60),
+            ))
+            .clock(Arc::new(clock.clone()))
+            .build()
+            .unwrap();
+
+        let started = std::time::Instant::now();
+        let outcome = client.send(&phone(), "hello", None, None).await;
+
+        assert!(matches!(outcome, Err(ClientError::Reqwest(err)) if err.is_connect()));
+        // Two retries, each after the backoff, which passes on the mock clock alone
+        assert_eq!(clock.now().timestamp(), 120);
+        assert!(started.elapsed() < std::time::Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn raw_posts_are_never_retried() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .retry_policy(RetryPolicy::new(3))
+            .build()
+            .unwrap();
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client
+            .raw_request(reqwest::Method::POST, "/v1/Things", &Params::new())
+            .await;
+
+        assert_err!(outcome);
+    }
+
+    #[derive(Debug)]
+    struct EveryoneOnWhatsApp;
+
+    #[async_trait::async_trait]
+    impl WhatsAppDirectory for EveryoneOnWhatsApp {
+        async fn whatsapp_number(&self, to: &Phone) -> Option<Phone> {
+            Some(to.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn landline_failures_fall_back_to_whatsapp() {
+        let mock_server = MockServer::start().await;
+        let fallback =
+            WhatsAppFallback::new(phone(), "HX123", std::sync::Arc::new(EveryoneOnWhatsApp));
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .whatsapp_fallback(fallback)
+            .build()
+            .unwrap();
+
+        Mock::given(body_string_contains("ContentSid=HX123"))
+            .and(body_string_contains("To=whatsapp%3A%2B254700782326"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "code": 21614,
+                "message": "'To' number is not a valid mobile number",
+                "status": 400
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.send_with_fallback(&phone(), "hello").await.unwrap();
+
+        assert!(outcome.fell_back());
+        assert_eq!(
+            outcome.trail[0],
+            FallbackStep::SmsFailed {
+                error_code: Some(21614),
+                permanent: true
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn messages_undelivered_after_the_send_fall_back_when_waiting_for_delivery() {
+        let mock_server = MockServer::start().await;
+        let fallback =
+            WhatsAppFallback::new(phone(), "HX123", std::sync::Arc::new(EveryoneOnWhatsApp))
+                .wait_for_delivery(
+                    std::time::Duration::from_millis(10),
+                    std::time::Duration::from_secs(1),
+                );
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .whatsapp_fallback(fallback)
+            .build()
+            .unwrap();
+
+        Mock::given(body_string_contains("ContentSid=HX123"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({"sid": "SM1", "status": "queued"})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        // Twilio only reports a landline once the carrier turns the message down
+        Mock::given(method("GET"))
+            .and(path("/2010-04-01/Accounts/AC123/Messages/SM1.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sid": "SM1",
+                "status": "undelivered",
+                "error_code": 30006,
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.send_with_fallback(&phone(), "hello").await.unwrap();
+
+        assert!(outcome.fell_back());
+        assert_eq!(
+            outcome.trail[..2],
+            [
+                FallbackStep::SmsAccepted,
+                FallbackStep::SmsFailed {
+                    error_code: Some(30006),
+                    permanent: true
+                }
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_checks_return_the_accepted_message() {
+        let mock_server = MockServer::start().await;
+        let fallback =
+            WhatsAppFallback::new(phone(), "HX123", std::sync::Arc::new(EveryoneOnWhatsApp))
+                .wait_for_delivery(
+                    std::time::Duration::from_millis(10),
+                    std::time::Duration::from_secs(1),
+                );
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .whatsapp_fallback(fallback)
+            .build()
+            .unwrap();
+
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({"sid": "SM1", "status": "queued"})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.send_with_fallback(&phone(), "hello").await.unwrap();
+
+        assert!(!outcome.fell_back());
+        assert_eq!(outcome.response.sid.as_deref(), Some("SM1"));
+        assert!(matches!(
+            outcome.trail[..],
+            [FallbackStep::SmsAccepted, FallbackStep::DeliveryUnknown(_)]
+        ));
+    }
+
+    #[tokio::test]
+    async fn sandbox_fallback_sends_a_plain_body_from_the_sandbox_number() {
+        let mock_server = MockServer::start().await;
+        let fallback =
+            WhatsAppFallback::new(phone(), "HX123", std::sync::Arc::new(EveryoneOnWhatsApp));
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .whatsapp_fallback(fallback)
+            .whatsapp_sandbox(WhatsAppSandbox::new("orange-tiger"))
+            .build()
+            .unwrap();
+
+        Mock::given(body_string_contains("From=whatsapp%3A%2B14155238886"))
+            .and(body_string_contains("Body=hello"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(SendSmsResponse::default()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "code": 21614,
+                "message": "'To' number is not a valid mobile number",
+                "status": 400
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.send_with_fallback(&phone(), "hello").await.unwrap();
+
+        assert!(outcome.fell_back());
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingStore(std::sync::Mutex<Vec<Receipt>>);
+
+    #[async_trait::async_trait]
+    impl ReceiptStore for RecordingStore {
+        async fn record(
+            &self,
+            receipt: Receipt,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            self.0.lock().unwrap().push(receipt);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn send_sms_records_a_receipt_for_accepted_messages() {
+        let mock_server = MockServer::start().await;
+        let store = std::sync::Arc::new(RecordingStore::default());
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .receipt_store(store.clone())
+            .build()
+            .unwrap();
+        let response = SendSmsResponse {
+            sid: Some("SM123".to_string()),
+            status: Some(Status::Queued),
+            ..Default::default()
+        };
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert_ok!(client.send(&phone(), "hello", None, None).await);
+
+        let receipts = store.0.lock().unwrap();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].message_sid, "SM123");
+        assert_eq!(receipts[0].to, phone().e164_number());
+        assert_eq!(receipts[0].status, Some(Status::Queued));
+    }
+
+    #[cfg(feature = "webhooks")]
+    #[tokio::test]
+    async fn status_transitions_are_recorded_once() {
+        let mock_server = MockServer::start().await;
+        let store = Arc::new(RecordingStore::default());
+        let clock = MockClock::new(chrono::Utc.with_ymd_and_hms(2024, 5, 6, 7, 0, 0).unwrap());
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .receipt_store(store.clone())
+            .clock(Arc::new(clock.clone()))
+            .build()
+            .unwrap();
+
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({"sid": "SM123", "status": "queued"})),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sid": "SM123",
+                "to": phone().e164_number(),
+                "status": "delivered",
+            })))
+            .mount(&mock_server)
+            .await;
+        let form = format!(
+            "MessageSid=SM123&AccountSid=AC123&To={}&MessageStatus=delivered",
+            phone().e164_number().replace('+', "%2B")
+        );
+        let callback: twilio_client::webhooks::MessageStatusCallback =
+            serde_urlencoded::from_str(&form).unwrap();
+
+        assert_ok!(client.send(&phone(), "hello", None, None).await);
+        clock.advance(chrono::Duration::seconds(5));
+        assert_ok!(client.fetch("SM123").await);
+        assert_ok!(client.fetch("SM123").await);
+        client.record_status_callback(&callback).await;
+
+        let receipts = store.0.lock().unwrap();
+        let statuses: Vec<_> = receipts.iter().map(|r| r.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![Some(Status::Queued), Some(Status::Delivered)]
+        );
+        assert_eq!(receipts[1].to, phone().e164_number());
+        assert_eq!(receipts[1].recorded_at, clock.now());
+    }
+
+    #[tokio::test]
+    async fn preflight_combines_lookup_suppression_and_country_checks() {
+        let mock_server = MockServer::start().await;
+        let suppressed = std::sync::Arc::new(InMemorySuppressionList::new());
+        let policy = PreflightPolicy::new()
+            .suppression_list(suppressed.clone())
+            .allowed_countries(["ke", "ug"])
+            .lookup_base_url(Url::parse(&mock_server.uri()).unwrap())
+            .max_sms_pumping_risk(60);
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .sender(phone())
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .preflight(policy)
+            .build()
+            .unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/v2/PhoneNumbers/%2B254700782326"))
+            .and(query_param(
+                "Fields",
+                "line_type_intelligence,sms_pumping_risk",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "phone_number": "+254700782326",
+                "line_type_intelligence": {"type": "nonFixedVoip", "carrier_name": "Acme"},
+                "sms_pumping_risk": {"sms_pumping_risk_score": 12, "number_blocked": false},
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/PhoneNumbers/%2B447400123456"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "line_type_intelligence": {"type": "mobile"},
+                "sms_pumping_risk": {"sms_pumping_risk_score": 93, "number_blocked": true},
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let preflight = client.preflight(&phone()).await.unwrap();
+        assert_eq!(preflight.verdict, Verdict::Warn);
+        assert!(preflight.should_send());
+        assert_eq!(preflight.line_type, LineType::Voip);
+        assert_eq!(preflight.sms_pumping_risk, Some(12));
+        assert_eq!(
+            preflight.reasons,
+            vec![PreflightReason::MaybeNotMobile(LineType::Voip)]
+        );
+
+        suppressed.insert(&phone());
+        let preflight = client.preflight(&phone()).await.unwrap();
+        assert_eq!(preflight.verdict, Verdict::Block);
+        assert!(preflight.reasons.contains(&PreflightReason::Suppressed));
+
+        let abroad = Phone::parse("07400123456", "GB").unwrap();
+        let preflight = client.preflight(&abroad).await.unwrap();
+        assert_eq!(preflight.verdict, Verdict::Block);
+        assert_eq!(
+            preflight.reasons,
+            vec![
+                PreflightReason::BlockedByTwilio,
+                PreflightReason::SmsPumpingRisk(93),
+                PreflightReason::CountryNotAllowed("GB".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn preflight_handles_numbers_without_a_country() {
+        let freephone = Phone::parse_with_no_country("+80012345678").unwrap();
+        let client = |policy| {
+            Client::builder()
+                .base_url(Url::parse("http://127.0.0.1:1").unwrap())
+                .sender(phone())
+                .account_sid(SecretString::from("AC123"))
+                .auth_token(SecretString::from("token"))
+                .preflight(policy)
+                .build()
+                .unwrap()
+        };
+
+        let preflight = client(PreflightPolicy::new())
+            .preflight(&freephone)
+            .await
+            .unwrap();
+        assert!(!preflight
+            .reasons
+            .iter()
+            .any(|reason| matches!(reason, PreflightReason::CountryNotAllowed(_))));
+
+        let preflight = client(PreflightPolicy::new().allowed_countries(["KE"]))
+            .preflight(&freephone)
+            .await
+            .unwrap();
+        assert!(preflight
+            .reasons
+            .contains(&PreflightReason::CountryNotAllowed("001".to_string())));
+    }
+
+    struct SendSmsBodyMatcher;
+
+    impl wiremock::Match for SendSmsBodyMatcher {
+        fn matches(&self, request: &Request) -> bool {
+            // Try to parse the body as a JSON value
+            let result: Result<serde_json::Value, _> = serde_urlencoded::from_bytes(&request.body);
+            if let Ok(body) = result {
+                // Check that all the mandatory fields are populated
+                body.get("From").is_some() && body.get("To").is_some() && body.get("Body").is_some()
+            } else {
+                // If parsing failed, do not match the request
+                false
+            }
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn responses_have_a_json_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(SendSmsResponse)).unwrap();
+
+        let properties = &schema["properties"];
+        assert_eq!(properties["date_created"]["format"], "date-time");
+        assert_eq!(properties["status"]["anyOf"][0]["$ref"], "#/$defs/Status");
+        let statuses = schema["$defs"]["Status"]["anyOf"][0]["enum"]
+            .as_array()
+            .unwrap();
+        assert!(statuses.contains(&serde_json::json!("delivered")));
     }
 }
@@ -1,25 +1,26 @@
 #[cfg(test)]
 mod tests {
     use claim::{assert_err, assert_ok};
-    use fake::faker::lorem::en::Sentence;
     use fake::{Fake, Faker};
     use reqwest::Url;
     use secrecy::{ExposeSecret, SecretString};
-    use serde::Serialize;
-    use twilio_client::verify::Client;
-    use twilio_client::Phone;
-    use wiremock::matchers::{any, header, method, path};
+    use std::sync::{Arc, Mutex};
+    use twilio_client::error::{ClientError, VerifyError};
+    use twilio_client::retry::RetryPolicy;
+    use twilio_client::stub::{LatencyProfile, StubResponses};
+    use twilio_client::verify::{
+        Client, EndUserContext, InMemoryBudgetCounter, InMemorySessionStore, LocaleFallback,
+        OtpFlowOptions, Region, ResendPolicy, SessionPolicy, SessionStore, Status,
+        VerificationBudget, VerifyEvent, VerifyEventKind, VerifyEventSink, VerifyOutcome,
+    };
+    use twilio_client::{Phone, Pseudonymizer};
+    use wiremock::matchers::{any, body_string_contains, header, method, path, query_param};
     use wiremock::{Mock, MockServer, Request, ResponseTemplate};
 
     fn generate_phone() -> (String, String) {
         (String::from("0700123456"), String::from("KE"))
     }
 
-    // Generate a random sms content
-    fn content() -> String {
-        Sentence(1..2).fake()
-    }
-
     // Generate a random user phone
     fn phone() -> Phone {
         let (number, country_id) = generate_phone();
@@ -69,8 +70,7 @@ mod tests {
     async fn send_sms_succeeds_if_the_server_returns_200() {
         let mock_server = MockServer::start().await;
         let (client, _) = twilio_verify_client(&mock_server.uri());
-        let template =
-            ResponseTemplate::new(200).set_body_json(RequestTwilioVerifyBodyMatcher::new());
+        let template = ResponseTemplate::new(200).set_body_json(serde_json::json!({}));
 
         Mock::given(any())
             .respond_with(template)
@@ -115,15 +115,815 @@ mod tests {
         assert_err!(outcome);
     }
 
-    #[derive(Serialize)]
-    struct RequestTwilioVerifyBodyMatcher;
+    #[tokio::test]
+    async fn warm_up_connects_to_the_base_url() {
+        let mock_server = MockServer::start().await;
+        let (client, _) = twilio_verify_client(&mock_server.uri());
+
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert_ok!(client.warm_up().await);
+    }
+
+    #[tokio::test]
+    async fn request_returns_max_send_attempts_with_the_reset_time() {
+        let mock_server = MockServer::start().await;
+        let (client, _) = twilio_verify_client(&mock_server.uri());
+        let template = ResponseTemplate::new(429)
+            .insert_header("Retry-After", "600")
+            .set_body_json(serde_json::json!({
+                "code": 60203,
+                "message": "Max send attempts reached",
+                "status": 429
+            }));
+
+        Mock::given(any())
+            .respond_with(template)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.request(&phone()).await;
+
+        match outcome {
+            Err(ClientError::Verify(VerifyError::MaxSendAttempts { retry_after, .. })) => {
+                assert_eq!(retry_after, Some(std::time::Duration::from_secs(600)))
+            }
+            other => panic!("unexpected outcome: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn stub_client_answers_without_credentials() {
+        let stub = StubResponses::new().respond(
+            reqwest::Method::POST,
+            "/VerificationCheck",
+            verify_response("approved", true),
+        );
+        let client = Client::builder().stub(stub).build().unwrap();
+
+        assert_ok!(client.request(&phone()).await);
+        let outcome = client
+            .verify(&phone(), SecretString::from("123456"))
+            .await
+            .unwrap();
+        assert!(outcome.valid);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stub_latency_delays_responses_and_trips_the_timeout() {
+        let stub = StubResponses::new()
+            .default_response(serde_json::json!({}))
+            .latency(LatencyProfile::fixed(std::time::Duration::from_millis(300)));
+        let client = Client::builder()
+            .stub(stub)
+            .timeout(std::time::Duration::from_secs(1))
+            .build()
+            .unwrap();
+        let started = tokio::time::Instant::now();
+
+        assert_ok!(client.request(&phone()).await);
+        assert_eq!(started.elapsed(), std::time::Duration::from_millis(300));
+
+        let slow =
+            StubResponses::new().latency(LatencyProfile::fixed(std::time::Duration::from_secs(5)));
+        let client = Client::builder()
+            .stub(slow)
+            .timeout(std::time::Duration::from_secs(1))
+            .build()
+            .unwrap();
+        let started = tokio::time::Instant::now();
+
+        assert!(matches!(
+            client.request(&phone()).await,
+            Err(ClientError::Timeout(1))
+        ));
+        assert_eq!(started.elapsed(), std::time::Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn checks_are_not_hedged() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .service_sid(SecretString::from("VA123"))
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .timeout(std::time::Duration::from_secs(5))
+            .hedge_after(std::time::Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        Mock::given(path("/v2/Services/VA123/VerificationCheck"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(verify_response("approved", true))
+                    .set_delay(std::time::Duration::from_millis(300)),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client
+            .check(&phone(), SecretString::from("123456"))
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, VerifyOutcome::Approved);
+    }
+
+    #[tokio::test]
+    async fn timed_out_checks_are_not_retried() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .service_sid(SecretString::from("VA123"))
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .timeout(std::time::Duration::from_millis(200))
+            .retry_policy(RetryPolicy::new(3).backoff(
+                std::time::Duration::from_millis(1),
+                std::time::Duration::from_millis(1),
+            ))
+            .build()
+            .unwrap();
+
+        // The lost response approved the code, so Twilio already deleted the verification
+        Mock::given(path("/v2/Services/VA123/VerificationCheck"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(verify_response("approved", true))
+                    .set_delay(std::time::Duration::from_secs(1)),
+            )
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/v2/Services/VA123/VerificationCheck"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.check(&phone(), SecretString::from("123456")).await;
+
+        assert!(matches!(outcome, Err(ClientError::Timeout(_))));
+    }
+
+    #[tokio::test]
+    async fn sessions_continue_on_another_instance_sharing_the_store() {
+        let mock_server = MockServer::start().await;
+        let store: Arc<dyn SessionStore> = Arc::new(InMemorySessionStore::new());
+        let instance = || {
+            Client::builder()
+                .base_url(Url::parse(&mock_server.uri()).unwrap())
+                .service_sid(SecretString::from("VA123"))
+                .account_sid(SecretString::from("AC123"))
+                .auth_token(SecretString::from("token"))
+                .sessions(
+                    SessionPolicy::new(store.clone())
+                        .max_sends(1)
+                        .max_attempts(2),
+                )
+                .build()
+                .unwrap()
+        };
+        let (first, second) = (instance(), instance());
+
+        Mock::given(path("/v2/Services/VA123/Verifications"))
+            .respond_with(
+                ResponseTemplate::new(201).set_body_json(verify_response("pending", false)),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/v2/Services/VA123/VerificationCheck"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(verify_response("pending", false)),
+            )
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        first.start_session("signin-1", &phone()).await.unwrap();
+        assert!(matches!(
+            second.resend_session("signin-1").await,
+            Err(ClientError::Verify(VerifyError::ResendLimitReached))
+        ));
+        for _ in 0..2 {
+            let outcome = second
+                .check_session("signin-1", SecretString::from("000000"))
+                .await
+                .unwrap();
+            assert_eq!(outcome, VerifyOutcome::Incorrect);
+        }
+
+        // Out of attempts without asking Twilio, which ends the session
+        let outcome = first
+            .check_session("signin-1", SecretString::from("123456"))
+            .await
+            .unwrap();
+        assert_eq!(outcome, VerifyOutcome::MaxAttemptsReached);
+        assert_eq!(first.session("signin-1").await.unwrap(), None);
+        assert!(matches!(
+            first.resend_session("signin-1").await,
+            Err(ClientError::Verify(VerifyError::SessionNotFound))
+        ));
+    }
+
+    fn verify_response(status: &str, valid: bool) -> serde_json::Value {
+        serde_json::json!({
+            "status": status,
+            "date_updated": "2024-05-06T07:08:09Z",
+            "account_sid": "AC",
+            "to": "+254700123456",
+            "valid": valid,
+            "sid": "VE",
+            "date_created": "2024-05-06T07:08:00Z",
+            "service_sid": "VA",
+            "channel": "sms"
+        })
+    }
+
+    #[tokio::test]
+    async fn unknown_verification_statuses_still_parse() {
+        let stub = StubResponses::new().respond(
+            reqwest::Method::POST,
+            "/VerificationCheck",
+            verify_response("under_review", false),
+        );
+        let client = Client::builder().stub(stub).build().unwrap();
+
+        let outcome = client
+            .check(&phone(), SecretString::from("123456"))
+            .await
+            .unwrap();
+        assert_eq!(outcome, VerifyOutcome::Incorrect);
+    }
+
+    #[test]
+    fn unknown_verification_statuses_keep_their_name() {
+        let status: Status = serde_json::from_value(serde_json::json!("under_review")).unwrap();
+
+        assert_eq!(status, Status::Unknown("under_review".to_string()));
+        assert_eq!(status.as_str(), "under_review");
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            serde_json::json!("under_review")
+        );
+        assert_eq!(
+            serde_json::to_value(Status::MaxAttemptsReached).unwrap(),
+            serde_json::json!("max_attempts_reached")
+        );
+    }
+
+    #[tokio::test]
+    async fn dry_run_still_validates_codes_but_skips_the_network() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .service_sid(SecretString::from("VA"))
+            .account_sid(SecretString::from("AC"))
+            .auth_token(SecretString::from("token"))
+            .code_length(6)
+            .dry_run(true)
+            .dry_run_code(SecretString::from("654321"))
+            .build()
+            .unwrap();
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        assert!(client.request(&phone()).await.unwrap().dry_run);
+        assert_err!(client.verify(&phone(), SecretString::from("123")).await);
+        let checked = client
+            .verify(&phone(), SecretString::from("123456"))
+            .await
+            .unwrap();
+        assert!(checked.dry_run && !checked.valid);
+        assert_eq!(
+            client
+                .check(&phone(), SecretString::from("123456"))
+                .await
+                .unwrap(),
+            VerifyOutcome::Incorrect
+        );
+        // Only the configured code gets through, and never as a real approval
+        assert_eq!(
+            client
+                .check(&phone(), SecretString::from("654321"))
+                .await
+                .unwrap(),
+            VerifyOutcome::DryRunApproved
+        );
+    }
+
+    // (span name, parent span name) for every span opened
+    type SpanTree = Vec<(String, Option<String>)>;
+
+    #[derive(Clone, Default)]
+    struct SpanParents(Arc<Mutex<SpanTree>>);
+
+    impl<S> tracing_subscriber::Layer<S> for SpanParents
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            _: &tracing::span::Attributes<'_>,
+            id: &tracing::span::Id,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let span = ctx.span(id).unwrap();
+            let parent = span.parent().map(|p| p.name().to_string());
+            self.0
+                .lock()
+                .unwrap()
+                .push((span.name().to_string(), parent));
+        }
+    }
+
+    #[tokio::test]
+    async fn with_span_parents_calls_under_the_given_span() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let parents = SpanParents::default();
+        let subscriber = tracing_subscriber::registry().with(parents.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let stub = StubResponses::new().default_response(verify_response("approved", true));
+        let client = Client::builder().stub(stub).build().unwrap();
+
+        let order = tracing::info_span!("order", order_id = 42);
+        let outcome = client
+            .with_span(order)
+            .check(&phone(), SecretString::from("123456"))
+            .await;
+
+        assert_eq!(outcome.unwrap(), VerifyOutcome::Approved);
+        let parents = parents.0.lock().unwrap();
+        let parent_of = |name: &str| {
+            parents
+                .iter()
+                .find(|(span, _)| span == name)
+                .and_then(|(_, parent)| parent.clone())
+        };
+        assert_eq!(
+            parent_of("Twilio Verify: Check OTP").as_deref(),
+            Some("order")
+        );
+        assert_eq!(
+            parent_of("Twilio Verify: Verify OTP").as_deref(),
+            Some("Twilio Verify: Check OTP")
+        );
+    }
+
+    #[tokio::test]
+    async fn verification_history_follows_every_page() {
+        let mock_server = MockServer::start().await;
+        let (client, service_sid) = twilio_verify_client(&mock_server.uri());
+        let attempt =
+            |sid: &str| serde_json::json!({"sid": sid, "conversion_status": "unconverted"});
+
+        Mock::given(path("/v2/Attempts"))
+            .and(query_param("Page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "attempts": [attempt("VL2")],
+                "meta": {"page": 1, "page_size": 1}
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/v2/Attempts"))
+            .and(query_param("ChannelData.To", "+254700123456"))
+            .and(query_param("VerifyServiceSid", service_sid.expose_secret()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "attempts": [attempt("VL1")],
+                "meta": {
+                    "page": 0,
+                    "page_size": 1,
+                    "next_page_url": format!("{}/v2/Attempts?Page=1", mock_server.uri())
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let since = chrono::Utc::now() - chrono::Duration::days(1);
+        let history = client.verification_history(&phone(), since).await.unwrap();
+
+        let sids: Vec<_> = history.iter().map(|a| a.sid.as_str()).collect();
+        assert_eq!(sids, ["VL1", "VL2"]);
+    }
+
+    #[tokio::test]
+    async fn check_reports_incorrect_codes() {
+        let mock_server = MockServer::start().await;
+        let (client, _) = twilio_verify_client(&mock_server.uri());
+
+        Mock::given(any())
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(verify_response("pending", false)),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.check(&phone(), SecretString::from("000000")).await;
+
+        assert_eq!(outcome.unwrap(), VerifyOutcome::Incorrect);
+    }
+
+    #[tokio::test]
+    async fn check_reports_expired_verifications() {
+        let mock_server = MockServer::start().await;
+        let (client, _) = twilio_verify_client(&mock_server.uri());
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.check(&phone(), SecretString::from("000000")).await;
+
+        assert_eq!(outcome.unwrap(), VerifyOutcome::Expired);
+    }
 
-    impl RequestTwilioVerifyBodyMatcher {
-        fn new() -> Self {
-            Self
+    #[tokio::test]
+    async fn request_with_context_forwards_the_device_ip() {
+        let mock_server = MockServer::start().await;
+        let (client, _) = twilio_verify_client(&mock_server.uri());
+
+        Mock::given(body_string_contains("DeviceIp=203.0.113.7"))
+            .and(body_string_contains("Tags="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let context = EndUserContext::new()
+            .ip("203.0.113.7".parse().unwrap())
+            .user_agent("Mozilla/5.0");
+        let outcome = client.request_with_context(&phone(), &context).await;
+
+        assert_ok!(outcome);
+    }
+
+    #[tokio::test]
+    async fn request_with_context_sends_the_fallback_locale() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .service_sid(SecretString::from("VA123"))
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .locale_fallback(LocaleFallback::new().chain("sw-KE", ["sw", "en-GB"]))
+            .build()
+            .unwrap();
+
+        Mock::given(body_string_contains("Locale=en-GB"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let context = EndUserContext::new().locale("sw-KE");
+        let outcome = client.request_with_context(&phone(), &context).await;
+
+        assert_ok!(outcome);
+    }
+
+    #[tokio::test]
+    async fn otp_flow_resends_until_a_code_is_approved() {
+        let mock_server = MockServer::start().await;
+        let (client, service_sid) = twilio_verify_client(&mock_server.uri());
+
+        Mock::given(path(format!(
+            "/v2/Services/{}/Verifications",
+            service_sid.expose_secret()
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(2..)
+        .mount(&mock_server)
+        .await;
+        Mock::given(path(format!(
+            "/v2/Services/{}/VerificationCheck",
+            service_sid.expose_secret()
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_json(verify_response("approved", true)))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+        let options = OtpFlowOptions {
+            deadline: std::time::Duration::from_secs(5),
+            resend_interval: std::time::Duration::from_millis(50),
+            max_resends: 2,
+        };
+        let outcome = client
+            .request_and_wait_for_check(
+                &phone(),
+                || async {
+                    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                    Some(SecretString::from("123456"))
+                },
+                options,
+            )
+            .await;
+
+        assert_eq!(outcome.unwrap(), VerifyOutcome::Approved);
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingSink(Mutex<Vec<VerifyEventKind>>);
+
+    impl VerifyEventSink for RecordingSink {
+        fn record(&self, event: VerifyEvent) {
+            self.0.lock().unwrap().push(event.kind);
         }
     }
 
+    #[tokio::test]
+    async fn verify_emits_expired_event_when_the_server_returns_404() {
+        let mock_server = MockServer::start().await;
+        let sink = Arc::new(RecordingSink::default());
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .service_sid(SecretString::from(Faker.fake::<String>()))
+            .account_sid(SecretString::from(Faker.fake::<String>()))
+            .auth_token(SecretString::from(Faker.fake::<String>()))
+            .event_sink(sink.clone())
+            .build()
+            .unwrap();
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.verify(&phone(), SecretString::from("123456")).await;
+
+        assert_err!(outcome);
+        assert_eq!(
+            *sink.0.lock().unwrap(),
+            vec![VerifyEventKind::CodeChecked, VerifyEventKind::Expired]
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_entity_reports_whether_the_entity_existed() {
+        let mock_server = MockServer::start().await;
+        let (client, service_sid) = twilio_verify_client(&mock_server.uri());
+
+        Mock::given(method("DELETE"))
+            .and(path(format!(
+                "/v2/Services/{}/Entities/user-42",
+                service_sid.expose_secret()
+            )))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "code": 20404,
+                "message": "The requested resource was not found"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        assert!(client.delete_entity("user-42").await.unwrap());
+        assert!(!client.delete_entity("user-43").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn validate_configuration_reports_an_unknown_service() {
+        let mock_server = MockServer::start().await;
+        let (client, _) = twilio_verify_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "code": 20404,
+                "message": "The requested resource was not found"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let report = client.validate_configuration().await;
+
+        assert!(!report.is_ok());
+        assert!(report.findings()[0].message.contains("does not exist"));
+    }
+
+    #[tokio::test]
+    async fn resends_inside_the_policy_interval_are_refused() {
+        let client = Client::builder()
+            .stub(StubResponses::new().default_response(serde_json::json!({})))
+            .resend_policy(ResendPolicy::new(std::time::Duration::from_secs(60)))
+            .build()
+            .unwrap();
+
+        assert_ok!(client.request(&phone()).await);
+        let resend = client.request(&phone()).await;
+
+        assert!(matches!(
+            resend,
+            Err(ClientError::Verify(VerifyError::ResendTooSoon { retry_in }))
+                if retry_in <= std::time::Duration::from_secs(60)
+        ));
+    }
+
+    #[tokio::test]
+    async fn simultaneous_resends_send_one_code() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .service_sid(SecretString::from("VA123"))
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .resend_policy(ResendPolicy::new(std::time::Duration::from_secs(60)))
+            .build()
+            .unwrap();
+
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({}))
+                    .set_delay(std::time::Duration::from_millis(200)),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let to = phone();
+        let (first, second) = tokio::join!(client.request(&to), client.request(&to));
+
+        assert!(first.is_ok() != second.is_ok());
+        assert!([first, second].into_iter().any(|outcome| matches!(
+            outcome,
+            Err(ClientError::Verify(VerifyError::ResendTooSoon { .. }))
+        )));
+    }
+
+    #[tokio::test]
+    async fn failed_requests_do_not_count_towards_the_resend_policy() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(Url::parse(&mock_server.uri()).unwrap())
+            .service_sid(SecretString::from("VA123"))
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .resend_policy(ResendPolicy::new(std::time::Duration::from_secs(60)))
+            .build()
+            .unwrap();
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({})))
+            .mount(&mock_server)
+            .await;
+
+        assert_err!(client.request(&phone()).await);
+        assert_ok!(client.request(&phone()).await);
+    }
+
+    #[tokio::test]
+    async fn requests_over_a_tenant_budget_are_refused() {
+        let budget = VerificationBudget::new(Arc::new(InMemoryBudgetCounter::new())).per_hour(1);
+        let client = Client::builder()
+            .stub(StubResponses::new().default_response(serde_json::json!({})))
+            .budget(budget)
+            .build()
+            .unwrap();
+        let acme = EndUserContext::new().tenant("acme");
+
+        assert_ok!(client.request_with_context(&phone(), &acme).await);
+        let over_budget = client.request_with_context(&phone(), &acme).await;
+
+        assert!(matches!(
+            over_budget,
+            Err(ClientError::Verify(VerifyError::BudgetExceeded { tenant: Some(tenant), limit: 1, .. }))
+                if tenant == "acme"
+        ));
+        let globex = EndUserContext::new().tenant("globex");
+        assert_ok!(client.request_with_context(&phone(), &globex).await);
+    }
+
+    #[tokio::test]
+    async fn unreachable_primary_region_fails_over_to_the_secondary() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            // Nothing listens on port 1, so connecting fails straight away
+            .region(Region::new(
+                "us1",
+                Url::parse("http://127.0.0.1:1").unwrap(),
+            ))
+            .failover_region(Region::new("ie1", Url::parse(&mock_server.uri()).unwrap()))
+            .service_sid(SecretString::from("VA123"))
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .build()
+            .unwrap();
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let resp = assert_ok!(client.request(&phone()).await);
+
+        assert_eq!(resp.served_by.as_deref(), Some("ie1"));
+    }
+
+    #[tokio::test]
+    async fn code_requests_that_timed_out_do_not_fail_over() {
+        let primary = MockServer::start().await;
+        let secondary = MockServer::start().await;
+        let client = Client::builder()
+            .region(Region::new("us1", Url::parse(&primary.uri()).unwrap()))
+            .failover_region(Region::new("ie1", Url::parse(&secondary.uri()).unwrap()))
+            .service_sid(SecretString::from("VA123"))
+            .account_sid(SecretString::from("AC123"))
+            .auth_token(SecretString::from("token"))
+            .timeout(std::time::Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        // The primary may still send the code after the client gave up on it
+        Mock::given(any())
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({}))
+                    .set_delay(std::time::Duration::from_secs(1)),
+            )
+            .expect(1)
+            .mount(&primary)
+            .await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(0)
+            .mount(&secondary)
+            .await;
+
+        let outcome = client.request(&phone()).await;
+
+        assert!(matches!(outcome, Err(ClientError::Timeout(_))));
+    }
+
+    #[derive(Debug, Default)]
+    struct PhoneHashSink(Mutex<Vec<Option<String>>>);
+
+    impl VerifyEventSink for PhoneHashSink {
+        fn record(&self, event: VerifyEvent) {
+            self.0.lock().unwrap().push(event.phone_hash);
+        }
+    }
+
+    #[tokio::test]
+    async fn events_carry_the_pseudonym_when_a_pseudonymizer_is_set() {
+        let sink = Arc::new(PhoneHashSink::default());
+        let pseudonymizer = Pseudonymizer::new(SecretString::from("analytics-key"));
+        let client = Client::builder()
+            .stub(StubResponses::new().default_response(serde_json::json!({})))
+            .event_sink(sink.clone())
+            .pseudonymizer(pseudonymizer.clone())
+            .build()
+            .unwrap();
+
+        let _ = client.request(&phone()).await;
+
+        let hashes = sink.0.lock().unwrap();
+        assert_eq!(*hashes, vec![Some(pseudonymizer.pseudonym(&phone()))]);
+    }
+
+    #[tokio::test]
+    async fn events_leave_the_phone_out_without_a_pseudonymizer() {
+        let sink = Arc::new(PhoneHashSink::default());
+        let client = Client::builder()
+            .stub(StubResponses::new().default_response(serde_json::json!({})))
+            .event_sink(sink.clone())
+            .build()
+            .unwrap();
+
+        let _ = client.request(&phone()).await;
+
+        assert_eq!(*sink.0.lock().unwrap(), vec![None]);
+    }
+
+    struct RequestTwilioVerifyBodyMatcher;
+
     impl wiremock::Match for RequestTwilioVerifyBodyMatcher {
         fn matches(&self, request: &Request) -> bool {
             // Try to parse the body as a JSON value